@@ -0,0 +1,60 @@
+// src/ack.rs
+// `timerterm ack [name]` leaves a marker file that a nagging timer of the
+// same name picks up, so alerts can be acknowledged from another terminal.
+use std::io;
+use std::path::PathBuf;
+
+pub const DEFAULT_NAME: &str = "timer";
+
+// $XDG_RUNTIME_DIR/timerterm, or a per-user dir under the system temp dir
+pub fn dir() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR").filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir).join("timerterm"),
+        None => std::env::temp_dir().join(format!("timerterm-{}", crate::platform::user_id())),
+    }
+}
+
+// Names end up in a file name, so keep them to a safe character set
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn marker(name: &str) -> PathBuf {
+    dir().join(format!("{}.ack", name))
+}
+
+pub fn acknowledge(name: &str) -> io::Result<()> {
+    std::fs::create_dir_all(dir())?;
+    std::fs::write(marker(name), b"")
+}
+
+// True (and consumes the marker) if `name` was acknowledged
+pub fn take(name: &str) -> bool {
+    std::fs::remove_file(marker(name)).is_ok()
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_file_safe() {
+        // Test: Plain names pass, paths and empties don't
+        assert!(is_valid_name("laundry"));
+        assert!(is_valid_name("meds-am_2"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("../etc"));
+        assert!(!is_valid_name("a b"));
+    }
+
+    #[test]
+    fn take_consumes_acknowledgment() {
+        // Test: An ack is seen exactly once
+        let name = format!("unit-test-{}", std::process::id());
+        assert!(!take(&name));
+        acknowledge(&name).unwrap();
+        assert!(take(&name));
+        assert!(!take(&name));
+    }
+}
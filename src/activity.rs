@@ -0,0 +1,60 @@
+// src/activity.rs
+// Things to do on a pomodoro break, from [break_activities] in the config.
+// An activity with a timer (from [break_timers]) makes way for the next one
+// when its time is up, one without stays until the break ends.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Activity {
+    pub name: String,      // Its config key, what [break_timers] refers to
+    pub text: String,      // What's shown, e.g. "Look 20ft away"
+    pub secs: Option<u32>, // Its own countdown, never zero
+}
+
+// The activity showing `elapsed` secs into a break that started on
+// activities[start], and how long its countdown has left. None without any.
+pub fn current(activities: &[Activity], start: usize, elapsed: u64) -> Option<(&Activity, Option<u64>)> {
+    if activities.is_empty() { return None; }
+    let (mut i, mut elapsed) = (start, elapsed);
+    loop {
+        let activity = &activities[i % activities.len()];
+        match activity.secs.map(u64::from) {
+            None => return Some((activity, None)),
+            Some(secs) if elapsed < secs => return Some((activity, Some(secs - elapsed))),
+            Some(secs) => {
+                elapsed -= secs;
+                i += 1;
+            }
+        }
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity(text: &str, secs: Option<u32>) -> Activity {
+        Activity { name: text.to_string(), text: text.to_string(), secs }
+    }
+
+    #[test]
+    fn timed_activities_hand_over_when_done() {
+        let list = [activity("eyes", Some(20)), activity("water", None), activity("squats", Some(30))];
+        // Test: The first one counts down, then the untimed one stays for good
+        assert_eq!(current(&list, 0, 0), Some((&list[0], Some(20))));
+        assert_eq!(current(&list, 0, 19), Some((&list[0], Some(1))));
+        assert_eq!(current(&list, 0, 20), Some((&list[1], None)));
+        assert_eq!(current(&list, 0, 600), Some((&list[1], None)));
+        // Test: A later break starts further along, wrapping round the list
+        assert_eq!(current(&list, 2, 35), Some((&list[0], Some(15))));
+        assert_eq!(current(&[], 0, 0), None);
+    }
+
+    #[test]
+    fn all_timed_activities_cycle() {
+        // Test: With every activity timed the list just keeps going round
+        let list = [activity("stretch", Some(10)), activity("breathe", Some(5))];
+        assert_eq!(current(&list, 0, 31), Some((&list[0], Some(9))));
+        assert_eq!(current(&list, 1, 4), Some((&list[1], Some(1))));
+    }
+}
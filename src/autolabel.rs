@@ -0,0 +1,94 @@
+// src/autolabel.rs
+// Labels for runs started without --label, so the history still says what
+// each one was for. Off unless `timer.auto_label` in the config picks a source.
+// Separately, a run inside tmux is always tagged with its session and window.
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AutoLabel {
+    #[default]
+    Off,
+    Tmux, // The tmux pane title, else the directory
+    Dir,  // The working directory's name
+}
+
+impl AutoLabel {
+    pub const NAMES: [&'static str; 3] = ["off", "tmux", "dir"];
+
+    pub fn parse(name: &str) -> Option<AutoLabel> {
+        match name {
+            "off" => Some(AutoLabel::Off),
+            "tmux" => Some(AutoLabel::Tmux),
+            "dir" => Some(AutoLabel::Dir),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> Option<String> {
+        let dir = || std::env::current_dir().ok().and_then(|dir| dir_name(&dir));
+        match self {
+            AutoLabel::Off => None,
+            AutoLabel::Tmux => tmux_title().or_else(dir),
+            AutoLabel::Dir => dir(),
+        }
+    }
+}
+
+// Only asked when running inside tmux, a missing or failing tmux is None
+fn tmux_title() -> Option<String> {
+    std::env::var_os("TMUX").filter(|v| !v.is_empty())?;
+    let out = Command::new("tmux").args(["display-message", "-p", "#{pane_title}\t#{host}\t#{host_short}"]).output().ok()?;
+    if !out.status.success() { return None; }
+    pane_title(&String::from_utf8_lossy(&out.stdout))
+}
+
+// "thesis:vim", the tmux session and window the run is in, for the tag=
+// on its START line. None outside tmux.
+pub fn tmux_workspace() -> Option<String> {
+    std::env::var_os("TMUX").filter(|v| !v.is_empty())?;
+    let out = Command::new("tmux").args(["display-message", "-p", "#S:#W"]).output().ok()?;
+    if !out.status.success() { return None; }
+    Some(String::from_utf8_lossy(&out.stdout).trim_end_matches(['\r', '\n']).to_string()).filter(|tag| !tag.is_empty())
+}
+
+// tmux titles every pane with the host name until something sets one, which
+// says nothing about the run
+fn pane_title(output: &str) -> Option<String> {
+    let mut fields = output.trim_end_matches(['\r', '\n']).split('\t');
+    let title = fields.next()?.trim();
+    if title.is_empty() || fields.any(|host| host == title) { return None; }
+    Some(title.to_string())
+}
+
+fn dir_name(dir: &Path) -> Option<String> {
+    dir.file_name().map(|name| name.to_string_lossy().into_owned())
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_knows_each_source() {
+        assert!(AutoLabel::NAMES.iter().all(|n| AutoLabel::parse(n).is_some()));
+        assert_eq!(AutoLabel::parse("pwd"), None);
+        assert_eq!(AutoLabel::Off.label(), None);
+    }
+
+    #[test]
+    fn pane_title_skips_the_default() {
+        // Test: A title something set is used, the host name tmux starts with isn't
+        assert_eq!(pane_title("vim notes.md\tbox.lan\tbox\n").as_deref(), Some("vim notes.md"));
+        assert_eq!(pane_title("box.lan\tbox.lan\tbox\n"), None);
+        assert_eq!(pane_title("box\tbox.lan\tbox\n"), None);
+        assert_eq!(pane_title("\tbox.lan\tbox\n"), None);
+    }
+
+    #[test]
+    fn dir_name_is_the_last_component() {
+        assert_eq!(dir_name(Path::new("/home/me/thesis")).as_deref(), Some("thesis"));
+        assert_eq!(dir_name(Path::new("/")), None);
+    }
+}
@@ -0,0 +1,212 @@
+// src/calendar.rs
+// Read-only busy detection from a local .ics file.
+// Only handles plain VEVENTs: RRULE recurrences and all-day events are skipped.
+// TZID times are read in that zone from the system's tz files (see zone.rs),
+// or as local time for a TZID that isn't there, like Outlook's Windows names.
+use std::collections::HashMap;
+
+use crate::zone::Zone;
+
+// One busy block, times are UNIX seconds
+#[derive(Debug, Clone, PartialEq)]
+pub struct Meeting {
+    pub summary: String,
+    pub start: i64,
+    pub end: i64,
+}
+
+// Join RFC 5545 folded lines (continuations start with a space or tab)
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.lines() {
+        let raw = raw.trim_end_matches('\r');
+        match (raw.strip_prefix(' ').or_else(|| raw.strip_prefix('\t')), lines.last_mut()) {
+            (Some(cont), Some(last)) => last.push_str(cont),
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+fn unescape(value: &str) -> String {
+    value.replace("\\n", " ").replace("\\N", " ").replace("\\,", ",").replace("\\;", ";").replace("\\\\", "\\")
+}
+
+// Days since 1970-01-01 for a civil date (Howard Hinnant's algorithm)
+pub fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+// Civil date of a day count since 1970-01-01, the inverse of days_from_civil
+pub fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let (d, m) = (doy - (153 * mp + 2) / 5 + 1, if mp < 10 { mp + 3 } else { mp - 9 });
+    (yoe + era * 400 + if m <= 2 { 1 } else { 0 }, m, d)
+}
+
+// Local wall-clock time to UNIX seconds, letting libc work out DST
+pub fn local_to_unix(y: i64, mo: i64, d: i64, h: i64, mi: i64, s: i64) -> Option<i64> {
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    tm.tm_year = (y - 1900) as i32;
+    tm.tm_mon = (mo - 1) as i32;
+    tm.tm_mday = d as i32;
+    tm.tm_hour = h as i32;
+    tm.tm_min = mi as i32;
+    tm.tm_sec = s as i32;
+    tm.tm_isdst = -1;
+    crate::platform::make_time(&mut tm)
+}
+
+// Parse "20250601T090000Z" (UTC) or "20250601T090000" (local).
+// Date-only values (all-day events) return None.
+pub fn parse_datetime(value: &str) -> Option<i64> {
+    parse_datetime_in(value, None)
+}
+
+// Like parse_datetime, with a time that isn't UTC read in `zone` if given
+pub fn parse_datetime_in(value: &str, zone: Option<&Zone>) -> Option<i64> {
+    let (date, time) = value.split_once('T')?;
+    let utc = time.ends_with('Z');
+    let time = time.trim_end_matches('Z');
+    if date.len() != 8 || time.len() != 6 || !date.chars().chain(time.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let num = |s: &str| s.parse::<i64>().ok();
+    let (y, mo, d) = (num(&date[..4])?, num(&date[4..6])?, num(&date[6..])?);
+    let (h, mi, s) = (num(&time[..2])?, num(&time[2..4])?, num(&time[4..])?);
+    let wall = days_from_civil(y, mo, d) * 86400 + h * 3600 + mi * 60 + s;
+    match (utc, zone) {
+        (true, _) => Some(wall),
+        (false, Some(zone)) => Some(wall_to_unix(wall, |at| zone.offset(at))),
+        (false, None) => local_to_unix(y, mo, d, h, mi, s),
+    }
+}
+
+// A wall-clock time (as if UTC) to UNIX seconds, given the zone's offset at
+// each instant. Taking the offset again at the first guess gets the side of
+// a DST change the time is on.
+fn wall_to_unix(wall: i64, offset: impl Fn(i64) -> i32) -> i64 {
+    let guess = wall - offset(wall) as i64;
+    wall - offset(guess) as i64
+}
+
+pub fn parse_ics(text: &str) -> Vec<Meeting> {
+    let mut meetings = Vec::new();
+    let (mut in_event, mut summary, mut start, mut end, mut recurring) = (false, String::new(), None, None, false);
+    // Each TZID loaded once, None for one that couldn't be
+    let mut zones: HashMap<String, Option<Zone>> = HashMap::new();
+
+    for line in unfold(text) {
+        let Some((name, value)) = line.split_once(':') else { continue };
+        // Of the property parameters only TZID matters, "DTSTART;TZID=Europe/Berlin"
+        let mut params = name.split(';');
+        let name = params.next().unwrap_or("").to_ascii_uppercase();
+        let tzid = params.find_map(|param| param.strip_prefix("TZID=")).map(|tzid| tzid.trim_matches('"'));
+        let zone = tzid.and_then(|tzid| zones.entry(tzid.to_string()).or_insert_with(|| Zone::load(tzid).ok()).as_ref());
+        match (name.as_str(), value) {
+            ("BEGIN", "VEVENT") => {
+                (in_event, summary, start, end, recurring) = (true, String::new(), None, None, false);
+            }
+            ("END", "VEVENT") if in_event => {
+                if let (Some(start), Some(end), false) = (start, end, recurring) {
+                    meetings.push(Meeting { summary: summary.clone(), start, end });
+                }
+                in_event = false;
+            }
+            ("SUMMARY", v) if in_event => summary = unescape(v),
+            ("DTSTART", v) if in_event => start = parse_datetime_in(v, zone),
+            ("DTEND", v) if in_event => end = parse_datetime_in(v, zone),
+            ("RRULE", _) if in_event => recurring = true,
+            _ => {}
+        }
+    }
+    meetings
+}
+
+// First meeting starting while a timer of `duration` secs begun at `now` is running
+pub fn next_conflict(meetings: &[Meeting], now: i64, duration: i64) -> Option<&Meeting> {
+    meetings.iter()
+        .filter(|m| m.start >= now && m.start < now + duration)
+        .min_by_key(|m| m.start)
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICS: &str = "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:Stand\r\n up\\, daily\r\n\
+DTSTART:20250601T090000Z\r\n\
+DTEND:20250601T091500Z\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:Weekly\r\n\
+RRULE:FREQ=WEEKLY\r\n\
+DTSTART:20250601T100000Z\r\n\
+DTEND:20250601T110000Z\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:Holiday\r\n\
+DTSTART;VALUE=DATE:20250602\r\n\
+DTEND;VALUE=DATE:20250603\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    #[test]
+    fn parse_datetime_handles_utc() {
+        // Test: 2025-06-01 09:00:00 UTC
+        assert_eq!(parse_datetime("20250601T090000Z"), Some(1748768400));
+        assert_eq!(parse_datetime("19700101T000000Z"), Some(0));
+        // Test: All-day dates and junk are rejected
+        assert_eq!(parse_datetime("20250601"), None);
+        assert_eq!(parse_datetime("2025-06-01T09:00:00Z"), None);
+    }
+
+    #[test]
+    fn wall_to_unix_finds_the_offset_in_force() {
+        let new_york = crate::zone::Rule::parse("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        let wall = |value: &str| parse_datetime(&format!("{}Z", value)).unwrap();
+        // Test: 09:00 in New York is 14:00 UTC in January and 13:00 in June
+        assert_eq!(wall_to_unix(wall("20250115T090000"), |at| new_york.offset(at)), wall("20250115T140000"));
+        assert_eq!(wall_to_unix(wall("20250601T090000"), |at| new_york.offset(at)), wall("20250601T130000"));
+        // Test: The morning clocks go forward, 08:00 is already EDT
+        assert_eq!(wall_to_unix(wall("20250309T080000"), |at| new_york.offset(at)), wall("20250309T120000"));
+    }
+
+    #[test]
+    fn parse_ics_reads_plain_events_only() {
+        // Test: Folded summary is joined and unescaped, RRULE/all-day events skipped
+        let meetings = parse_ics(ICS);
+        assert_eq!(meetings, vec![Meeting {
+            summary: "Standup, daily".to_string(),
+            start: 1748768400,
+            end: 1748768400 + 900,
+        }]);
+    }
+
+    #[test]
+    fn next_conflict_finds_earliest_overlap() {
+        let meetings = vec![
+            Meeting { summary: "late".into(), start: 2000, end: 2100 },
+            Meeting { summary: "soon".into(), start: 1500, end: 1600 },
+            Meeting { summary: "past".into(), start: 500, end: 900 },
+        ];
+        // Test: 25m (1500s) timer from t=1000 runs into both, "soon" comes first
+        assert_eq!(next_conflict(&meetings, 1000, 1500).unwrap().summary, "soon");
+        // Test: Short timer ends before anything starts
+        assert_eq!(next_conflict(&meetings, 1000, 300), None);
+    }
+}
@@ -1,53 +1,1173 @@
 // src/cli.rs
+use crate::{ack, calendar, platform};
+use crate::cron::Cron;
+use crate::hook::OrphanPolicy;
+use crate::trigger::Trigger;
+use crate::pace::Distance;
+use crate::power::PowerSave;
+use crate::render::{format_duration, Layout};
 
-fn parse_time_fmt(time_str: &str) -> Option<u32> {
-    // Handle ss format
-    if !time_str.contains(':') { return time_str.parse().ok(); }
+pub fn parse_time_fmt(time_str: &str) -> Option<u32> {
+    parse_duration(time_str).ok()
+}
+
+// Digits typed on the --keypad screen, read from the right as seconds,
+// minutes and hours like a microwave's: "2500" is 25:00 and "90" is 1:30
+pub fn keypad_duration(digits: &str) -> u32 {
+    let value: u32 = digits.parse().unwrap_or(0);
+    value / 10000 * 3600 + value / 100 % 100 * 60 + value % 100
+}
+
+// Duration in seconds from "90" (bare numbers are secs), "mm:ss", "hh:mm:ss",
+// or units like "1h30m", "90s", "2h" and "45m10s".
+// Errors name the exact token that didn't parse.
+pub fn parse_duration(time_str: &str) -> Result<u32, String> {
+    if time_str.contains(':') { return parse_colons(time_str); }
+    if !time_str.is_empty() && time_str.bytes().all(|b| b.is_ascii_digit()) {
+        return time_str.parse().map_err(|_| format!("'{}' is too large", time_str));
+    }
+    parse_units(time_str)
+}
 
+fn parse_colons(time_str: &str) -> Result<u32, String> {
     let time_units: Vec<&str> = time_str.split(':').collect();
+    // Largest unit first: [hh:]mm:ss
+    let scales: &[u32] = match time_units.len() {
+        2 => &[60, 1],
+        3 => &[3600, 60, 1],
+        _ => return Err(format!("'{}' should be mm:ss or hh:mm:ss", time_str)),
+    };
+    let mut total: u32 = 0;
+    for (unit, scale) in time_units.iter().zip(scales) {
+        let n: u32 = match unit.parse() {
+            Ok(n) if !unit.starts_with('+') => n,
+            _ => return Err(format!("'{}' in '{}' isn't a number", unit, time_str)),
+        };
+        total = n.checked_mul(*scale).and_then(|n| total.checked_add(n))
+            .ok_or_else(|| format!("'{}' is too large", time_str))?;
+    }
+    Ok(total)
+}
+
+fn parse_units(time_str: &str) -> Result<u32, String> {
+    if time_str.is_empty() { return Err("empty duration".to_string()); }
+    let (mut total, mut prev_scale, mut rest): (u32, u32, &str) = (0, u32::MAX, time_str);
+    while !rest.is_empty() {
+        // A token is a run of digits then a run of anything else, e.g. "30m"
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let end = rest[digits..].find(|c: char| c.is_ascii_digit()).map_or(rest.len(), |i| digits + i);
+        let (token, tail) = rest.split_at(end);
+        let (num, unit) = token.split_at(digits);
+        let err = |why: &str| Err(format!("'{}' in '{}' {}", token, time_str, why));
+
+        let scale = match unit {
+            "h" | "H" => 3600,
+            "m" | "M" => 60,
+            "s" | "S" => 1,
+            "" => return err("needs a unit (h, m or s)"),
+            _ => return err("has an unknown unit, expected h, m or s"),
+        };
+        if num.is_empty() { return err("is missing a number"); }
+        // Each unit once, largest first, so "30m1h" or "1m1m" are typos
+        if scale >= prev_scale { return err("repeats a unit or comes after a smaller one"); }
+        let n: u32 = num.parse().or_else(|_| err("is too large"))?;
+        total = n.checked_mul(scale).and_then(|n| total.checked_add(n))
+            .ok_or_else(|| format!("'{}' is too large", time_str))?;
+        (prev_scale, rest) = (scale, tail);
+    }
+    Ok(total)
+}
+
+// Why a countdown of `secs` looks like a typo, if it does
+pub fn suspicious_duration(secs: u32, max: u32) -> Option<String> {
+    if secs == 0 { return Some("A 0:00 timer ends immediately".to_string()); }
+    if secs > max {
+        return Some(format!("{} is longer than {}", format_duration(secs as u64), format_duration(max as u64)));
+    }
+    None
+}
 
-    match time_units.len() {
-        2 => { // handle mm:ss format
-            let mins = time_units[0].parse::<u32>().ok()?;
-            let secs = time_units[1].parse::<u32>().ok()?;
-            Some(mins * 60 + secs)
+// A wall-clock moment to count down to, from --until "14:30" (the next time
+// the clock reads it) or "2025-07-01T09:00". Local time unless it ends in "Z"
+// or an offset like "+02:00".
+#[derive(Debug, Clone, PartialEq)]
+pub struct Until {
+    text: String,                  // As given, for error messages
+    date: Option<(i32, u8, u8)>, // Year, month, day
+    secs: u32,                   // Seconds past midnight
+    offset: Option<i32>,         // Seconds east of UTC, None for local time
+}
+
+impl Until {
+    pub fn parse(value: &str) -> Result<Until, String> {
+        let bad = || format!("--until must be HH:MM[:SS] or YYYY-MM-DDTHH:MM[:SS], not '{}'", value);
+        let (date, time) = match value.split_once('T') {
+            Some((date, time)) => (Some(date), time),
+            None => (None, value),
+        };
+        let (time, offset) = match time.find(['Z', '+', '-']) {
+            Some(i) => (&time[..i], Some(parse_offset(&time[i..]).ok_or_else(bad)?)),
+            None => (time, None),
+        };
+        let fields = |s: &str, sep: char| -> Option<Vec<i64>> {
+            s.split(sep).map(|f| if !f.is_empty() && f.bytes().all(|b| b.is_ascii_digit()) { f.parse().ok() } else { None }).collect()
+        };
+        let (h, m, sec) = match fields(time, ':').ok_or_else(bad)?[..] {
+            [h, m] => (h, m, 0),
+            [h, m, sec] => (h, m, sec),
+            _ => return Err(bad()),
+        };
+        if h > 23 || m > 59 || sec > 59 { return Err(format!("'{}' isn't a time of day", time)); }
+        let date = match date {
+            Some(date) => match fields(date, '-').ok_or_else(bad)?[..] {
+                [y, mo, d] if y <= 9999 && (1..=12).contains(&mo) && d >= 1 && d <= days_in_month(y, mo) => Some((y as i32, mo as u8, d as u8)),
+                [_, _, _] => return Err(format!("'{}' isn't a date", date)),
+                _ => return Err(bad()),
+            },
+            None => None,
+        };
+        Ok(Until { text: value.to_string(), date, secs: (h * 3600 + m * 60 + sec) as u32, offset: offset.map(|o| o as i32) })
+    }
+
+    // Seconds from `now` (UNIX seconds) until the moment. A time alone is
+    // today's or, once that's passed, tomorrow's; a date must be ahead.
+    pub fn secs_from(&self, now: i64) -> Result<u32, String> {
+        let (secs, offset) = (self.secs as i64, self.offset.map(i64::from));
+        let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+        let at = |(y, mo, d): (i64, i64, i64)| match offset {
+            Some(offset) => Some(calendar::days_from_civil(y, mo, d) * 86400 + secs - offset),
+            None => calendar::local_to_unix(y, mo, d, h, m, s),
+        };
+        let target = match self.date {
+            Some((y, mo, d)) => at((y as i64, mo as i64, d as i64)),
+            None => {
+                let today = match offset {
+                    Some(offset) => calendar::civil_from_days((now + offset).div_euclid(86400)),
+                    None => platform::local_time(now).map(|tm| (tm.tm_year as i64 + 1900, tm.tm_mon as i64 + 1, tm.tm_mday as i64))
+                        .ok_or_else(|| "couldn't read the local time".to_string())?,
+                };
+                // Tomorrow may be day 32, which mktime and days_from_civil both roll over
+                at(today).filter(|&t| t > now).or_else(|| at((today.0, today.1, today.2 + 1)))
+            }
+        };
+        let target = target.ok_or_else(|| format!("'{}' doesn't exist in local time", self.text))?;
+        if target <= now { return Err(format!("'{}' has already passed", self.text)); }
+        u32::try_from(target - now).map_err(|_| format!("'{}' is too far away", self.text))
+    }
+}
+
+// "Z", "+02:00", "-0530" as seconds east of UTC
+fn parse_offset(s: &str) -> Option<i64> {
+    if s == "Z" { return Some(0); }
+    let (sign, rest) = match s.split_at(1) {
+        ("+", rest) => (1, rest),
+        ("-", rest) => (-1, rest),
+        _ => return None,
+    };
+    let digits = rest.replace(':', "");
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) { return None; }
+    let (h, m): (i64, i64) = (digits[..2].parse().ok()?, digits[2..].parse().ok()?);
+    if h > 23 || m > 59 { return None; }
+    Some(sign * (h * 3600 + m * 60))
+}
+
+fn days_in_month(y: i64, m: i64) -> i64 {
+    match m {
+        2 if y % 4 == 0 && (y % 100 != 0 || y % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// How many times --repeat runs the countdown
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Repeat {
+    Times(u32), // At least 1
+    Forever,    // Until quit
+}
+
+impl Repeat {
+    pub fn parse(value: &str) -> Result<Repeat, String> {
+        match value {
+            "forever" => Ok(Repeat::Forever),
+            _ => match value.parse::<u32>() {
+                Ok(0) => Err("--repeat needs at least 1 cycle".to_string()),
+                Ok(n) => Ok(Repeat::Times(n)),
+                Err(_) => Err(format!("--repeat must be a number of cycles or 'forever', not '{}'", value)),
+            },
         }
-        3 => { // handle hh:mm:ss format
-            let hrs = time_units[0].parse::<u32>().ok()?;
-            let mins = time_units[1].parse::<u32>().ok()?;
-            let secs = time_units[2].parse::<u32>().ok()?;
-            Some(hrs * 3600 + mins * 60 + secs)
+    }
+
+    pub fn is_last(self, cycle: u32) -> bool {
+        self == Repeat::Times(cycle)
+    }
+
+    // Shown under the time, "Cycle 2 of 3" or just "Cycle 2" forever
+    pub fn counter(self, cycle: u32) -> String {
+        match self {
+            Repeat::Times(n) => format!("Cycle {} of {}", cycle, n),
+            Repeat::Forever => format!("Cycle {}", cycle),
         }
-        _ => None, // Invalid format
     }
 }
 
-pub fn parse_args(args: Vec<String>) -> Option<u32> {
-    // TODO: Consider using a defaults module or struct for default values
-    match args.len() {
-        1 => Some(600), // Default to 10 minutes if no duration provided
-        2 => parse_time_fmt(&args[1]), // Parse 2nd argument as u32
-        _ => None, // Invalid number of arguments
+// `timerterm sounds ...`
+#[derive(Debug, PartialEq)]
+pub enum Sounds {
+    List,
+    Play(String), // By library name
+    Add(String),  // A file to copy in
+}
+
+// What the binary was asked to do
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(Box<TimerArgs>),    // Boxed, it dwarfs the other variants
+    Pomodoro(PomodoroArgs),
+    Intervals(IntervalArgs),
+    Duel(DuelArgs),
+    Schedule(ScheduleArgs), // `run FILE`
+    Ack(String),            // Acknowledge a nagging timer by name
+    Schema(Option<String>), // Print JSON Schema, all formats if no name given
+    Presets,                // List the config file's presets
+    Sounds(Sounds),         // Manage the sound library
+    Today,                  // Draw today's runs from the history log
+    Stats(StatsArgs),       // Totals over the whole history log
+    Days(DaysArgs),         // Count the days to a date
+    Diagnose(u32),          // Sample the clocks for this many seconds
+    Help(&'static str),     // Print this help text
+    Version,
+}
+
+// Everything a timer run needs, flags included
+#[derive(Debug, PartialEq, Default)]
+pub struct TimerArgs {
+    pub duration: Option<u32>,    // None when not given, config decides
+    pub then: Vec<u32>,           // More durations, run back to back after it
+    pub on_cancel_then: Option<u32>, // Run instead of the rest when quit
+    pub until: Option<Until>,     // Count down to a clock time instead
+    pub cron: Option<Cron>,       // Or to a crontab line's next run
+    pub keypad: bool,             // Or type it in first, microwave style
+    pub elapsed: Option<u32>,     // Start as if already running this long
+    pub estimate: Option<u32>,    // How long the task should take, for `timerterm stats`
+    pub stopwatch: bool,          // Count up from zero instead of down
+    pub calendar: Option<String>, // .ics file checked for upcoming meetings
+    pub force: bool,              // Skip confirmation prompts
+    pub no_confirm: bool,         // Fail instead of prompting, for scripts
+    pub ack_required: bool,       // Keep alerting after finishing until acknowledged
+    pub overtime: bool,           // Count on past zero until acknowledged
+    pub repeat: Option<Repeat>,   // Start over each time it finishes
+    pub name: Option<String>,     // What `timerterm ack` calls this timer
+    pub label: Option<String>,    // Free text header, window title and notification
+    pub preset: Option<String>,   // Config preset giving the duration
+    pub notify: Option<bool>,     // --notify/--no-notify, None leaves it to config
+    pub exec: Option<String>,     // Shell command run once the timer finishes
+    pub watch_exec: bool,         // Run it on the timer screen, k kills it
+    pub layout: Option<Layout>,   // --layout, None leaves it to config
+    pub power_save: Option<PowerSave>, // --power-save, None leaves it to config
+    pub distance: Option<Distance>, // Stopwatch only, shows live pace over it
+    pub pause_reasons: bool,      // Ask why on every pause
+    pub pause_on_suspend: bool,   // Ctrl+Z pauses rather than the clock running on
+    pub no_log: bool,             // Leave this run out of the history log
+    pub plain: bool,              // A line per update even on a terminal
+    pub screenshot: Option<String>, // Where to save the final screen
+    pub quotes: Option<String>,   // Quotes file, None leaves it to config
+    pub control_fifo: Option<String>, // FIFO read for control::parse commands
+    pub status_file: Option<String>, // Kept holding the status as JSON while it runs
+    pub serial: Option<String>,   // Device sent the time as it's shown
+    pub events_file: Option<String>, // Each event appended to it as a line of JSON
+    pub json: bool,               // Print the summary as JSON on exit
+    pub start_on: Option<Trigger>, // Armed until it fires, None starts at once
+    pub timebox: Option<Timebox>, // `git-timebox`, labeled by the repo with a trailer at the end
+    pub alarm: AlarmArgs,
+}
+
+// `git-timebox`'s own flag
+#[derive(Debug, PartialEq, Default)]
+pub struct Timebox {
+    pub message_file: Option<String>, // None for the repo's .git/COMMIT_EDITMSG
+}
+
+// --sound/--volume/--silent, each None/false leaves it to config
+#[derive(Debug, PartialEq, Default)]
+pub struct AlarmArgs {
+    pub sound: Option<String>,
+    pub volume: Option<u8>,
+    pub silent: bool, // No bell or sound at all
+}
+
+// Pomodoro cycle lengths, all in seconds
+#[derive(Debug, PartialEq)]
+pub struct PomodoroArgs {
+    pub work: u32,
+    pub short_break: u32,
+    pub long_break: u32,
+    pub long_every: u32, // Work blocks per long break
+    pub pre_roll: u32,   // Secs counted down into each phase after the first, 0 for none
+    pub strict_breaks: bool, // Breaks can't be paused, skipped or quit
+    pub notify: Option<bool>,
+    pub pause_reasons: bool,
+    pub pause_on_suspend: bool,
+    pub no_log: bool,
+    pub plain: bool,
+    pub quotes: Option<String>,
+    pub control_fifo: Option<String>,
+    pub alarm: AlarmArgs,
+}
+
+impl Default for PomodoroArgs {
+    fn default() -> Self {
+        PomodoroArgs {
+            work: 25 * 60,
+            short_break: 5 * 60,
+            long_break: 15 * 60,
+            long_every: 4,
+            pre_roll: 0,
+            strict_breaks: false,
+            notify: None,
+            pause_reasons: false,
+            pause_on_suspend: false,
+            no_log: false,
+            plain: false,
+            quotes: None,
+            control_fifo: None,
+            alarm: AlarmArgs::default(),
+        }
     }
 }
 
+// Work/rest lengths in seconds and how many rounds of them
+#[derive(Debug, PartialEq)]
+pub struct IntervalArgs {
+    pub work: u32,
+    pub rest: u32,
+    pub rounds: u32,
+    pub notify: Option<bool>,
+    pub pause_on_suspend: bool,
+    pub no_log: bool,
+    pub plain: bool,
+    pub control_fifo: Option<String>,
+    pub alarm: AlarmArgs,
+}
+
+// What `timerterm stats` breaks the totals down by
+#[derive(Debug, PartialEq, Default)]
+pub struct StatsArgs {
+    pub by_project: bool, // A line per directory runs started in
+}
+
+// The local date `timerterm days` counts down to
+#[derive(Debug, PartialEq)]
+pub struct DaysArgs {
+    pub date: (i32, u8, u8), // Year, month, day
+    pub plain: bool,
+}
+
+// One countdown length for both sides, and what to call them
+#[derive(Debug, PartialEq)]
+pub struct DuelArgs {
+    pub duration: u32,
+    pub names: [String; 2], // Left, then right
+    pub notify: Option<bool>,
+    pub plain: bool,
+    pub alarm: AlarmArgs,
+}
+
+// A schedule file to run and how to end its segments
+#[derive(Debug, PartialEq)]
+pub struct ScheduleArgs {
+    pub path: String,
+    pub notify: Option<bool>,
+    pub no_log: bool,
+    pub plain: bool,
+    pub orphan_policy: OrphanPolicy, // Hooks still running at the end
+    pub alarm: AlarmArgs,
+}
+
+// Each subcommand's help, `timerterm help` without one prints USAGE
+macro_rules! alarm_options { () => {
+"      --notify, --no-notify  Send a desktop notification (or not) when done
+      --sound FILE           Play FILE instead of ringing the terminal bell,
+                             or a name from 'timerterm sounds'
+      --volume PERCENT       Alarm volume, 0-100 (\"60\" or \"60%\")
+      --silent               No bell or sound at all
+" } }
+
+pub const USAGE: &str = "timerterm - a countdown timer for the terminal
+
+Usage:
+  timerterm [countdown] [DURATION]... [OPTIONS]
+  timerterm stopwatch [OPTIONS]
+  timerterm pomodoro [OPTIONS]
+  timerterm intervals WORK/REST [xROUNDS] [OPTIONS]
+  timerterm duel DURATION [OPTIONS]
+  timerterm run FILE [OPTIONS]
+  timerterm preset NAME [--set VAR=VALUE]... [OPTIONS]
+  timerterm presets
+  timerterm sounds list|play NAME|add FILE
+  timerterm git-timebox DURATION [OPTIONS]
+  timerterm today
+  timerterm stats [--by-project]
+  timerterm days DATE [OPTIONS]
+  timerterm diagnose [DURATION]
+  timerterm ack [NAME]
+  timerterm schema [FORMAT]
+
+DURATION is seconds (\"90\"), mm:ss, hh:mm:ss or units (\"1h30m\"), and
+defaults to timer.duration from the config file, or 10:00.
+
+Options:
+  -h, --help     Show help, 'timerterm help COMMAND' for a command's options
+  -v, --version  Show the version
+
+Keys while running: space pauses, r restarts, + and - add or take off a
+minute (timer.adjust_step), n shows the next quote, m mutes, [ and ] turn
+the volume down and up, q quits.
+Runs are logged to $XDG_CACHE_HOME/timerterm/timerterm.log (~/.cache).
+";
+
+const COUNTDOWN_HELP: &str = concat!("Usage: timerterm [countdown] [DURATION]... [OPTIONS]
+
+Count down from DURATION (e.g. 90, 25:00, 1h30m). More durations run back to
+back after it, e.g. '25:00 5:00 25:00 15:00', with the overall progress shown.
+
+Options:
+      --until TIME           Count down to a clock time instead, \"14:30\" or
+                             \"2025-07-01T09:00\", local unless it ends in Z/+hh:mm
+      --cron LINE            Count down to the next run of a crontab line like
+                             \"0 9 * * 1-5\", local time; --repeat waits for the
+                             run after
+      --keypad               Type the duration in first, microwave style:
+                             2500 then Enter is 25:00, 90 is 1:30
+      --elapsed DURATION     Start as if it had already run that long, e.g.
+                             to pick up a timer closed by mistake
+      --estimate DURATION    How long the task should take, apart from the
+                             countdown's length; 'timerterm stats' compares
+                             it with how long it did
+      --name NAME            What 'timerterm ack' and notifications call it
+      --label TEXT           Shown above the timer, in the window title,
+                             notifications and the history log
+      --layout LAYOUT        remaining, elapsed, remaining+elapsed or elapsed+remaining
+      --power-save MODE      on, off or auto (only on battery): poll for
+                             triggers and acks once a second, not every tick
+      --ack-required         Keep ringing when done until acknowledged
+      --overtime             Count on past zero in red until a key press or
+                             'timerterm ack', then print how far over it went
+      --repeat N|forever     Start over each time it finishes, N times in
+                             all, chiming (sound.chime) between cycles
+      --then DURATION        Count down DURATION after it, like another DURATION
+      --on-cancel-then DURATION
+                             Count down DURATION instead if it's quit early
+      --exec CMD             Run CMD with sh once the timer finishes
+      --watch-exec           Keep the screen up while it runs, showing its
+                             output and how long it's taken, k kills it
+      --screenshot FILE      Save the final screen as text, or as an image
+                             of the time for .png (needs the png feature)
+      --calendar FILE        Check an .ics file for meetings during the timer
+      --quotes FILE          Show a random line of FILE under the timer
+      --control-fifo PATH    Obey pause, resume, toggle, restart, cancel and
+                             +/-DURATION lines written to the FIFO PATH
+      --status-file PATH     Keep PATH holding the status as JSON while it
+                             runs, e.g. for a status bar
+      --serial DEVICE        Send the time to a serial port as it changes,
+                             at serial.baud in serial.format
+      --events-file PATH     Append each event to PATH as a line of JSON
+      --json                 Print the summary as JSON when it ends
+      --wait-for-key         Show the timer armed, starting it on Space
+      --wait-for-file PATH   Show the timer armed, starting it once PATH exists
+      --after-pid PID        Show the timer armed, starting it once PID exits
+      --after NAME           The same for every process named NAME (pgrep -x)
+      --force                Skip confirmation prompts
+      --no-confirm           Fail instead of asking, for scripts
+      --stopwatch            Count up instead, same as 'timerterm stopwatch'
+      --pause-reasons        Ask why on each pause and log the answer
+      --pause-on-suspend     Don't count time stopped with Ctrl+Z
+      --no-log               Leave this run out of the history log
+      --plain                A line per update instead of the full screen,
+                             the default when output isn't a terminal
+", alarm_options!());
+
+const STOPWATCH_HELP: &str = "Usage: timerterm stopwatch [OPTIONS]
+
+Count up from zero until q is pressed, then print the elapsed time.
+
+Options:
+      --elapsed DURATION     Start counting from DURATION instead of zero
+      --estimate DURATION    How long the task should take, for 'timerterm stats'
+      --distance DISTANCE    Show the pace over DISTANCE, per 500m for meters
+                             (\"2000m\") or per km for kilometers (\"5km\")
+      --screenshot FILE      Save the final time as text, or .png
+      --status-file PATH     Keep PATH holding the status as JSON while it runs
+      --serial DEVICE        Send the time to a serial port as it changes
+      --events-file PATH     Append each event to PATH as a line of JSON
+      --json                 Print the summary as JSON when stopped
+      --wait-for-key         Start on Space rather than at once
+      --wait-for-file PATH   Start once PATH exists
+      --after-pid PID        Start once PID exits
+      --after NAME           Start once every process named NAME has exited
+      --plain                A line per update instead of the full screen,
+                             the default when output isn't a terminal
+";
+
+const POMODORO_HELP: &str = concat!("Usage: timerterm pomodoro [OPTIONS]
+
+Cycle work blocks and breaks until quit. Bare numbers are minutes.
+Breaks suggest the [break_activities] from the config file, in turn.
+During a work block i notes an interruption: type it and press Enter, or Esc
+to drop it. Notes are logged and listed when the session ends. With
+pomodoro.reflection on, each finished work block asks what you got done,
+logging the answer. Enter on nothing or Esc skips it.
+
+Options:
+      --work DURATION        Work block length (default 25)
+      --break DURATION       Short break length (default 5)
+      --long-break DURATION  Long break length (default 15)
+      --long-every N         Work blocks per long break (default 4)
+      --pre-roll SECS        Count SECS down into each next phase, e.g.
+                             \"Break starts in\" (default 0, none)
+      --strict-breaks        Breaks ignore space, r, +, - and q, and Ctrl+C
+                             waits until the break is over. pomodoro.dim
+                             runs as one starts, pomodoro.undim as it ends.
+      --quotes FILE          Show a random line of FILE, a new one each phase
+      --control-fifo PATH    Obey pause, resume, toggle, restart, cancel and
+                             +/-DURATION lines written to the FIFO PATH
+      --pause-reasons        Ask why on each pause and log the answer
+      --pause-on-suspend     Don't count time stopped with Ctrl+Z
+      --no-log               Leave this run out of the history log
+      --plain                A line per update instead of the full screen,
+                             the default when output isn't a terminal
+", alarm_options!());
+
+const INTERVALS_HELP: &str = concat!("Usage: timerterm intervals WORK/REST [xROUNDS] [OPTIONS]
+
+Alternate work and rest for ROUNDS rounds (default 8), e.g. '40s/20s x8',
+then print the time worked and rested. Work is drawn red and rest green. The
+chime (sound.chime) starts each rest, and the alarm each work block and the end.
+
+Options:
+      --control-fifo PATH    Obey pause, resume, toggle, restart, cancel and
+                             +/-DURATION lines written to the FIFO PATH
+      --pause-on-suspend     Don't count time stopped with Ctrl+Z
+      --no-log               Leave this run out of the history log
+      --plain                A line per update instead of the full screen,
+                             the default when output isn't a terminal
+", alarm_options!());
+
+const DUEL_HELP: &str = concat!("Usage: timerterm duel DURATION [OPTIONS]
+
+Two countdowns from DURATION side by side, for two people racing through the
+same block. a pauses and resumes the left one, l the right, space both. When
+both are done (or on q) it prints how each went and who won: the one who
+spent less time paused. Duels aren't logged.
+
+Options:
+      --names LEFT,RIGHT     What to call the two sides (default Left,Right)
+      --plain                A line per update instead of the full screen,
+                             the default when output isn't a terminal
+", alarm_options!());
+
+const RUN_HELP: &str = concat!("Usage: timerterm run FILE [OPTIONS]
+
+Run a schedule file's segments back to back, e.g. a workout routine or a talk
+rehearsal, with the overall progress shown. Each [section] is a segment, in
+the order they appear:
+
+  name = \"Leg day\"       # Shown at the top, optional
+
+  [warmup]
+  duration = \"5:00\"
+  label = \"Warm up\"      # Shown under the time, the section name if unset
+  sound = \"gong.wav\"     # Played when it ends, relative to FILE
+  exec = \"say squats\"    # Started in the background when it ends
+  alert = \"bell, desktop\" # What fires when it ends, of bell, sound and
+                          # desktop, or none
+
+Without a sound the chime (sound.chime) ends each segment, and the alarm the
+last one. An alert replaces that with only what it lists, sound being it.
+Hooks see TIMERTERM_NAME (the label) and TIMERTERM_DURATION.
+
+Options:
+      --orphan-policy POLICY
+                             What to do with hooks still running at the end:
+                             wait (the default), kill (SIGTERM, then SIGKILL
+                             after 2s, for everything they started) or detach.
+                             A run stopped by a signal kills unless detaching.
+      --no-log               Leave this run out of the history log
+      --plain                A line per update instead of the full screen,
+                             the default when output isn't a terminal
+", alarm_options!());
+
+const PRESET_HELP: &str = "Usage: timerterm preset NAME [--set VAR=VALUE]... [OPTIONS]
+
+Count down from the [presets] entry NAME in the config file. Takes the
+same options as countdown, but no DURATION.
+
+A preset can also be a command line with {placeholders}, asked for when it
+starts unless given with --set (or failing with --no-confirm):
+
+  [presets]
+  review = \"{minutes}m --label 'Review {pr}'\"
+";
+
+const GIT_TIMEBOX_HELP: &str = "Usage: timerterm git-timebox DURATION [OPTIONS]
+
+Count down from DURATION labeled with the git repo and branch, e.g.
+'timerterm/main', so the history log says what the time went on. When it
+finishes, or is quit early, a trailer is added to the commit message being
+prepared, e.g. 'Timeboxed: 30m actual 27m'. Takes the same options as
+countdown, plus:
+
+      --message-file PATH    Add the trailer to PATH instead of the repo's
+                             .git/COMMIT_EDITMSG
+";
+
+const PRESETS_HELP: &str = "Usage: timerterm presets
+
+List the presets defined in the config file.
+";
+
+const SOUNDS_HELP: &str = "Usage: timerterm sounds list|play NAME|add FILE
+
+Manage the sound library in $XDG_DATA_HOME/timerterm/sounds (~/.local/share).
+list prints the names, play NAME previews one at sound.volume, and add FILE
+copies FILE in, named after it without the extension. A name from the
+library works wherever a sound file does: --sound chime, or sound.file,
+sound.chime and preset command lines.
+";
+
+const TODAY_HELP: &str = "Usage: timerterm today
+
+Draw today's runs from the history log as a timeline, a block per run in
+its label's color: full when completed, light when cancelled, dark while
+still running. Then the time spent under each label.
+";
+
+const STATS_HELP: &str = "Usage: timerterm stats [OPTIONS]
+
+Count the runs in the history log, how many completed or were cancelled,
+and the time they took.
+
+Options:
+      --by-project           Then the time spent in each project, longest
+                             first: the git repo a run started in, or its
+                             directory outside one
+";
+
+const DAYS_HELP: &str = "Usage: timerterm days DATE [OPTIONS]
+
+Show how many days are left until DATE (YYYY-MM-DD, local time) in big
+digits, rolling over at midnight. Ends once DATE has passed, or on q.
+
+Options:
+      --plain                A line per update instead of the full screen,
+                             the default when output isn't a terminal
+";
+
+const DIAGNOSE_HELP: &str = "Usage: timerterm diagnose [DURATION]
+
+Watch the monotonic clock timers count on next to the wall clock for
+DURATION (3 seconds by default), then report what could make timers
+misbehave here: a coarse clock, wall clock jumps or drift, and sleeps
+overrunning, which VMs and overloaded hosts are prone to.
+";
+
+const ACK_HELP: &str = "Usage: timerterm ack [NAME]
+
+Stop a finished --ack-required timer from ringing. NAME defaults to \"timer\".
+
+Left unacknowledged for the config file's escalate.after, a timer rings at
+full volume, sends a second notification and runs escalate.exec, once.
+";
+
+const SCHEMA_HELP: &str = "Usage: timerterm schema [FORMAT]
+
+Print the JSON Schema of a machine-readable format, or of all of them:
+plan, status (--status-file), event (--events-file) and summary (--json).
+";
+
+const SUBCOMMANDS: [(&str, &str); 17] = [
+    ("countdown", COUNTDOWN_HELP),
+    ("stopwatch", STOPWATCH_HELP),
+    ("pomodoro", POMODORO_HELP),
+    ("intervals", INTERVALS_HELP),
+    ("duel", DUEL_HELP),
+    ("run", RUN_HELP),
+    ("preset", PRESET_HELP),
+    ("presets", PRESETS_HELP),
+    ("sounds", SOUNDS_HELP),
+    ("git-timebox", GIT_TIMEBOX_HELP),
+    ("today", TODAY_HELP),
+    ("stats", STATS_HELP),
+    ("days", DAYS_HELP),
+    ("diagnose", DIAGNOSE_HELP),
+    ("ack", ACK_HELP),
+    ("schema", SCHEMA_HELP),
+    ("help", USAGE),
+];
+
+// Walks the args after the subcommand, splitting "--flag=value" as it goes
+struct Flags {
+    args: std::vec::IntoIter<String>,
+    inline: Option<String>, // Value from "--flag=value" not yet taken
+}
+
+impl Flags {
+    fn new(args: Vec<String>) -> Self {
+        Flags { args: args.into_iter(), inline: None }
+    }
+
+    fn next(&mut self) -> Result<Option<String>, String> {
+        if let Some(value) = self.inline.take() { return Err(format!("unexpected value '{}'", value)); }
+        let Some(arg) = self.args.next() else { return Ok(None) };
+        match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                self.inline = Some(value.to_string());
+                Ok(Some(flag.to_string()))
+            }
+            _ => Ok(Some(arg)),
+        }
+    }
+
+    // The value of `flag`, inline or the next arg
+    fn value(&mut self, flag: &str) -> Result<String, String> {
+        self.inline.take().or_else(|| self.args.next()).ok_or_else(|| format!("{} needs a value", flag))
+    }
+
+    // A flag without a value mustn't have been given one inline
+    fn switch(&mut self, flag: &str) -> Result<(), String> {
+        match self.inline.take() {
+            Some(_) => Err(format!("{} doesn't take a value", flag)),
+            None => Ok(()),
+        }
+    }
+}
+
+// Strip the hidden --internal-test-harness flag wherever it is, true if it was there
+pub fn take_test_harness(args: &mut Vec<String>) -> bool {
+    let before = args.len();
+    args.retain(|a| a != "--internal-test-harness");
+    args.len() != before
+}
+
+pub fn parse_command(args: Vec<String>) -> Result<Command, String> {
+    let mut rest: Vec<String> = args.into_iter().skip(1).collect(); // Program name
+    let sub = match rest.first() {
+        Some(first) if SUBCOMMANDS.iter().any(|(name, _)| name == first) => Some(rest.remove(0)),
+        _ => None,
+    };
+    // --help wins over everything else, so it works even on a half-typed command line
+    if sub.as_deref() == Some("help") || rest.iter().any(|a| a == "-h" || a == "--help") {
+        let topic = if sub.as_deref() == Some("help") { rest.first().cloned() } else { sub };
+        return match topic {
+            None => Ok(Command::Help(USAGE)),
+            Some(topic) => SUBCOMMANDS.iter().find(|(name, _)| *name == topic)
+                .map(|(_, help)| Command::Help(help))
+                .ok_or_else(|| format!("no such command '{}'", topic)),
+        };
+    }
+    if sub.is_none() && rest.iter().any(|a| a == "-v" || a == "--version") { return Ok(Command::Version); }
+
+    let mut flags = Flags::new(rest);
+    match sub.as_deref() {
+        None | Some("countdown") => parse_timer(&mut flags, false).map(|opts| Command::Run(Box::new(opts))),
+        Some("stopwatch") => parse_timer(&mut flags, true).map(|opts| Command::Run(Box::new(opts))),
+        Some("pomodoro") => parse_pomodoro(&mut flags).map(Command::Pomodoro),
+        Some("intervals") => parse_intervals(&mut flags).map(Command::Intervals),
+        Some("duel") => parse_duel(&mut flags).map(Command::Duel),
+        Some("run") => parse_schedule(&mut flags).map(Command::Schedule),
+        // Flags still apply, but the duration comes from the preset
+        Some("preset") => {
+            let name = match flags.next()? {
+                Some(name) if !name.starts_with('-') => name,
+                _ => return Err("preset needs a NAME".to_string()),
+            };
+            let opts = parse_timer(&mut flags, false)?;
+            if opts.duration.is_some() || opts.until.is_some() || opts.cron.is_some() || opts.keypad || opts.stopwatch {
+                return Err(format!("preset '{}' already sets the duration", name));
+            }
+            Ok(Command::Run(Box::new(TimerArgs { preset: Some(name), ..opts })))
+        }
+        Some("git-timebox") => {
+            let opts = parse_timer_from(&mut flags, TimerArgs { timebox: Some(Timebox::default()), ..TimerArgs::default() })?;
+            if opts.duration.is_none() || opts.stopwatch || opts.until.is_some() || opts.cron.is_some() || opts.keypad { return Err("git-timebox needs a DURATION".to_string()); }
+            if !opts.then.is_empty() || opts.repeat.is_some() { return Err("git-timebox takes a single DURATION".to_string()); }
+            Ok(Command::Run(Box::new(opts)))
+        }
+        Some("presets") => no_more(&mut flags, Command::Presets),
+        Some("sounds") => {
+            let sounds = match optional_operand(&mut flags)?.as_deref() {
+                Some("list") => Sounds::List,
+                Some("play") => Sounds::Play(optional_operand(&mut flags)?.ok_or("sounds play needs a NAME")?),
+                Some("add") => Sounds::Add(optional_operand(&mut flags)?.ok_or("sounds add needs a FILE")?),
+                _ => return Err("sounds needs list, play NAME or add FILE".to_string()),
+            };
+            no_more(&mut flags, Command::Sounds(sounds))
+        }
+        Some("today") => no_more(&mut flags, Command::Today),
+        Some("stats") => parse_stats(&mut flags).map(Command::Stats),
+        Some("days") => parse_days(&mut flags).map(Command::Days),
+        Some("diagnose") => {
+            let secs = match optional_operand(&mut flags)? {
+                Some(duration) => parse_duration(&duration)?,
+                None => 3,
+            };
+            if secs == 0 { return Err("duration must be longer than 0".to_string()); }
+            no_more(&mut flags, Command::Diagnose(secs))
+        }
+        Some("ack") => {
+            let name = optional_operand(&mut flags)?.unwrap_or_else(|| ack::DEFAULT_NAME.to_string());
+            no_more(&mut flags, Command::Ack(name))
+        }
+        Some("schema") => {
+            let name = optional_operand(&mut flags)?;
+            no_more(&mut flags, Command::Schema(name))
+        }
+        Some(other) => unreachable!("subcommand '{}' has no parser", other),
+    }
+}
+
+fn optional_operand(flags: &mut Flags) -> Result<Option<String>, String> {
+    match flags.next()? {
+        Some(flag) if flag.starts_with('-') => Err(format!("unknown flag '{}'", flag)),
+        operand => Ok(operand),
+    }
+}
+
+fn no_more(flags: &mut Flags, command: Command) -> Result<Command, String> {
+    match flags.next()? {
+        Some(arg) => Err(format!("unexpected argument '{}'", arg)),
+        None => Ok(command),
+    }
+}
+
+// The alarm and notification flags countdowns and pomodoros share.
+// False if `flag` isn't one of them.
+fn parse_alarm_flag(flag: &str, flags: &mut Flags, notify: &mut Option<bool>, alarm: &mut AlarmArgs) -> Result<bool, String> {
+    match flag {
+        "--notify" => { flags.switch(flag)?; *notify = Some(true) }
+        "--no-notify" => { flags.switch(flag)?; *notify = Some(false) }
+        "--silent" => { flags.switch(flag)?; alarm.silent = true }
+        "--sound" => alarm.sound = Some(flags.value(flag)?),
+        "--volume" => alarm.volume = Some(parse_volume(&flags.value(flag)?)?),
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn parse_timer(flags: &mut Flags, stopwatch: bool) -> Result<TimerArgs, String> {
+    parse_timer_from(flags, TimerArgs { stopwatch, ..TimerArgs::default() })
+}
+
+// Flags over `opts`, which says which command's extra flags are allowed
+fn parse_timer_from(flags: &mut Flags, mut opts: TimerArgs) -> Result<TimerArgs, String> {
+    while let Some(arg) = flags.next()? {
+        if parse_alarm_flag(&arg, flags, &mut opts.notify, &mut opts.alarm)? { continue; }
+        match arg.as_str() {
+            "--force" => { flags.switch(&arg)?; opts.force = true }
+            "--no-confirm" => { flags.switch(&arg)?; opts.no_confirm = true }
+            "--stopwatch" => { flags.switch(&arg)?; opts.stopwatch = true }
+            "--ack-required" => { flags.switch(&arg)?; opts.ack_required = true }
+            "--overtime" => { flags.switch(&arg)?; opts.overtime = true }
+            "--pause-reasons" => { flags.switch(&arg)?; opts.pause_reasons = true }
+            "--pause-on-suspend" => { flags.switch(&arg)?; opts.pause_on_suspend = true }
+            "--no-log" => { flags.switch(&arg)?; opts.no_log = true }
+            "--plain" => { flags.switch(&arg)?; opts.plain = true }
+            "--json" => { flags.switch(&arg)?; opts.json = true }
+            "--wait-for-key" => { flags.switch(&arg)?; set_trigger(&mut opts, Trigger::Key)? }
+            "--wait-for-file" => { let path = flags.value(&arg)?; set_trigger(&mut opts, Trigger::File(path.into()))? }
+            "--after-pid" => {
+                let value = flags.value(&arg)?;
+                let pid = value.parse().map_err(|_| format!("--after-pid needs a process ID, not '{}'", value))?;
+                set_trigger(&mut opts, Trigger::Pid(pid))?
+            }
+            "--message-file" if opts.timebox.is_some() => opts.timebox = Some(Timebox { message_file: Some(flags.value(&arg)?) }),
+            "--after" => { let name = flags.value(&arg)?; set_trigger(&mut opts, Trigger::Named(name, Vec::new()))? }
+            "--name" => opts.name = Some(flags.value(&arg)?),
+            "--label" => opts.label = Some(flags.value(&arg)?),
+            "--calendar" => opts.calendar = Some(flags.value(&arg)?),
+            "--distance" => opts.distance = Some(Distance::parse(&flags.value(&arg)?)?),
+            "--exec" => opts.exec = Some(flags.value(&arg)?),
+            "--watch-exec" => { flags.switch(&arg)?; opts.watch_exec = true }
+            "--screenshot" => opts.screenshot = Some(flags.value(&arg)?),
+            "--quotes" => opts.quotes = Some(flags.value(&arg)?),
+            "--control-fifo" => opts.control_fifo = Some(flags.value(&arg)?),
+            "--status-file" => opts.status_file = Some(flags.value(&arg)?),
+            "--serial" => opts.serial = Some(flags.value(&arg)?),
+            "--events-file" => opts.events_file = Some(flags.value(&arg)?),
+            "--until" => opts.until = Some(Until::parse(&flags.value(&arg)?)?),
+            "--cron" => opts.cron = Some(Cron::parse(&flags.value(&arg)?)?),
+            "--keypad" => { flags.switch(&arg)?; opts.keypad = true }
+            "--elapsed" => opts.elapsed = Some(parse_duration(&flags.value(&arg)?)?),
+            "--estimate" => match parse_duration(&flags.value(&arg)?)? {
+                0 => return Err("--estimate must be longer than 0".to_string()),
+                secs => opts.estimate = Some(secs),
+            },
+            "--then" => opts.then.push(parse_duration(&flags.value(&arg)?)?),
+            "--on-cancel-then" => opts.on_cancel_then = Some(parse_duration(&flags.value(&arg)?)?),
+            "--repeat" => opts.repeat = Some(Repeat::parse(&flags.value(&arg)?)?),
+            "--layout" => {
+                let value = flags.value(&arg)?;
+                opts.layout = Some(Layout::parse(&value)
+                    .ok_or_else(|| format!("--layout must be one of {}, not '{}'", Layout::NAMES.join(", "), value))?);
+            }
+            "--power-save" => {
+                let value = flags.value(&arg)?;
+                opts.power_save = Some(PowerSave::parse(&value)
+                    .ok_or_else(|| format!("--power-save must be one of {}, not '{}'", PowerSave::NAMES.join(", "), value))?);
+            }
+            flag if flag.starts_with('-') && flag.len() > 1 => return Err(format!("unknown flag '{}'", flag)),
+            // A word with no digits at all is more likely a mistyped command
+            _ if !arg.bytes().any(|b| b.is_ascii_digit()) => return Err(format!("'{}' isn't a command or a duration", arg)),
+            _ if opts.duration.is_some() => opts.then.push(parse_duration(&arg)?),
+            _ => opts.duration = Some(parse_duration(&arg)?),
+        }
+    }
+    // A stopwatch has no duration, so one given by mistake is an error
+    if opts.stopwatch && opts.duration.is_some() { return Err("a stopwatch doesn't take a duration".to_string()); }
+    if opts.until.is_some() && (opts.stopwatch || opts.duration.is_some()) {
+        return Err("--until sets the duration, it can't have one too".to_string());
+    }
+    if opts.cron.is_some() && (opts.stopwatch || opts.duration.is_some()) {
+        return Err("--cron sets the duration, it can't have one too".to_string());
+    }
+    if opts.cron.is_some() && opts.until.is_some() { return Err("--cron and --until both set the duration, pick one".to_string()); }
+    if opts.keypad && (opts.stopwatch || opts.duration.is_some() || opts.until.is_some() || opts.cron.is_some()) {
+        return Err("--keypad types the duration in, it can't have one too".to_string());
+    }
+    if opts.keypad && opts.elapsed.is_some() { return Err("--elapsed doesn't work with --keypad".to_string()); }
+    // Both need the whole length before the first segment is typed in
+    if opts.keypad && !opts.then.is_empty() { return Err("--then doesn't work with --keypad".to_string()); }
+    if opts.keypad && opts.calendar.is_some() { return Err("--calendar doesn't work with --keypad".to_string()); }
+    // Each segment is a run of its own, the estimate would be logged with all of them
+    if opts.estimate.is_some() && !opts.then.is_empty() { return Err("--then doesn't work with --estimate".to_string()); }
+    // The time left is already fixed by --until or --cron
+    if opts.until.is_some() && opts.elapsed.is_some() { return Err("--elapsed doesn't work with --until".to_string()); }
+    if opts.cron.is_some() && opts.elapsed.is_some() { return Err("--elapsed doesn't work with --cron".to_string()); }
+    if opts.watch_exec && opts.exec.is_none() { return Err("--watch-exec needs an --exec command to watch".to_string()); }
+    if opts.distance.is_some() && !opts.stopwatch { return Err("--distance only works with a stopwatch".to_string()); }
+    if opts.overtime && opts.stopwatch { return Err("--overtime needs a countdown, a stopwatch never reaches zero".to_string()); }
+    if opts.repeat.is_some() && opts.stopwatch { return Err("--repeat needs a countdown, a stopwatch never finishes".to_string()); }
+    if opts.repeat.is_some() && !opts.then.is_empty() { return Err("--repeat takes a single duration".to_string()); }
+    // The overall progress is counted in the same u32 seconds
+    let chained = opts.then.iter().try_fold(opts.duration.unwrap_or(0), |total, &secs| total.checked_add(secs));
+    if chained.is_none() { return Err("the chain is too long".to_string()); }
+    // A clock time only comes round once
+    if opts.repeat.is_some() && opts.until.is_some() { return Err("--repeat doesn't work with --until".to_string()); }
+    if opts.until.is_some() && opts.start_on.is_some() { return Err("--until starts at once, it can't wait for a trigger".to_string()); }
+    if opts.cron.is_some() && opts.start_on.is_some() { return Err("--cron starts at once, it can't wait for a trigger".to_string()); }
+    Ok(opts)
+}
+
+// One trigger arms the timer, a second would leave which one starts it unclear
+fn set_trigger(opts: &mut TimerArgs, trigger: Trigger) -> Result<(), String> {
+    if opts.start_on.replace(trigger).is_some() {
+        return Err("only one of --wait-for-key, --wait-for-file, --after-pid and --after".to_string());
+    }
+    Ok(())
+}
+
+// Bare numbers are minutes here ("25"), other forms parse as usual ("0:30", "90s")
+fn parse_minutes(value: &str) -> Result<u32, String> {
+    if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+        return value.parse::<u32>().ok().and_then(|m| m.checked_mul(60))
+            .ok_or_else(|| format!("'{}' minutes is too large", value));
+    }
+    parse_duration(value)
+}
+
+// "60" or "60%", at most 100
+fn parse_volume(value: &str) -> Result<u8, String> {
+    value.strip_suffix('%').unwrap_or(value).parse().ok().filter(|&v| v <= 100)
+        .ok_or_else(|| format!("--volume must be a percentage from 0 to 100, not '{}'", value))
+}
+
+fn parse_pomodoro(flags: &mut Flags) -> Result<PomodoroArgs, String> {
+    let mut opts = PomodoroArgs::default();
+    while let Some(arg) = flags.next()? {
+        if parse_alarm_flag(&arg, flags, &mut opts.notify, &mut opts.alarm)? { continue; }
+        match arg.as_str() {
+            "--pause-reasons" => { flags.switch(&arg)?; opts.pause_reasons = true }
+            "--pause-on-suspend" => { flags.switch(&arg)?; opts.pause_on_suspend = true }
+            "--no-log" => { flags.switch(&arg)?; opts.no_log = true }
+            "--plain" => { flags.switch(&arg)?; opts.plain = true }
+            "--work" => opts.work = parse_minutes(&flags.value(&arg)?)?,
+            "--break" => opts.short_break = parse_minutes(&flags.value(&arg)?)?,
+            "--long-break" => opts.long_break = parse_minutes(&flags.value(&arg)?)?,
+            "--quotes" => opts.quotes = Some(flags.value(&arg)?),
+            "--control-fifo" => opts.control_fifo = Some(flags.value(&arg)?),
+            "--long-every" => {
+                let value = flags.value(&arg)?;
+                opts.long_every = value.parse().ok().filter(|&n| n > 0)
+                    .ok_or_else(|| format!("--long-every must be a whole number above 0, not '{}'", value))?;
+            }
+            "--pre-roll" => {
+                let value = flags.value(&arg)?;
+                opts.pre_roll = value.parse()
+                    .map_err(|_| format!("--pre-roll must be a whole number of seconds, not '{}'", value))?;
+            }
+            "--strict-breaks" => { flags.switch(&arg)?; opts.strict_breaks = true }
+            flag if flag.starts_with('-') => return Err(format!("unknown flag '{}'", flag)),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(opts)
+}
+
+// "40s/20s" and an optional "x8", in either order among the flags
+fn parse_intervals(flags: &mut Flags) -> Result<IntervalArgs, String> {
+    let (mut lengths, mut rounds) = (None, None);
+    let mut opts = IntervalArgs { work: 0, rest: 0, rounds: 8, notify: None, pause_on_suspend: false, no_log: false, plain: false, control_fifo: None, alarm: AlarmArgs::default() };
+    while let Some(arg) = flags.next()? {
+        if parse_alarm_flag(&arg, flags, &mut opts.notify, &mut opts.alarm)? { continue; }
+        match arg.as_str() {
+            "--pause-on-suspend" => { flags.switch(&arg)?; opts.pause_on_suspend = true }
+            "--no-log" => { flags.switch(&arg)?; opts.no_log = true }
+            "--plain" => { flags.switch(&arg)?; opts.plain = true }
+            "--control-fifo" => opts.control_fifo = Some(flags.value(&arg)?),
+            flag if flag.starts_with('-') => return Err(format!("unknown flag '{}'", flag)),
+            count if rounds.is_none() && count.starts_with('x') => {
+                rounds = Some(count[1..].parse::<u32>().ok().filter(|&n| n > 0)
+                    .ok_or_else(|| format!("rounds must be like 'x8', not '{}'", count))?);
+            }
+            spec if lengths.is_none() => {
+                let (work, rest) = spec.split_once('/').ok_or_else(|| format!("intervals need WORK/REST like '40s/20s', not '{}'", spec))?;
+                lengths = Some((parse_duration(work)?, parse_duration(rest)?));
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    (opts.work, opts.rest) = lengths.ok_or("intervals need WORK/REST like '40s/20s'")?;
+    if opts.work == 0 || opts.rest == 0 { return Err("work and rest must both be longer than 0".to_string()); }
+    opts.rounds = rounds.unwrap_or(opts.rounds);
+    Ok(opts)
+}
+
+fn parse_duel(flags: &mut Flags) -> Result<DuelArgs, String> {
+    let mut opts = DuelArgs { duration: 0, names: ["Left".to_string(), "Right".to_string()], notify: None, plain: false, alarm: AlarmArgs::default() };
+    let mut duration = None;
+    while let Some(arg) = flags.next()? {
+        if parse_alarm_flag(&arg, flags, &mut opts.notify, &mut opts.alarm)? { continue; }
+        match arg.as_str() {
+            "--plain" => { flags.switch(&arg)?; opts.plain = true }
+            "--names" => {
+                let value = flags.value(&arg)?;
+                let (left, right) = value.split_once(',').map(|(l, r)| (l.trim(), r.trim()))
+                    .filter(|(l, r)| !l.is_empty() && !r.is_empty() && !r.contains(','))
+                    .ok_or_else(|| format!("--names needs two names like 'Ana,Ben', not '{}'", value))?;
+                opts.names = [left.to_string(), right.to_string()];
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown flag '{}'", flag)),
+            _ if duration.is_none() => duration = Some(parse_duration(&arg)?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    opts.duration = duration.ok_or("duel needs a DURATION")?;
+    if opts.duration == 0 { return Err("duration must be longer than 0".to_string()); }
+    Ok(opts)
+}
+
+fn parse_stats(flags: &mut Flags) -> Result<StatsArgs, String> {
+    let mut opts = StatsArgs::default();
+    while let Some(arg) = flags.next()? {
+        match arg.as_str() {
+            "--by-project" => { flags.switch(&arg)?; opts.by_project = true }
+            flag if flag.starts_with('-') => return Err(format!("unknown flag '{}'", flag)),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(opts)
+}
+
+fn parse_days(flags: &mut Flags) -> Result<DaysArgs, String> {
+    let mut opts = DaysArgs { date: (0, 0, 0), plain: false };
+    let mut date = None;
+    while let Some(arg) = flags.next()? {
+        match arg.as_str() {
+            "--plain" => { flags.switch(&arg)?; opts.plain = true }
+            flag if flag.starts_with('-') => return Err(format!("unknown flag '{}'", flag)),
+            _ if date.is_none() => date = Some(parse_date(&arg)?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    opts.date = date.ok_or("days needs a DATE like 2025-06-01")?;
+    Ok(opts)
+}
+
+// "2025-06-01"
+fn parse_date(value: &str) -> Result<(i32, u8, u8), String> {
+    let fields: Option<Vec<i64>> = value.split('-')
+        .map(|f| if !f.is_empty() && f.bytes().all(|b| b.is_ascii_digit()) { f.parse().ok() } else { None })
+        .collect();
+    match fields.as_deref() {
+        Some(&[y, m, d]) if y <= 9999 && (1..=12).contains(&m) && d >= 1 && d <= days_in_month(y, m) => Ok((y as i32, m as u8, d as u8)),
+        Some([_, _, _]) => Err(format!("'{}' isn't a date", value)),
+        _ => Err(format!("days needs a DATE like 2025-06-01, not '{}'", value)),
+    }
+}
+
+fn parse_schedule(flags: &mut Flags) -> Result<ScheduleArgs, String> {
+    let mut opts = ScheduleArgs { path: String::new(), notify: None, no_log: false, plain: false, orphan_policy: OrphanPolicy::Wait, alarm: AlarmArgs::default() };
+    let mut path = None;
+    while let Some(arg) = flags.next()? {
+        if parse_alarm_flag(&arg, flags, &mut opts.notify, &mut opts.alarm)? { continue; }
+        match arg.as_str() {
+            "--no-log" => { flags.switch(&arg)?; opts.no_log = true }
+            "--plain" => { flags.switch(&arg)?; opts.plain = true }
+            "--orphan-policy" => {
+                let value = flags.value(&arg)?;
+                opts.orphan_policy = OrphanPolicy::parse(&value)
+                    .ok_or_else(|| format!("--orphan-policy must be one of {}, not '{}'", OrphanPolicy::NAMES.join(", "), value))?;
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown flag '{}'", flag)),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    opts.path = path.ok_or("run needs a schedule FILE")?;
+    Ok(opts)
+}
+
 // ============ Unit Tests =============
 #[cfg(test)]
 mod tests {
+    use super::Command;
+
+    fn args(a: &[&str]) -> Vec<String> {
+        std::iter::once("timeterm").chain(a.iter().copied()).map(String::from).collect()
+    }
+
+    fn run(a: &[&str]) -> Result<super::TimerArgs, String> {
+        match super::parse_command(args(a))? {
+            Command::Run(opts) => Ok(*opts),
+            other => panic!("expected a timer run, got {:?}", other),
+        }
+    }
+
     #[test]
-    fn parse_args_extracts_second_duration() {
-        // Test: prase_args should extract duration from CLI args
-        let args = vec!["timeterm".to_string(), "30".to_string()];
-        assert_eq!(super::parse_args(args), Some(30));
-        let args = vec!["timeterm".to_string(), "4294967295".to_string()];
-        assert_eq!(super::parse_args(args), Some(4294967295));
+    fn parse_command_extracts_second_duration() {
+        // Test: parse_command should extract duration from CLI args
+        assert_eq!(run(&["30"]).unwrap().duration, Some(30));
+        assert_eq!(run(&["4294967295"]).unwrap().duration, Some(4294967295));
+        assert_eq!(run(&["countdown", "1:00"]).unwrap().duration, Some(60));
     }
 
     #[test]
-    fn parse_args_defaults_10min() {
-        // Test: parse_args should default to 10 minutes if no args
-        let args = vec!["timeterm".to_string()];
-        assert_eq!(super::parse_args(args), Some(600));
+    fn take_test_harness_strips_flag() {
+        // Test: The flag can go anywhere and never reaches parse_command
+        let mut a = args(&["pomodoro", "--internal-test-harness", "--work", "1"]);
+        assert!(super::take_test_harness(&mut a));
+        assert_eq!(a, args(&["pomodoro", "--work", "1"]));
+        assert!(!super::take_test_harness(&mut a));
+    }
+
+    #[test]
+    fn parse_command_leaves_default_duration_to_config() {
+        // Test: No args is a countdown with no duration, config's 10 minutes apply
+        assert_eq!(super::parse_command(args(&[])), Ok(Command::Run(Default::default())));
+        // Test: Bad positionals are errors instead of a silent default
+        assert_eq!(run(&["stopwach"]), Err("'stopwach' isn't a command or a duration".to_string()));
+        assert_eq!(run(&["30", "stopwach"]), Err("'stopwach' isn't a command or a duration".to_string()));
     }
 
     #[test]
@@ -73,5 +1193,412 @@ mod tests {
     assert_eq!(super::parse_time_fmt("2:15:00"), Some(8100));
     }
 
+    #[test]
+    fn parse_command_detects_schema() {
+        // Test: "schema" with an optional format name is its own command
+        assert_eq!(super::parse_command(args(&["schema"])), Ok(Command::Schema(None)));
+        assert_eq!(super::parse_command(args(&["schema", "event"])), Ok(Command::Schema(Some("event".to_string()))));
+        assert!(super::parse_command(args(&["schema", "event", "summary"])).is_err());
+    }
+
+    #[test]
+    fn parse_command_detects_help_and_version() {
+        // Test: --help anywhere shows the help of the subcommand it follows
+        assert_eq!(super::parse_command(args(&["--help"])), Ok(Command::Help(super::USAGE)));
+        assert_eq!(super::parse_command(args(&["pomodoro", "--work", "-h"])), Ok(Command::Help(super::POMODORO_HELP)));
+        assert_eq!(super::parse_command(args(&["help", "stopwatch"])), Ok(Command::Help(super::STOPWATCH_HELP)));
+        assert_eq!(super::parse_command(args(&["help", "bogus"])), Err("no such command 'bogus'".to_string()));
+        assert_eq!(super::parse_command(args(&["-v"])), Ok(Command::Version));
+    }
+
+    #[test]
+    fn parse_timer_extracts_flags() {
+        // Test: Flags can come before or after the duration, values inline or not
+        let opts = run(&["--calendar", "work.ics", "25:00", "--force"]).unwrap();
+        assert_eq!(opts.duration, Some(1500));
+        assert_eq!(opts.calendar.as_deref(), Some("work.ics"));
+        assert!(opts.force);
+        assert_eq!(run(&["--calendar=work.ics"]).unwrap().calendar.as_deref(), Some("work.ics"));
+        // Test: Flags alone leave the duration for config/defaults to decide
+        assert_eq!(run(&["--force"]).unwrap().duration, None);
+        assert_eq!(run(&["--force", "1:00:00:00"]), Err("'1:00:00:00' should be mm:ss or hh:mm:ss".to_string()));
+    }
+
+    #[test]
+    fn parse_timer_rejects_bad_flags() {
+        // Test: Unknown flags and missing or unwanted flag values fail, saying why
+        assert_eq!(run(&["--bogus"]), Err("unknown flag '--bogus'".to_string()));
+        assert_eq!(run(&["--calendar"]), Err("--calendar needs a value".to_string()));
+        assert_eq!(run(&["--force=yes"]), Err("--force doesn't take a value".to_string()));
+        assert!(run(&["--layout", "sideways"]).unwrap_err().starts_with("--layout must be one of remaining, "));
+    }
+
+    #[test]
+    fn parse_command_detects_preset() {
+        let expected = super::TimerArgs { preset: Some("tea".to_string()), force: true, ..Default::default() };
+        assert_eq!(run(&["preset", "tea", "--force"]), Ok(expected));
+        // Test: A preset needs a name and can't also take a duration
+        assert_eq!(run(&["preset"]), Err("preset needs a NAME".to_string()));
+        assert!(run(&["preset", "tea", "5:00"]).is_err());
+        assert_eq!(super::parse_command(args(&["presets"])), Ok(Command::Presets));
+        assert_eq!(super::parse_command(args(&["today"])), Ok(Command::Today));
+        assert!(super::parse_command(args(&["today", "yesterday"])).is_err());
+    }
+
+    #[test]
+    fn parse_command_detects_stats() {
+        assert_eq!(super::parse_command(args(&["stats"])), Ok(Command::Stats(super::StatsArgs { by_project: false })));
+        assert_eq!(super::parse_command(args(&["stats", "--by-project"])), Ok(Command::Stats(super::StatsArgs { by_project: true })));
+        // Test: Nothing else to break it down by
+        assert_eq!(super::parse_command(args(&["stats", "--by-label"])), Err("unknown flag '--by-label'".to_string()));
+    }
+
+    #[test]
+    fn parse_command_detects_diagnose() {
+        // Test: diagnose samples for 3 seconds unless told otherwise
+        assert_eq!(super::parse_command(args(&["diagnose"])), Ok(Command::Diagnose(3)));
+        assert_eq!(super::parse_command(args(&["diagnose", "1m"])), Ok(Command::Diagnose(60)));
+        assert_eq!(super::parse_command(args(&["diagnose", "0"])), Err("duration must be longer than 0".to_string()));
+    }
+
+    #[test]
+    fn parse_command_detects_days() {
+        assert_eq!(super::parse_command(args(&["days", "2025-06-01", "--plain"])), Ok(Command::Days(super::DaysArgs { date: (2025, 6, 1), plain: true })));
+        // Test: The date must be one
+        assert_eq!(super::parse_command(args(&["days"])), Err("days needs a DATE like 2025-06-01".to_string()));
+        assert_eq!(super::parse_command(args(&["days", "2025-02-29"])), Err("'2025-02-29' isn't a date".to_string()));
+        assert_eq!(super::parse_command(args(&["days", "June"])), Err("days needs a DATE like 2025-06-01, not 'June'".to_string()));
+    }
+
+    #[test]
+    fn parse_timer_reads_json_outputs() {
+        let opts = run(&["5:00", "--status-file", "/tmp/status.json", "--events-file=/tmp/events.jsonl", "--json"]).unwrap();
+        assert_eq!(opts.status_file.as_deref(), Some("/tmp/status.json"));
+        assert_eq!(opts.events_file.as_deref(), Some("/tmp/events.jsonl"));
+        assert!(opts.json);
+        assert_eq!(run(&["--json=yes"]), Err("--json doesn't take a value".to_string()));
+    }
+
+    #[test]
+    fn parse_timer_reads_serial() {
+        assert_eq!(run(&["5:00", "--serial", "/dev/ttyUSB0"]).unwrap().serial.as_deref(), Some("/dev/ttyUSB0"));
+        assert_eq!(run(&["stopwatch", "--serial=/dev/ttyACM0"]).unwrap().serial.as_deref(), Some("/dev/ttyACM0"));
+        // Test: The device is required, its baud rate is the config file's
+        assert_eq!(run(&["5:00", "--serial"]), Err("--serial needs a value".to_string()));
+    }
+
+    #[test]
+    fn parse_timer_reads_estimate() {
+        assert_eq!(run(&["1h", "--estimate", "45m"]).unwrap().estimate, Some(2700));
+        assert_eq!(run(&["stopwatch", "--estimate=20m"]).unwrap().estimate, Some(1200));
+        // Test: It must be some time, for a single run
+        assert_eq!(run(&["1h", "--estimate", "0"]), Err("--estimate must be longer than 0".to_string()));
+        assert_eq!(run(&["25:00", "--estimate", "20m", "--then", "5:00"]), Err("--then doesn't work with --estimate".to_string()));
+    }
+
+    #[test]
+    fn parse_timer_reads_start_triggers() {
+        assert_eq!(run(&["5:00", "--wait-for-key"]).unwrap().start_on, Some(super::Trigger::Key));
+        assert_eq!(run(&["--wait-for-file", "/tmp/go"]).unwrap().start_on, Some(super::Trigger::File("/tmp/go".into())));
+        // Test: One trigger at a time, and none for a clock time
+        assert_eq!(run(&["--after-pid", "42"]).unwrap().start_on, Some(super::Trigger::Pid(42)));
+        assert_eq!(run(&["--after", "make"]).unwrap().start_on, Some(super::Trigger::Named("make".into(), vec![])));
+        assert_eq!(run(&["--after-pid", "make"]), Err("--after-pid needs a process ID, not 'make'".to_string()));
+        assert_eq!(run(&["--wait-for-key", "--after-pid", "42"]), Err("only one of --wait-for-key, --wait-for-file, --after-pid and --after".to_string()));
+        assert!(run(&["--until", "14:30", "--wait-for-key"]).is_err());
+    }
+
+    #[test]
+    fn parse_command_reads_git_timebox() {
+        let parsed = super::parse_command(args(&["git-timebox", "30m", "--message-file", "msg.txt"])).unwrap();
+        let Command::Run(opts) = parsed else { panic!("expected a run, got {:?}", parsed) };
+        assert_eq!((opts.duration, opts.timebox), (Some(1800), Some(super::Timebox { message_file: Some("msg.txt".into()) })));
+        // Test: It needs one duration, and --message-file is its alone
+        assert_eq!(super::parse_command(args(&["git-timebox"])), Err("git-timebox needs a DURATION".to_string()));
+        assert_eq!(super::parse_command(args(&["git-timebox", "30m", "5m"])), Err("git-timebox takes a single DURATION".to_string()));
+        assert_eq!(super::parse_command(args(&["30m", "--message-file", "msg.txt"])), Err("unknown flag '--message-file'".to_string()));
+    }
+
+    #[test]
+    fn parse_command_detects_ack() {
+        // Test: ack takes an optional timer name
+        assert_eq!(super::parse_command(args(&["ack"])), Ok(Command::Ack("timer".to_string())));
+        assert_eq!(super::parse_command(args(&["ack", "laundry"])), Ok(Command::Ack("laundry".to_string())));
+        // Test: The name comes with the timer that needs acknowledging
+        let opts = run(&["45:00", "--ack-required", "--name", "laundry"]).unwrap();
+        assert!(opts.ack_required);
+        assert_eq!(opts.notify, None);
+        // Test: --exec takes the whole command as one argument
+        assert_eq!(run(&["--exec", "make test", "1"]).unwrap().exec.as_deref(), Some("make test"));
+        assert_eq!(run(&["--layout", "remaining+elapsed"]).unwrap().layout, Some(super::Layout::RemainingElapsed));
+        assert_eq!(opts.name.as_deref(), Some("laundry"));
+    }
+
+    #[test]
+    fn parse_timer_reads_power_save() {
+        assert_eq!(run(&["--power-save", "auto", "1"]).unwrap().power_save, Some(super::PowerSave::Auto));
+        assert_eq!(run(&["1"]).unwrap().power_save, None);
+        assert_eq!(run(&["--power-save", "yes"]), Err("--power-save must be one of auto, on, off, not 'yes'".to_string()));
+    }
+
+    #[test]
+    fn parse_timer_reads_watch_exec() {
+        assert!(run(&["--exec", "make test", "--watch-exec", "1"]).unwrap().watch_exec);
+        // Test: There has to be a command to watch
+        assert_eq!(run(&["--watch-exec", "1"]), Err("--watch-exec needs an --exec command to watch".to_string()));
+    }
+
+    #[test]
+    fn parse_command_detects_sounds() {
+        assert_eq!(super::parse_command(args(&["sounds", "list"])), Ok(Command::Sounds(super::Sounds::List)));
+        assert_eq!(super::parse_command(args(&["sounds", "play", "chime"])), Ok(Command::Sounds(super::Sounds::Play("chime".to_string()))));
+        // Test: add needs its file, and nothing takes more than it uses
+        assert_eq!(super::parse_command(args(&["sounds", "add"])), Err("sounds add needs a FILE".to_string()));
+        assert!(super::parse_command(args(&["sounds"])).is_err());
+        assert!(super::parse_command(args(&["sounds", "list", "extra"])).is_err());
+    }
+
+    #[test]
+    fn parse_timer_chains_durations() {
+        // Test: Durations after the first run after it, in order
+        let opts = run(&["25:00", "5m", "25:00", "900"]).unwrap();
+        assert_eq!((opts.duration, opts.then), (Some(1500), vec![300, 1500, 900]));
+        assert_eq!(run(&["30", "40", "--repeat", "2"]), Err("--repeat takes a single duration".to_string()));
+        assert!(run(&["30", "4o"]).is_err());
+        assert_eq!(run(&["4000000000", "4000000000"]), Err("the chain is too long".to_string()));
+        // Test: --then adds to the chain, --on-cancel-then stands apart from it
+        let opts = run(&["25:00", "--then", "5:00", "--on-cancel-then", "2m"]).unwrap();
+        assert_eq!((opts.then, opts.on_cancel_then), (vec![300], Some(120)));
+    }
+
+    #[test]
+    fn parse_timer_handles_repeat() {
+        // Test: A count or forever, never zero, and only for a countdown
+        assert_eq!(run(&["30s", "--repeat", "8"]).unwrap().repeat, Some(super::Repeat::Times(8)));
+        assert_eq!(run(&["30s", "--repeat=forever"]).unwrap().repeat, Some(super::Repeat::Forever));
+        assert_eq!(run(&["30s", "--repeat", "0"]), Err("--repeat needs at least 1 cycle".to_string()));
+        assert!(run(&["30s", "--repeat", "always"]).is_err());
+        assert!(run(&["stopwatch", "--repeat", "2"]).is_err());
+        assert!(run(&["--until", "09:00", "--repeat", "2"]).is_err());
+        // Test: The counter knows the last cycle, forever has none
+        assert_eq!(super::Repeat::Times(3).counter(2), "Cycle 2 of 3");
+        assert!(super::Repeat::Times(3).is_last(3));
+        assert_eq!(super::Repeat::Forever.counter(12), "Cycle 12");
+        assert!(!super::Repeat::Forever.is_last(1));
+    }
+
+    #[test]
+    fn parse_timer_handles_overtime() {
+        // Test: Only a countdown has a zero to run past
+        assert!(run(&["15:00", "--overtime"]).unwrap().overtime);
+        assert_eq!(run(&["stopwatch", "--overtime"]), Err("--overtime needs a countdown, a stopwatch never reaches zero".to_string()));
+    }
+
+    #[test]
+    fn parse_timer_handles_stopwatch() {
+        // Test: The subcommand and the --stopwatch alias both take no duration
+        assert!(run(&["stopwatch"]).unwrap().stopwatch);
+        assert!(run(&["--stopwatch"]).unwrap().stopwatch);
+        assert_eq!(run(&["stopwatch", "30"]), Err("a stopwatch doesn't take a duration".to_string()));
+    }
+
+    #[test]
+    fn parse_command_detects_pomodoro() {
+        // Test: No flags gives the classic 25/5/15, long break every 4
+        assert_eq!(super::parse_command(args(&["pomodoro"])), Ok(Command::Pomodoro(Default::default())));
+        // Test: Bare numbers are minutes, colon forms are the usual duration format
+        let expected = super::PomodoroArgs { work: 3000, short_break: 30, long_every: 2, pre_roll: 10, strict_breaks: true, ..Default::default() };
+        let parsed = super::parse_command(args(&["pomodoro", "--work", "50", "--break", "0:30", "--long-every", "2", "--pre-roll", "10", "--strict-breaks"]));
+        assert_eq!(parsed, Ok(Command::Pomodoro(expected)));
+    }
+
+    #[test]
+    fn parse_command_reads_quotes_file() {
+        // Test: --quotes works for countdowns and pomodoros alike
+        assert_eq!(run(&["5:00", "--quotes", "q.txt"]).unwrap().quotes.as_deref(), Some("q.txt"));
+        let parsed = super::parse_command(args(&["pomodoro", "--quotes=q.txt"]));
+        assert_eq!(parsed, Ok(Command::Pomodoro(super::PomodoroArgs { quotes: Some("q.txt".to_string()), ..Default::default() })));
+    }
+
+    #[test]
+    fn parse_pomodoro_rejects_bad_flags() {
+        // Test: Unknown flags, missing values and zero cycles fail
+        let err = |a: &[&str]| super::parse_pomodoro(&mut super::Flags::new(a.iter().map(|s| s.to_string()).collect())).unwrap_err();
+        assert_eq!(err(&["--bogus", "1"]), "unknown flag '--bogus'");
+        assert_eq!(err(&["--work"]), "--work needs a value");
+        assert_eq!(err(&["--long-every", "0"]), "--long-every must be a whole number above 0, not '0'");
+        assert_eq!(err(&["--pre-roll", "0:10"]), "--pre-roll must be a whole number of seconds, not '0:10'");
+    }
+
+    #[test]
+    fn parse_intervals_reads_lengths_and_rounds() {
+        let parse = |a: &[&str]| super::parse_intervals(&mut super::Flags::new(a.iter().map(|s| s.to_string()).collect()));
+        let opts = parse(&["40s/20s", "x10", "--no-log", "--plain"]).unwrap();
+        assert_eq!((opts.work, opts.rest, opts.rounds, opts.no_log, opts.plain), (40, 20, 10, true, true));
+        // Test: Eight rounds unless told otherwise, any duration form works
+        assert_eq!(parse(&["1:00/30"]).map(|o| (o.work, o.rest, o.rounds)), Ok((60, 30, 8)));
+        // Test: Missing, zero or garbled parts are errors
+        assert_eq!(parse(&["x8"]).unwrap_err(), "intervals need WORK/REST like '40s/20s'");
+        assert_eq!(parse(&["40s"]).unwrap_err(), "intervals need WORK/REST like '40s/20s', not '40s'");
+        assert_eq!(parse(&["40s/0"]).unwrap_err(), "work and rest must both be longer than 0");
+        assert_eq!(parse(&["40s/20s", "x0"]).unwrap_err(), "rounds must be like 'x8', not 'x0'");
+        assert!(parse(&["40s/20s", "x8", "x9"]).is_err());
+    }
+
+    #[test]
+    fn parse_duel_reads_duration_and_names() {
+        let parse = |a: &[&str]| super::parse_duel(&mut super::Flags::new(a.iter().map(|s| s.to_string()).collect()));
+        let opts = parse(&["25:00", "--names", "Ana, Ben", "--silent"]).unwrap();
+        assert_eq!((opts.duration, opts.names, opts.alarm.silent), (1500, ["Ana".to_string(), "Ben".to_string()], true));
+        assert_eq!(parse(&["90"]).unwrap().names, ["Left".to_string(), "Right".to_string()]);
+        // Test: A duration is needed, and exactly two names
+        assert_eq!(parse(&[]).unwrap_err(), "duel needs a DURATION");
+        assert_eq!(parse(&["0"]).unwrap_err(), "duration must be longer than 0");
+        assert_eq!(parse(&["90", "--names", "Ana"]).unwrap_err(), "--names needs two names like 'Ana,Ben', not 'Ana'");
+        assert!(parse(&["90", "--names", "A,B,C"]).is_err());
+        assert!(parse(&["90", "60"]).is_err());
+    }
+
+    #[test]
+    fn parse_command_reads_run_file() {
+        let parsed = super::parse_command(args(&["run", "legs.toml", "--no-log", "--silent"])).unwrap();
+        let Command::Schedule(opts) = parsed else { panic!("expected a schedule, got {:?}", parsed) };
+        assert_eq!((opts.path.as_str(), opts.no_log, opts.alarm.silent), ("legs.toml", true, true));
+        assert_eq!(super::parse_command(args(&["run"])), Err("run needs a schedule FILE".to_string()));
+        // Test: Hooks are waited for unless told otherwise
+        assert_eq!(opts.orphan_policy, super::OrphanPolicy::Wait);
+        let parsed = super::parse_command(args(&["run", "legs.toml", "--orphan-policy", "kill"])).unwrap();
+        assert!(matches!(parsed, Command::Schedule(super::ScheduleArgs { orphan_policy: super::OrphanPolicy::Kill, .. })));
+        assert_eq!(super::parse_command(args(&["run", "legs.toml", "--orphan-policy", "abandon"])),
+            Err("--orphan-policy must be one of wait, kill, detach, not 'abandon'".to_string()));
+        assert!(super::parse_command(args(&["run", "a.toml", "b.toml"])).is_err());
+    }
+
+    #[test]
+    fn parse_timer_extracts_alarm_flags() {
+        let alarm = run(&["5:00", "--sound", "ding.wav", "--volume", "60%"]).unwrap().alarm;
+        assert_eq!(alarm, super::AlarmArgs { sound: Some("ding.wav".to_string()), volume: Some(60), silent: false });
+        // Test: Volume is a percentage, with or without the sign
+        assert_eq!(super::parse_volume("100"), Ok(100));
+        assert!(super::parse_volume("101%").is_err());
+        assert!(super::parse_volume("loud").is_err());
+    }
+
+    #[test]
+    fn notify_flags_override_config() {
+        // Test: The last of --notify/--no-notify wins, unset leaves it to config
+        assert_eq!(run(&["--notify", "--no-notify"]).unwrap().notify, Some(false));
+        let parsed = super::parse_command(args(&["pomodoro", "--work", "1", "--notify"]));
+        assert!(matches!(parsed, Ok(Command::Pomodoro(super::PomodoroArgs { notify: Some(true), .. }))));
+    }
+
+    #[test]
+    fn suspicious_duration_flags_typos() {
+        // Test: 90000 (25h) over a 24h limit and zero are flagged, the rest pass
+        assert_eq!(super::suspicious_duration(90000, 86400).unwrap(), "25:00:00 is longer than 24:00:00");
+        assert_eq!(super::suspicious_duration(0, 86400).unwrap(), "A 0:00 timer ends immediately");
+        assert_eq!(super::suspicious_duration(86400, 86400), None);
+        assert_eq!(super::suspicious_duration(1, 86400), None);
+    }
+
+    #[test]
+    fn parse_duration_handles_units() {
+        // Test: Unit forms, alone and combined, largest unit first
+        assert_eq!(super::parse_duration("1h30m"), Ok(5400));
+        assert_eq!(super::parse_duration("90s"), Ok(90));
+        assert_eq!(super::parse_duration("2h"), Ok(7200));
+        assert_eq!(super::parse_duration("45m10s"), Ok(2710));
+        assert_eq!(super::parse_duration("1h0m5s"), Ok(3605));
+        // Test: Bare numbers stay seconds
+        assert_eq!(super::parse_duration("90"), Ok(90));
+    }
+
+    #[test]
+    fn parse_duration_names_bad_token() {
+        // Test: Each error quotes the token that failed
+        let err = |s: &str| super::parse_duration(s).unwrap_err();
+        assert_eq!(err("1h3x"), "'3x' in '1h3x' has an unknown unit, expected h, m or s");
+        assert_eq!(err("1h30"), "'30' in '1h30' needs a unit (h, m or s)");
+        assert_eq!(err("h30m"), "'h' in 'h30m' is missing a number");
+        assert_eq!(err("30m1h"), "'1h' in '30m1h' repeats a unit or comes after a smaller one");
+        assert_eq!(err("1:x"), "'x' in '1:x' isn't a number");
+        assert_eq!(err("1:2:3:4"), "'1:2:3:4' should be mm:ss or hh:mm:ss");
+        assert_eq!(err("2000000h"), "'2000000h' is too large");
+        assert_eq!(err(""), "empty duration");
+    }
+
+    #[test]
+    fn until_parses_times_and_dates() {
+        use super::Until;
+        // Test: Offsets and seconds are optional, "Z" is UTC
+        let at = |s: &str| Until::parse(s).unwrap();
+        assert_eq!(at("2025-07-01T09:00Z").secs_from(1751360400 - 90), Ok(90));
+        assert_eq!(at("2025-07-01T11:00:30+02:00").secs_from(1751360400), Ok(30));
+        // Test: A time alone is the next one, rolling over past midnight
+        assert_eq!(at("14:30Z").secs_from(1751360400), Ok(5 * 3600 + 1800));
+        assert_eq!(at("08:00Z").secs_from(1751360400), Ok(23 * 3600));
+        assert_eq!(at("23:59:59-00:00").secs_from(1751327999 + 86400), Ok(86400));
+        // Test: Month ends roll over too (2025-06-30 23:30 UTC)
+        assert_eq!(at("00:15Z").secs_from(1751326200), Ok(2700));
+        // Test: A date that's gone can't be counted down to
+        assert_eq!(at("2000-01-01T00:00Z").secs_from(1751360400), Err("'2000-01-01T00:00Z' has already passed".to_string()));
+        // Test: Local times land within a day either side of the UTC reading
+        let local = at("2025-07-01T09:00").secs_from(1751360400 - 86400 * 2).unwrap();
+        assert!((86400..=86400 * 3).contains(&local));
+        assert!(at("12:00").secs_from(1751360400).is_ok_and(|s| s > 0 && s <= 86400 + 3600));
+    }
+
+    #[test]
+    fn until_rejects_bad_times() {
+        let err = |s: &str| super::Until::parse(s).unwrap_err();
+        assert_eq!(err("24:00"), "'24:00' isn't a time of day");
+        assert_eq!(err("2025-02-29T09:00"), "'2025-02-29' isn't a date");
+        assert!(super::Until::parse("2024-02-29T09:00").is_ok());
+        assert_eq!(err("2pm"), "--until must be HH:MM[:SS] or YYYY-MM-DDTHH:MM[:SS], not '2pm'");
+        assert!(err("09:00+2").starts_with("--until must be"));
+        assert!(err("2025-07T09:00").starts_with("--until must be"));
+        // Test: --until is the duration, so it can't come with another
+        assert_eq!(run(&["--until", "09:00", "5:00"]).unwrap_err(), "--until sets the duration, it can't have one too");
+        assert!(run(&["--stopwatch", "--until=09:00"]).is_err());
+        assert!(run(&["--until=09:00"]).unwrap().until.is_some());
+    }
+
+    #[test]
+    fn cron_is_the_duration() {
+        assert_eq!(run(&["--cron", "0 9 * * 1-5"]).unwrap().cron, Some(super::Cron::parse("0 9 * * 1-5").unwrap()));
+        assert!(run(&["--cron", "0 9 * *"]).unwrap_err().starts_with("--cron needs five fields"));
+        assert_eq!(run(&["--cron", "* * * * *", "5:00"]).unwrap_err(), "--cron sets the duration, it can't have one too");
+        assert!(run(&["--cron", "* * * * *", "--until", "09:00"]).is_err());
+        assert!(run(&["--cron", "* * * * *", "--elapsed", "1m"]).is_err());
+        assert!(run(&["--cron", "* * * * *", "--wait-for-key"]).is_err());
+        // Test: Unlike --until it comes round again
+        assert!(run(&["--cron", "*/5 * * * *", "--repeat", "forever"]).is_ok());
+    }
+
+    #[test]
+    fn keypad_reads_digits_like_a_microwave() {
+        assert_eq!(super::keypad_duration("2500"), 1500);
+        assert_eq!(super::keypad_duration("90"), 90);
+        assert_eq!(super::keypad_duration("130"), 90);
+        assert_eq!(super::keypad_duration("13000"), 5400);
+        assert_eq!(super::keypad_duration(""), 0);
+        // Test: It's the duration, so it can't come with another
+        assert!(run(&["--keypad"]).unwrap().keypad);
+        assert_eq!(run(&["--keypad", "5:00"]).unwrap_err(), "--keypad types the duration in, it can't have one too");
+        assert!(run(&["--keypad", "--stopwatch"]).is_err());
+        assert!(run(&["--keypad", "--elapsed", "1m"]).is_err());
+        // Test: Nothing that needs the length before it's typed
+        assert_eq!(run(&["--keypad", "--then", "5m"]).unwrap_err(), "--then doesn't work with --keypad");
+        assert_eq!(run(&["--keypad", "--calendar", "work.ics"]).unwrap_err(), "--calendar doesn't work with --keypad");
+    }
+
+    #[test]
+    fn elapsed_takes_a_duration() {
+        // Test: Any duration form works, and a stopwatch can have one too
+        assert_eq!(run(&["10:00", "--elapsed", "7m"]).unwrap().elapsed, Some(420));
+        assert_eq!(run(&["--stopwatch", "--elapsed=1:00"]).unwrap().elapsed, Some(60));
+        assert_eq!(run(&["--until", "09:00", "--elapsed", "1m"]).unwrap_err(), "--elapsed doesn't work with --until");
+    }
+
     // TODO: Need leading zero tests for ss, mm:ss, hh:mm:ss formats
 }
@@ -0,0 +1,553 @@
+// src/config.rs
+// User defaults from $XDG_CONFIG_HOME/timerterm/config.toml (~/.config when unset).
+// Only the TOML we need is understood: [section] headers and `key = value` lines
+// with strings, integers or booleans. No arrays, inline tables or dotted keys.
+// File paths may start with ~/ for the home directory.
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::activity::Activity;
+use crate::autolabel::AutoLabel;
+use crate::cli;
+use crate::morse::{self, Morse};
+use crate::notify::Fallback;
+use crate::power::PowerSave;
+use crate::render::Layout;
+use crate::serial;
+use crate::template::Template;
+use crate::terminal::ansi;
+use crate::tone::{self, Tones};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "config: {}", self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Error {
+    // Without the "config: " prefix, for other files in the same format
+    pub fn message(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Str(String),
+    Int(i64),
+    Bool(bool),
+}
+
+// One `key = value` line, key prefixed with its section ("timer.duration")
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub line: usize,
+    pub key: String,
+    pub value: Value,
+}
+
+// Settings CLI args fall back to. Defaults match the behavior without a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub duration: u32,               // Secs, used when no duration is given
+    pub max_duration: u32,           // Secs, longer countdowns need confirming
+    pub adjust_step: u32,            // Secs the + and - keys add or take off
+    pub auto_label: AutoLabel,       // Where a label comes from without --label
+    pub tick: Duration,              // Polling interval, for what can't wake the loop
+    pub power_save: PowerSave,       // Poll only once a second, or only on battery
+    pub color: Option<String>,       // Digit color, one of the ansi::fg names
+    pub layout: Layout,              // Which of remaining/elapsed is drawn big
+    pub plain: bool,                 // A line per update even on a terminal
+    pub quotes: Option<PathBuf>,     // File of lines shown under the timer
+    pub world_clock: Vec<String>,    // Time zones whose clocks are shown under it too
+    pub bell: bool,                  // Ring the terminal bell on alarms
+    pub sound: Option<PathBuf>,      // Alarm sound file, played instead of the bell
+    pub chime: Option<PathBuf>,      // Played between --repeat cycles instead
+    pub volume: u8,                  // Alarm volume percent
+    pub pause_media: bool,           // Pause media players while a finished timer is held
+    pub escalate_after: Option<u32>, // Secs a held timer goes unacknowledged before escalating
+    pub escalate_exec: Option<String>, // Run as it escalates
+    pub notify: bool,                // Desktop notifications on completion
+    pub notify_fallback: Vec<Fallback>, // Tried in order when one can't be sent
+    pub gpio_pin: Option<u32>,       // Pulsed on alarms, needs the gpio feature
+    pub gpio_pulse: Duration,        // How long the pin stays high
+    pub serial_baud: u32,            // For --serial, one of serial::BAUDS
+    pub serial_format: String,       // Each --serial line, see serial.rs
+    pub morse: Morse,                // Alarm and chime spelled out in Morse instead
+    pub tone: Tones,                 // Alarm and chime synthesized, without a sound file
+    pub pause_reasons: bool,         // Ask why on every pause, for the log
+    pub pause_on_suspend: bool,      // Don't count time stopped with Ctrl+Z
+    pub reflection: bool,            // Ask what got done after each pomodoro work block
+    pub dim: Option<String>,         // Run as a --strict-breaks break starts
+    pub undim: Option<String>,       // And as it ends, however it ends
+    pub log: bool,                   // Record runs in the history log
+    pub presets: Vec<(String, u32)>, // [presets] name = duration, in file order
+    pub templates: Vec<(String, Template)>, // [presets] that are command lines, see template.rs
+    pub break_activities: Vec<Activity>, // Suggested on pomodoro breaks, in file order
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            duration: 600,
+            max_duration: 24 * 3600,
+            adjust_step: 60,
+            auto_label: AutoLabel::default(),
+            tick: Duration::from_millis(100),
+            power_save: PowerSave::default(),
+            color: None,
+            layout: Layout::default(),
+            plain: false,
+            quotes: None,
+            world_clock: Vec::new(),
+            bell: true,
+            sound: None,
+            chime: None,
+            volume: 100,
+            pause_media: false,
+            escalate_after: None,
+            escalate_exec: None,
+            notify: true,
+            notify_fallback: Vec::new(),
+            gpio_pin: None,
+            gpio_pulse: Duration::from_millis(500),
+            serial_baud: serial::DEFAULT_BAUD,
+            serial_format: serial::DEFAULT_FORMAT.to_string(),
+            morse: Morse::default(),
+            tone: Tones::default(),
+            pause_reasons: false,
+            pause_on_suspend: false,
+            reflection: false,
+            dim: None,
+            undim: None,
+            log: true,
+            presets: Vec::new(),
+            templates: Vec::new(),
+            break_activities: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    pub fn from_toml(text: &str) -> Result<Config, Error> {
+        let mut config = Config::default();
+        let mut timers = Vec::new(); // (line, name, secs), matched up once every activity is in
+        for Entry { line, key, value } in parse_toml(text)? {
+            let bad = |want: &str| Err(Error(format!("line {}: {} must be {}", line, key, want)));
+            match (key.as_str(), value) {
+                ("timer.duration", value) => match duration_secs(value) {
+                    Ok(secs) => config.duration = secs,
+                    Err(why) => return bad(&why),
+                },
+                ("timer.max_duration", value) => match duration_secs(value) {
+                    Ok(secs) => config.max_duration = secs,
+                    Err(why) => return bad(&why),
+                },
+                ("timer.adjust_step", value) => match duration_secs(value) {
+                    Ok(0) => return bad("longer than 0 seconds"),
+                    Ok(secs) => config.adjust_step = secs,
+                    Err(why) => return bad(&why),
+                },
+                ("timer.auto_label", value) => {
+                    let source = if let Value::Str(s) = value { AutoLabel::parse(&s) } else { None };
+                    match source {
+                        Some(source) => config.auto_label = source,
+                        None => return bad(&format!("one of {}", AutoLabel::NAMES.join(", "))),
+                    }
+                }
+                (preset, Value::Str(s)) if preset.starts_with("presets.") && Template::is_template(&s) => match Template::parse(&s) {
+                    Ok(template) => config.templates.push((preset["presets.".len()..].to_string(), template)),
+                    Err(why) => return bad(&format!("a duration or a command line ({})", why)),
+                },
+                (preset, value) if preset.starts_with("presets.") => match duration_secs(value) {
+                    Ok(secs) => config.presets.push((preset["presets.".len()..].to_string(), secs)),
+                    Err(why) => return bad(&why),
+                },
+                (activity, Value::Str(text)) if activity.starts_with("break_activities.") => {
+                    let name = activity["break_activities.".len()..].to_string();
+                    config.break_activities.push(Activity { name, text, secs: None });
+                }
+                (activity, _) if activity.starts_with("break_activities.") => return bad("a string"),
+                (timer, value) if timer.starts_with("break_timers.") => match duration_secs(value) {
+                    Ok(0) => return bad("longer than 0 seconds"),
+                    Ok(secs) => timers.push((line, timer["break_timers.".len()..].to_string(), secs)),
+                    Err(why) => return bad(&why),
+                },
+                // Anything slower than 1s would make an ack feel ignored
+                ("timer.tick_ms", Value::Int(n @ 1..=1000)) => config.tick = Duration::from_millis(n as u64),
+                ("timer.tick_ms", _) => return bad("between 1 and 1000"),
+                ("timer.power_save", value) => {
+                    let mode = if let Value::Str(s) = value { PowerSave::parse(&s) } else { None };
+                    match mode {
+                        Some(mode) => config.power_save = mode,
+                        None => return bad(&format!("one of {}", PowerSave::NAMES.join(", "))),
+                    }
+                }
+                ("display.color", Value::Str(s)) if ansi::fg(&s).is_some() => config.color = Some(s),
+                ("display.color", _) => return bad("a color name like \"cyan\""),
+                ("display.layout", value) => {
+                    let layout = if let Value::Str(s) = value { Layout::parse(&s) } else { None };
+                    match layout {
+                        Some(layout) => config.layout = layout,
+                        None => return bad(&format!("one of {}", Layout::NAMES.join(", "))),
+                    }
+                }
+                ("display.plain", Value::Bool(b)) => config.plain = b,
+                ("display.quotes", Value::Str(s)) => config.quotes = Some(expand_home(&s)),
+                ("display.quotes", _) => return bad("a file path"),
+                ("display.world_clock", Value::Str(s)) => {
+                    let zones: Vec<String> = s.split(',').map(|zone| zone.trim().to_string()).collect();
+                    if zones.iter().any(String::is_empty) { return bad("time zones like \"Europe/London, Asia/Tokyo\""); }
+                    config.world_clock = zones;
+                }
+                ("display.world_clock", _) => return bad("time zones like \"Europe/London, Asia/Tokyo\""),
+                ("sound.bell", Value::Bool(b)) => config.bell = b,
+                ("sound.file", Value::Str(s)) => config.sound = Some(expand_home(&s)),
+                ("sound.file", _) => return bad("a file path"),
+                ("sound.chime", Value::Str(s)) => config.chime = Some(expand_home(&s)),
+                ("sound.chime", _) => return bad("a file path"),
+                ("sound.volume", Value::Int(n @ 0..=100)) => config.volume = n as u8,
+                ("sound.volume", _) => return bad("between 0 and 100"),
+                ("sound.pause_media", Value::Bool(b)) => config.pause_media = b,
+                ("escalate.after", value) => match duration_secs(value) {
+                    Ok(0) => return bad("longer than 0 seconds"),
+                    Ok(secs) => config.escalate_after = Some(secs),
+                    Err(why) => return bad(&why),
+                },
+                ("escalate.exec", Value::Str(s)) => config.escalate_exec = Some(s),
+                ("escalate.exec", _) => return bad("a command"),
+                ("notify.enabled", Value::Bool(b)) => config.notify = b,
+                ("notify.fallback", value) => {
+                    let chain = if let Value::Str(s) = value { s.split(',').map(|name| Fallback::parse(name.trim())).collect() } else { None };
+                    match chain {
+                        Some(chain) => config.notify_fallback = chain,
+                        None => return bad(&format!("a list of {}, like \"osc, stderr\"", Fallback::NAMES.join(", "))),
+                    }
+                }
+                ("timer.pause_reasons", Value::Bool(b)) => config.pause_reasons = b,
+                ("timer.pause_on_suspend", Value::Bool(b)) => config.pause_on_suspend = b,
+                ("pomodoro.reflection", Value::Bool(b)) => config.reflection = b,
+                ("pomodoro.dim", Value::Str(s)) => config.dim = Some(s),
+                ("pomodoro.undim", Value::Str(s)) => config.undim = Some(s),
+                ("pomodoro.dim" | "pomodoro.undim", _) => return bad("a command"),
+                ("log.enabled", Value::Bool(b)) => config.log = b,
+                ("sound.bell" | "sound.pause_media" | "display.plain" | "notify.enabled" | "timer.pause_reasons" | "timer.pause_on_suspend" | "pomodoro.reflection" | "log.enabled", _) => {
+                    return bad("true or false");
+                }
+                ("gpio.pin", Value::Int(n)) if u32::try_from(n).is_ok() => config.gpio_pin = Some(n as u32),
+                ("gpio.pin", _) => return bad("a pin number"),
+                ("gpio.pulse_ms", Value::Int(n @ 1..=60_000)) => config.gpio_pulse = Duration::from_millis(n as u64),
+                ("gpio.pulse_ms", _) => return bad("between 1 and 60000"),
+                ("serial.baud", Value::Int(n)) if serial::BAUDS.iter().any(|&baud| baud as i64 == n) => config.serial_baud = n as u32,
+                ("serial.baud", _) => {
+                    let bauds: Vec<String> = serial::BAUDS.iter().map(u32::to_string).collect();
+                    return bad(&format!("one of {}", bauds.join(", ")));
+                }
+                ("serial.format", Value::Str(s)) => config.serial_format = s,
+                ("serial.format", _) => return bad("a string like \"{time}\""),
+                ("morse.alarm" | "morse.chime", Value::Str(s)) => match morse::encode(&s) {
+                    Ok(code) if key == "morse.alarm" => config.morse.alarm = Some(code),
+                    Ok(code) => config.morse.chime = Some(code),
+                    Err(why) => return bad(&format!("letters and digits ({})", why)),
+                },
+                ("morse.alarm" | "morse.chime", _) => return bad("letters and digits"),
+                ("tone.alarm" | "tone.chime", Value::Str(s)) => match tone::parse(&s) {
+                    Ok(notes) if key == "tone.alarm" => config.tone.alarm = Some(notes),
+                    Ok(notes) => config.tone.chime = Some(notes),
+                    Err(why) => return bad(&format!("notes like \"880/200 0/100 660+880/400\" ({})", why)),
+                },
+                ("tone.alarm" | "tone.chime", _) => return bad("notes like \"880/200 0/100 660+880/400\""),
+                ("morse.unit_ms", Value::Int(n @ 10..=1000)) => config.morse.unit = Duration::from_millis(n as u64),
+                ("morse.unit_ms", _) => return bad("between 10 and 1000"),
+                _ => {} // Unknown keys are left for newer versions
+            }
+        }
+        for (line, name, secs) in timers {
+            match config.break_activities.iter_mut().find(|a| a.name == name) {
+                Some(activity) => activity.secs = Some(secs),
+                None => return Err(Error(format!("line {}: break_timers.{} has no break_activities.{} to time", line, name, name))),
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn preset(&self, name: &str) -> Option<u32> {
+        self.presets.iter().find(|(n, _)| n == name).map(|&(_, secs)| secs)
+    }
+}
+
+// Same formats as the command line, or plain seconds.
+// The error finishes "<key> must be ...".
+fn duration_secs(value: Value) -> Result<u32, String> {
+    let want = || "a duration like \"25:00\"".to_string();
+    match value {
+        Value::Str(s) => cli::parse_duration(&s).map_err(|e| format!("{} ({})", want(), e)),
+        Value::Int(n) => u32::try_from(n).map_err(|_| want()),
+        Value::Bool(_) => Err(want()),
+    }
+}
+
+// A leading ~/ from $HOME, as a shell would. Left alone without a $HOME.
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+pub fn path() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join("timerterm").join("config.toml"))
+}
+
+// Read the config file, a missing file just means defaults
+pub fn load() -> Result<Config, Error> {
+    let Some(path) = path() else { return Ok(Config::default()) };
+    match std::fs::read_to_string(&path) {
+        Ok(text) => Config::from_toml(&text).map_err(|e| Error(format!("{}: {}", path.display(), e.0))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(Error(format!("{}: {}", path.display(), e))),
+    }
+}
+
+pub fn parse_toml(text: &str) -> Result<Vec<Entry>, Error> {
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut section = String::new();
+    for (i, raw) in text.lines().enumerate() {
+        let line = i + 1;
+        let err = |msg: &str| Error(format!("line {}: {}", line, msg));
+        let rest = raw.trim();
+        if rest.is_empty() || rest.starts_with('#') { continue; }
+
+        if let Some(header) = rest.strip_prefix('[') {
+            let (name, tail) = header.split_once(']').ok_or_else(|| err("unclosed section header"))?;
+            if !is_bare_key(name.trim()) { return Err(err("bad section name")); }
+            if !is_comment(tail) { return Err(err("unexpected text after section header")); }
+            section = name.trim().to_string();
+            continue;
+        }
+
+        let (key, value) = rest.split_once('=').ok_or_else(|| err("expected key = value"))?;
+        let key = key.trim();
+        if !is_bare_key(key) { return Err(err("bad key")); }
+        let (value, tail) = parse_value(value.trim()).ok_or_else(|| err("bad value"))?;
+        if !is_comment(tail) { return Err(err("unexpected text after value")); }
+
+        let key = if section.is_empty() { key.to_string() } else { format!("{}.{}", section, key) };
+        if entries.iter().any(|e| e.key == key) { return Err(err(&format!("duplicate key {}", key))); }
+        entries.push(Entry { line, key, value });
+    }
+    Ok(entries)
+}
+
+fn is_bare_key(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn is_comment(s: &str) -> bool {
+    let s = s.trim();
+    s.is_empty() || s.starts_with('#')
+}
+
+// One value from the start of `s`, plus whatever follows it
+fn parse_value(s: &str) -> Option<(Value, &str)> {
+    if let Some(body) = s.strip_prefix('\'') {
+        // Literal string, no escapes
+        let (text, tail) = body.split_once('\'')?;
+        return Some((Value::Str(text.to_string()), tail));
+    }
+    if let Some(body) = s.strip_prefix('"') {
+        let mut out = String::new();
+        let mut chars = body.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => return Some((Value::Str(out), &body[i + 1..])),
+                '\\' => out.push(match chars.next()?.1 {
+                    'n' => '\n',
+                    't' => '\t',
+                    '"' => '"',
+                    '\\' => '\\',
+                    _ => return None,
+                }),
+                c => out.push(c),
+            }
+        }
+        return None; // Unterminated
+    }
+    let end = s.find(|c: char| c.is_whitespace() || c == '#').unwrap_or(s.len());
+    let (word, tail) = s.split_at(end);
+    let value = match word {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        // Underscores are allowed between digits only
+        _ if !word.starts_with('_') && !word.ends_with('_') && !word.contains("__") => {
+            Value::Int(word.replace('_', "").parse().ok()?)
+        }
+        _ => return None,
+    };
+    Some((value, tail))
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_toml_reads_sections_and_values() {
+        let text = "# defaults\ntop = 1\n\n[timer]\nduration = \"25:00\" # pomodoro\ntick_ms = 1_000\n[sound]\nbell = false\n";
+        let entries = parse_toml(text).unwrap();
+        let pairs: Vec<(&str, &Value)> = entries.iter().map(|e| (e.key.as_str(), &e.value)).collect();
+        // Test: Keys are prefixed by section, comments and blanks skipped
+        assert_eq!(pairs, vec![
+            ("top", &Value::Int(1)),
+            ("timer.duration", &Value::Str("25:00".to_string())),
+            ("timer.tick_ms", &Value::Int(1000)),
+            ("sound.bell", &Value::Bool(false)),
+        ]);
+        assert_eq!(entries[1].line, 5);
+    }
+
+    #[test]
+    fn parse_toml_handles_strings() {
+        // Test: Basic strings unescape, literal strings don't
+        let entries = parse_toml("a = \"say \\\"hi\\\"\\n\"\nb = 'C:\\dir # not a comment'").unwrap();
+        assert_eq!(entries[0].value, Value::Str("say \"hi\"\n".to_string()));
+        assert_eq!(entries[1].value, Value::Str("C:\\dir # not a comment".to_string()));
+    }
+
+    #[test]
+    fn parse_toml_reports_line_of_error() {
+        // Test: Each kind of bad input names its line
+        let err = |text: &str| parse_toml(text).unwrap_err().to_string();
+        assert_eq!(err("[timer\n"), "config: line 1: unclosed section header");
+        assert_eq!(err("ok = 1\nnope\n"), "config: line 2: expected key = value");
+        assert_eq!(err("a = \"open\n"), "config: line 1: bad value");
+        assert_eq!(err("a = [1, 2]\n"), "config: line 1: bad value");
+        assert_eq!(err("a = 1 2\n"), "config: line 1: unexpected text after value");
+        assert_eq!(err("a = 1\na = 2\n"), "config: line 2: duplicate key a");
+    }
+
+    #[test]
+    fn config_defaults_without_settings() {
+        // Test: Empty file and unknown keys leave the built-in defaults
+        assert_eq!(Config::from_toml("").unwrap(), Config::default());
+        assert_eq!(Config::from_toml("[future]\nthing = true\n").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn config_applies_known_keys() {
+        let text = "[timer]\nduration = \"1:30\"\ntick_ms = 50\n[display]\ncolor = \"cyan\"\n[notify]\nenabled = false\n";
+        let config = Config::from_toml(text).unwrap();
+        assert_eq!(config.duration, 90);
+        assert_eq!(config.tick, Duration::from_millis(50));
+        assert_eq!(config.color.as_deref(), Some("cyan"));
+        assert!(!config.notify);
+        assert_eq!(Config::from_toml("[notify]\nfallback = \"osc, stderr\"").unwrap().notify_fallback, [Fallback::Osc, Fallback::Stderr]);
+        assert!(Config::from_toml("[notify]\nfallback = \"osc, email\"").is_err());
+        assert_eq!(Config::from_toml("[timer]\nmax_duration = \"2h\"").unwrap().max_duration, 7200);
+        assert_eq!(Config::from_toml("[timer]\nadjust_step = \"30s\"").unwrap().adjust_step, 30);
+        assert!(Config::from_toml("[timer]\nadjust_step = 0").is_err());
+        assert_eq!(Config::from_toml("[timer]\nauto_label = \"tmux\"").unwrap().auto_label, AutoLabel::Tmux);
+        assert!(Config::from_toml("[timer]\nauto_label = true").is_err());
+        let config = Config::from_toml("[display]\nlayout = \"elapsed+remaining\"\nquotes = \"~/quotes.txt\"\nplain = true").unwrap();
+        assert_eq!(config.layout, Layout::ElapsedRemaining);
+        assert!(config.plain);
+        assert_eq!(Config::from_toml("[display]\nworld_clock = \"Europe/London,  Asia/Tokyo\"").unwrap().world_clock, ["Europe/London", "Asia/Tokyo"]);
+        assert_eq!(Config::from_toml("[timer]\npower_save = \"auto\"").unwrap().power_save, PowerSave::Auto);
+        // Test: Paths under ~/ are taken from $HOME
+        let home = PathBuf::from(std::env::var_os("HOME").unwrap());
+        assert_eq!(config.quotes, Some(home.join("quotes.txt")));
+        assert_eq!(expand_home("/tmp/~/x"), PathBuf::from("/tmp/~/x"));
+        assert_eq!(expand_home("~user/x"), PathBuf::from("~user/x"));
+        let config = Config::from_toml("[sound]\nfile = '/tmp/ding.wav'\nchime = '/tmp/dong.wav'\nvolume = 40\npause_media = true\n").unwrap();
+        assert_eq!(config.sound, Some(PathBuf::from("/tmp/ding.wav")));
+        assert_eq!(config.chime, Some(PathBuf::from("/tmp/dong.wav")));
+        assert_eq!(config.volume, 40);
+        assert!(config.pause_media);
+        // Test: GPIO settings parse with or without the gpio feature
+        let config = Config::from_toml("[gpio]\npin = 17\npulse_ms = 2000\n").unwrap();
+        assert_eq!(config.gpio_pin, Some(17));
+        assert_eq!(config.gpio_pulse, Duration::from_secs(2));
+        let config = Config::from_toml("[serial]\nbaud = 115200\nformat = \"T{secs}\"\n").unwrap();
+        assert_eq!((config.serial_baud, config.serial_format.as_str()), (115200, "T{secs}"));
+        assert!(Config::from_toml("[serial]\nbaud = 9601\n").is_err());
+        let config = Config::from_toml("[escalate]\nafter = \"5:00\"\nexec = \"notify-phone\"\n").unwrap();
+        assert_eq!((config.escalate_after, config.escalate_exec.as_deref()), (Some(300), Some("notify-phone")));
+        assert!(Config::from_toml("[escalate]\nafter = 0\n").is_err());
+        let config = Config::from_toml("[morse]\nalarm = \"SOS\"\nunit_ms = 80\n").unwrap();
+        assert_eq!(config.morse.alarm, Some(morse::encode("SOS").unwrap()));
+        assert_eq!((config.morse.chime, config.morse.unit), (None, Duration::from_millis(80)));
+        let config = Config::from_toml("[tone]\nchime = \"1320/150\"\n").unwrap();
+        assert_eq!(config.tone.chime, Some(vec![(vec![1320], Duration::from_millis(150))]));
+        assert_eq!(config.tone.alarm, None);
+        let config = Config::from_toml("[timer]\npause_reasons = true\npause_on_suspend = true\n[pomodoro]\nreflection = true\ndim = \"xset dpms force off\"\n[log]\nenabled = false\n").unwrap();
+        assert!(config.pause_reasons && config.pause_on_suspend && config.reflection && !config.log);
+        assert_eq!((config.dim.as_deref(), config.undim), (Some("xset dpms force off"), None));
+        // Test: Plain integers are seconds
+        assert_eq!(Config::from_toml("[timer]\nduration = 45").unwrap().duration, 45);
+    }
+
+    #[test]
+    fn config_reads_presets() {
+        let config = Config::from_toml("[presets]\ntea = \"3:00\"\nworkout = \"45:00\"\nnap = 1200\n").unwrap();
+        // Test: Presets keep file order and share the duration formats
+        let names: Vec<&str> = config.presets.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, ["tea", "workout", "nap"]);
+        assert_eq!(config.preset("tea"), Some(180));
+        assert_eq!(config.preset("nap"), Some(1200));
+        assert_eq!(config.preset("coffee"), None);
+        assert!(Config::from_toml("[presets]\ntea = true").is_err());
+        // Test: A command line is a template, kept apart from the durations
+        let config = Config::from_toml("[presets]\ntea = \"3:00\"\nreview = \"{minutes}m --label 'Review {pr}'\"\n").unwrap();
+        assert_eq!(config.presets, vec![("tea".to_string(), 180)]);
+        assert_eq!(config.templates[0].0, "review");
+        assert_eq!(config.templates[0].1.placeholders(), ["minutes", "pr"]);
+        assert_eq!(Config::from_toml("[presets]\nreview = \"5m --label 'x\"").unwrap_err().message(),
+            "line 2: presets.review must be a duration or a command line (unclosed ' quote)");
+    }
+
+    #[test]
+    fn config_reads_break_activities() {
+        // Test: Timers attach to the activity with the same name, wherever they come in the file
+        let text = "[break_timers]\neyes = \"20s\"\n[break_activities]\nstretch = \"Stand up and stretch\"\neyes = \"Look 20ft away\"\n";
+        let config = Config::from_toml(text).unwrap();
+        assert_eq!(config.break_activities, vec![
+            Activity { name: "stretch".to_string(), text: "Stand up and stretch".to_string(), secs: None },
+            Activity { name: "eyes".to_string(), text: "Look 20ft away".to_string(), secs: Some(20) },
+        ]);
+        // Test: A timer with nothing to time, or no time at all, is an error
+        let err = |text: &str| Config::from_toml(text).unwrap_err().to_string();
+        assert_eq!(err("[break_timers]\nnap = 60\n"), "config: line 2: break_timers.nap has no break_activities.nap to time");
+        assert_eq!(err("[break_activities]\nnap = \"Nap\"\n[break_timers]\nnap = 0\n"), "config: line 4: break_timers.nap must be longer than 0 seconds");
+    }
+
+    #[test]
+    fn config_rejects_bad_values() {
+        // Test: Wrong types and out of range values point at the line
+        let err = |text: &str| Config::from_toml(text).unwrap_err().to_string();
+        assert_eq!(err("[timer]\ntick_ms = 0"), "config: line 2: timer.tick_ms must be between 1 and 1000");
+        assert_eq!(err("[timer]\npower_save = true"), "config: line 2: timer.power_save must be one of auto, on, off");
+        assert_eq!(err("[display]\nworld_clock = \"UTC,\""), "config: line 2: display.world_clock must be time zones like \"Europe/London, Asia/Tokyo\"");
+        assert_eq!(err("[display]\ncolor = \"plaid\""), "config: line 2: display.color must be a color name like \"cyan\"");
+        assert_eq!(err("[sound]\nbell = \"yes\""), "config: line 2: sound.bell must be true or false");
+        assert_eq!(err("[sound]\nvolume = 101"), "config: line 2: sound.volume must be between 0 and 100");
+        assert_eq!(err("[tone]\nalarm = \"beep\""), "config: line 2: tone.alarm must be notes like \"880/200 0/100 660+880/400\" ('beep' isn't HZ/MS like 880/200)");
+        assert_eq!(err("[morse]\nchime = \"go!\""), "config: line 2: morse.chime must be letters and digits (no Morse code for '!')");
+        assert_eq!(err("[timer]\nduration = -5"), "config: line 2: timer.duration must be a duration like \"25:00\"");
+        // Test: Bad duration strings pass on the failing token
+        assert_eq!(
+            err("[timer]\nduration = \"1h3x\""),
+            "config: line 2: timer.duration must be a duration like \"25:00\" ('3x' in '1h3x' has an unknown unit, expected h, m or s)",
+        );
+    }
+}
@@ -0,0 +1,106 @@
+// src/control.rs
+// Text commands for driving a running timer from outside its terminal, one
+// per line. `--control-fifo` reads them from a named pipe:
+//   echo pause > /tmp/timer.fifo
+// Anything that later takes commands over IPC should parse them here too.
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::thread;
+
+use crate::cli;
+use crate::input::Action;
+
+pub const COMMANDS: &str = "pause, resume, toggle, restart, cancel, +DURATION or -DURATION";
+
+// "pause", "+60", "-1m30s"... Durations take the usual forms, bare numbers
+// are seconds. Blank lines are Ok(None).
+pub fn parse(line: &str) -> Result<Option<Action>, String> {
+    let line = line.trim();
+    let adjust = |sign: i64, by: &str| cli::parse_duration(by).map(|secs| Some(Action::Adjust(sign * secs as i64)));
+    match line {
+        "" => Ok(None),
+        "pause" => Ok(Some(Action::Pause)),
+        "resume" => Ok(Some(Action::Resume)),
+        "toggle" => Ok(Some(Action::TogglePause)),
+        "restart" => Ok(Some(Action::Restart)),
+        "cancel" => Ok(Some(Action::Quit)),
+        _ => match (line.strip_prefix('+'), line.strip_prefix('-')) {
+            (Some(by), _) => adjust(1, by),
+            (_, Some(by)) => adjust(-1, by),
+            _ => Err(format!("unknown command '{}', expected {}", line, COMMANDS)),
+        },
+    }
+}
+
+// A FIFO commands are read from, removed again on drop if it was made here
+pub struct Fifo {
+    path: PathBuf,
+    made: bool,
+}
+
+impl Fifo {
+    // Use the FIFO at `path`, making it if there's nothing there yet
+    pub fn open(path: &Path) -> io::Result<Fifo> {
+        let made = crate::platform::make_fifo(path)?;
+        Ok(Fifo { path: path.to_path_buf(), made })
+    }
+
+    // Forward commands to `actions` from a background thread, for as long as
+    // the receiver is around. Each writer's lines are read until it closes
+    // the pipe, then the next one is waited for. Bad commands are dropped,
+    // there's no way to answer a writer and stderr is under the timer.
+    pub fn listen(&self, actions: Sender<Action>) {
+        let path = self.path.clone();
+        thread::spawn(move || loop {
+            // Blocks until something opens the FIFO to write
+            let Ok(file) = std::fs::File::open(&path) else { return };
+            for line in BufReader::new(file).lines() {
+                let Ok(line) = line else { break };
+                if let Ok(Some(action)) = parse(&line) {
+                    if actions.send(action).is_err() { return; }
+                }
+            }
+        });
+    }
+}
+
+impl Drop for Fifo {
+    fn drop(&mut self) {
+        if self.made { let _ = std::fs::remove_file(&self.path); }
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_knows_each_command() {
+        // Test: Words map to actions, signed durations to adjustments
+        assert_eq!(parse("pause"), Ok(Some(Action::Pause)));
+        assert_eq!(parse(" resume\r"), Ok(Some(Action::Resume)));
+        assert_eq!(parse("cancel"), Ok(Some(Action::Quit)));
+        assert_eq!(parse("+60"), Ok(Some(Action::Adjust(60))));
+        assert_eq!(parse("-1m30s"), Ok(Some(Action::Adjust(-90))));
+        assert_eq!(parse(""), Ok(None));
+        // Test: Unknown words and bad durations are errors
+        assert_eq!(parse("stop"), Err(format!("unknown command 'stop', expected {}", COMMANDS)));
+        assert!(parse("+soon").is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn fifo_is_made_and_removed() {
+        // Test: A FIFO made for the run goes away with it, a file is refused
+        let path = std::env::temp_dir().join(format!("timerterm-fifo-{}", std::process::id()));
+        let fifo = Fifo::open(&path).unwrap();
+        assert!(Fifo::open(&path).is_ok_and(|again| !again.made));
+        drop(fifo);
+        assert!(!path.exists());
+        std::fs::write(&path, "").unwrap();
+        assert!(Fifo::open(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,212 @@
+// src/cron.rs
+// --cron "0 9 * * 1-5": count down to the next time a crontab line would
+// run, in local time. Five fields, minute hour day-of-month month weekday,
+// each `*`, a number, a range `1-5`, a step `*/15` or `1-30/2`, or a list
+// of those. Months and weekdays may be names (jan, mon), and Sunday is 0 or
+// 7. As in cron, a line restricting both day fields runs on either.
+use crate::{calendar, platform};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cron {
+    text: String,      // As given, for errors
+    minutes: u64,      // Bit n set when minute n matches
+    hours: u64,
+    days: u64,         // 1 to 31
+    months: u64,       // 1 to 12
+    weekdays: u64,     // 0 to 6, Sunday first
+    any_day: bool,     // Day of month was *
+    any_weekday: bool, // Weekday was *
+}
+
+const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+// How many years ahead to look for a match, "0 0 29 2 1" can take decades
+const HORIZON_DAYS: i64 = 30 * 366;
+
+impl Cron {
+    pub fn parse(text: &str) -> Result<Cron, String> {
+        let fields: Vec<&str> = text.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!("--cron needs five fields like \"0 9 * * 1-5\", not '{}'", text));
+        };
+        let weekdays = field(weekday, 0, 7, &WEEKDAYS, "weekday")?;
+        Ok(Cron {
+            text: text.to_string(),
+            minutes: field(minute, 0, 59, &[], "minute")?,
+            hours: field(hour, 0, 23, &[], "hour")?,
+            days: field(day, 1, 31, &[], "day of month")?,
+            months: field(month, 1, 12, &MONTHS, "month")?,
+            // 7 is Sunday too
+            weekdays: (weekdays | weekdays >> 7) & 0x7f,
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+
+    // The first matching minute after `now` (UNIX seconds), in local time
+    pub fn next_after(&self, now: i64) -> Result<i64, String> {
+        self.next_in(now, system_local)
+    }
+
+    // Local clocks are read off real instants, so a time skipped when DST
+    // starts fires at the first minute after the gap, and one repeated when
+    // it ends fires only the first time round.
+    fn next_in(&self, now: i64, local: impl Fn(i64) -> Option<Local>) -> Result<i64, String> {
+        let (today, _) = local(now).ok_or("couldn't read the local time")?;
+        for day in today..today + HORIZON_DAYS {
+            let (_, m, d) = calendar::civil_from_days(day);
+            if !self.on_day(m, d, (day + 4).rem_euclid(7)) { continue; }
+            let (Some(midnight), Some(next_midnight)) = (start_of_day(day, &local), start_of_day(day + 1, &local)) else { continue };
+            // Minute of the day last seen, -1 before the first
+            let mut seen = -1;
+            let mut at = midnight;
+            while at < next_midnight {
+                let Some((_, minute)) = local(at) else { break };
+                // Every minute jumped past counts, and going back repeats none
+                if minute > seen && (seen + 1..=minute).any(|m| self.at_minute(m)) && at > now {
+                    return Ok(at);
+                }
+                seen = seen.max(minute);
+                at += 60;
+            }
+        }
+        Err(format!("'{}' doesn't come round in the next 30 years", self.text))
+    }
+
+    fn on_day(&self, month: i64, day: i64, weekday: i64) -> bool {
+        let by_date = self.days & 1 << day != 0;
+        let by_weekday = self.weekdays & 1 << weekday != 0;
+        let day_ok = match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (false, true) => by_date,
+            (true, false) => by_weekday,
+            (false, false) => by_date || by_weekday,
+        };
+        self.months & 1 << month != 0 && day_ok
+    }
+
+    fn at_minute(&self, minute_of_day: i64) -> bool {
+        self.hours & 1 << (minute_of_day / 60) != 0 && self.minutes & 1 << (minute_of_day % 60) != 0
+    }
+}
+
+// A local clock reading: days since 1970-01-01 and the minute of the day
+type Local = (i64, i64);
+
+fn system_local(at: i64) -> Option<Local> {
+    let tm = platform::local_time(at)?;
+    let day = calendar::days_from_civil(tm.tm_year as i64 + 1900, tm.tm_mon as i64 + 1, tm.tm_mday as i64);
+    Some((day, (tm.tm_hour * 60 + tm.tm_min) as i64))
+}
+
+// The first minute of a local day. UTC midnight moved by the offset there
+// lands within the hour of it, then a DST change that day is stepped over.
+fn start_of_day(day: i64, local: impl Fn(i64) -> Option<Local>) -> Option<i64> {
+    let (on, minute) = local(day * 86400)?;
+    let mut at = day * 86400 - ((on - day) * 1440 + minute) * 60;
+    while local(at)?.0 < day { at += 60; }
+    while local(at - 60)?.0 == day { at -= 60; }
+    Some(at)
+}
+
+// One field's matching values as bits, out of low..=high
+fn field(text: &str, low: u32, high: u32, names: &[&str], what: &str) -> Result<u64, String> {
+    let bad = || format!("--cron {} must be like *, 5, 1-5, */15 or 1,15, not '{}'", what, text);
+    let value = |s: &str| -> Result<u32, String> {
+        let lower = s.to_ascii_lowercase();
+        let n = match names.iter().position(|name| *name == lower) {
+            Some(i) => i as u32 + if low == 1 { 1 } else { 0 },
+            None => s.parse().map_err(|_| bad())?,
+        };
+        if n < low || n > high { return Err(format!("--cron {} must be between {} and {}, not {}", what, low, high, n)); }
+        Ok(n)
+    };
+    let mut bits = 0;
+    for part in text.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().ok().filter(|&s| s > 0).ok_or_else(bad)?),
+            None => (part, 1),
+        };
+        let (from, to) = match range.split_once('-') {
+            _ if range == "*" => (low, high),
+            Some((from, to)) => (value(from)?, value(to)?),
+            None if step > 1 => (value(range)?, high), // "5/15" is 5, 20, 35, 50
+            None => { let n = value(range)?; (n, n) }
+        };
+        if from > to { return Err(bad()); }
+        for n in (from..=to).step_by(step as usize) { bits |= 1 << n; }
+    }
+    Ok(bits)
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_each_field() {
+        let cron = Cron::parse("*/15 9-17 * jan-mar mon,fri").unwrap();
+        assert_eq!(cron.minutes, 1 | 1 << 15 | 1 << 30 | 1 << 45);
+        assert_eq!(cron.hours, (9..=17).fold(0, |bits, h| bits | 1 << h));
+        assert_eq!(cron.months, 0b1110);
+        assert_eq!(cron.weekdays, 1 << 1 | 1 << 5);
+        // Test: Sunday is 0 or 7
+        assert_eq!(Cron::parse("0 0 * * 7").unwrap().weekdays, 1);
+        assert_eq!(Cron::parse("5/20 * * * *").unwrap().minutes, 1 << 5 | 1 << 25 | 1 << 45);
+    }
+
+    #[test]
+    fn parse_rejects_bad_lines() {
+        assert_eq!(Cron::parse("0 9 * *"), Err("--cron needs five fields like \"0 9 * * 1-5\", not '0 9 * *'".to_string()));
+        assert_eq!(Cron::parse("60 * * * *"), Err("--cron minute must be between 0 and 59, not 60".to_string()));
+        assert_eq!(Cron::parse("* * * * fun"), Err("--cron weekday must be like *, 5, 1-5, */15 or 1,15, not 'fun'".to_string()));
+        assert!(Cron::parse("*/0 * * * *").is_err());
+        assert!(Cron::parse("5-1 * * * *").is_err());
+    }
+
+    #[test]
+    fn next_after_finds_the_next_minute() {
+        // Whatever the zone the tests run in, local times are checked through local_time
+        let now = 1_750_000_000;
+        let local = |at: i64| platform::local_time(at).map(|tm| (tm.tm_wday, tm.tm_hour, tm.tm_min)).unwrap();
+        let next = Cron::parse("30 9 * * 1-5").unwrap().next_after(now).unwrap();
+        let (weekday, hour, minute) = local(next);
+        assert!((1..=5).contains(&weekday));
+        assert_eq!((hour, minute), (9, 30));
+        assert!(next > now && next - now <= 4 * 86400);
+        // Test: Every minute is the next one, never now itself
+        let every = Cron::parse("* * * * *").unwrap();
+        assert_eq!(every.next_after(now - now % 60).unwrap(), now - now % 60 + 60);
+        // Test: Both day fields set is either one
+        let next = Cron::parse("0 0 13 * 5").unwrap().next_after(now).unwrap();
+        let tm = platform::local_time(next).unwrap();
+        assert!(tm.tm_mday == 13 || tm.tm_wday == 5);
+        assert!(Cron::parse("0 0 31 2 *").unwrap().next_after(now).is_err());
+    }
+
+    // New York, whatever zone the tests run in
+    fn new_york(at: i64) -> Option<Local> {
+        let local = at + crate::zone::Rule::parse("EST5EDT,M3.2.0,M11.1.0")?.offset(at) as i64;
+        Some((local.div_euclid(86400), local.rem_euclid(86400) / 60))
+    }
+
+    #[test]
+    fn dst_gaps_and_overlaps() {
+        // Test: 02:30 doesn't exist on 2025-03-09, so it fires at 03:00 EDT, 07:00 UTC
+        let cron = Cron::parse("30 2 * * *").unwrap();
+        assert_eq!(cron.next_in(1_741_496_400, new_york), Ok(1_741_503_600));
+        // The next day is back to 02:30, now EDT
+        assert_eq!(cron.next_in(1_741_503_600, new_york), Ok(1_741_503_600 + 86400 - 1800));
+        // Test: 01:30 comes twice on 2025-11-02, it fires at the first only
+        let cron = Cron::parse("30 1 * * *").unwrap();
+        assert_eq!(cron.next_in(1_762_059_600, new_york), Ok(1_762_061_400));
+        assert_eq!(cron.next_in(1_762_061_400, new_york), Ok(1_762_151_400));
+        // Test: Hourly skips no hour and doubles none across either change
+        let hourly = Cron::parse("0 * * * *").unwrap();
+        let mut at = 1_741_496_400;
+        let fired: Vec<i64> = (0..4).map(|_| { at = hourly.next_in(at, new_york).unwrap(); at }).collect();
+        assert_eq!(fired, [1, 2, 3, 4].map(|h| 1_741_496_400 + h * 3600));
+    }
+}
@@ -0,0 +1,50 @@
+// src/days.rs
+// `timerterm days DATE`: the whole days left until a local date, drawn big
+// and counted again once a minute so it rolls over at local midnight. It goes
+// by calendar dates rather than dividing seconds, so a 23 or 25 hour day
+// across a DST change can't throw it off by one.
+use crate::calendar;
+use crate::platform;
+
+// The days from the local date at `now` (UNIX seconds) to `date`, negative
+// once it's passed. None if the local time can't be read.
+pub fn until(date: (i32, u8, u8), now: i64) -> Option<i64> {
+    let tm = platform::local_time(now)?;
+    let today = calendar::days_from_civil(tm.tm_year as i64 + 1900, tm.tm_mon as i64 + 1, tm.tm_mday as i64);
+    Some(calendar::days_from_civil(date.0 as i64, date.1 as i64, date.2 as i64) - today)
+}
+
+// Shown under the number: "days until 2025-06-01"
+pub fn status(days: i64, date: (i32, u8, u8)) -> String {
+    let date = format!("{:04}-{:02}-{:02}", date.0, date.1, date.2);
+    match days {
+        0 => format!("{} is today", date),
+        1 => format!("day until {}", date),
+        _ => format!("days until {}", date),
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn until_counts_calendar_days() {
+        let now = 1_750_000_000; // Mid June 2025 wherever the tests run
+        let tm = platform::local_time(now).unwrap();
+        let today = (tm.tm_year + 1900, tm.tm_mon as u8 + 1, tm.tm_mday as u8);
+        assert_eq!(until(today, now), Some(0));
+        // Test: Any time of day counts as the same day
+        assert_eq!(until(today, now - (tm.tm_hour * 3600 + tm.tm_min * 60 + tm.tm_sec) as i64), Some(0));
+        assert_eq!(until((2026, 6, 1), now).map(|days| days > 300), Some(true));
+        assert_eq!(until((2025, 1, 1), now).map(|days| days < 0), Some(true));
+    }
+
+    #[test]
+    fn status_says_day_or_days() {
+        assert_eq!(status(12, (2025, 6, 1)), "days until 2025-06-01");
+        assert_eq!(status(1, (2025, 6, 1)), "day until 2025-06-01");
+        assert_eq!(status(0, (2025, 6, 1)), "2025-06-01 is today");
+    }
+}
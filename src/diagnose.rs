@@ -0,0 +1,216 @@
+// src/diagnose.rs
+// `timerterm diagnose`: watch the monotonic clock timers count on next to
+// the wall clock for a few seconds, and say what's off about this host.
+// VMs are the usual suspects: the wall clock jumps when the host steps it,
+// sleeps overrun when the vCPU isn't scheduled, and some clock sources only
+// tick every few milliseconds.
+use std::time::{Duration, Instant, SystemTime};
+
+// How long each sample sleeps for
+pub const INTERVAL: Duration = Duration::from_millis(10);
+
+// Past these it's worth telling the user
+const COARSE: Duration = Duration::from_millis(1); // Clock resolution
+const JUMP: Duration = Duration::from_millis(50);  // Wall clock moving apart from monotonic in one sample
+const LATE: Duration = Duration::from_millis(20);  // A sleep overrunning
+const DRIFT_PPM: f64 = 1000.0;                     // NTP slews at 500 at most
+
+// Both clocks at one point, from the first sample
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    pub mono: Duration,
+    pub wall: i64, // Microseconds, negative if the wall clock went back past the start
+}
+
+// The wall clock moving apart from the monotonic one between two samples
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Jump {
+    pub at: Duration, // Monotonic time of the sample after it
+    pub by: i64,      // Microseconds, negative when it went back
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub source: Option<String>, // The kernel's clock source, Linux only
+    pub resolution: Duration,   // Smallest step seen on the monotonic clock
+    pub typical_late: Duration, // Median sleep overrun
+    pub worst_late: Duration,
+    pub drift_ppm: f64,         // Wall against monotonic, jumps left out
+    pub jumps: Vec<Jump>,
+    pub span: Duration,         // Monotonic time covered
+}
+
+// Sleep INTERVAL at a time for `span`, reading both clocks after each
+pub fn sample(span: Duration) -> Vec<Sample> {
+    let (mono_start, wall_start) = (Instant::now(), SystemTime::now());
+    let read = || {
+        let wall = match SystemTime::now().duration_since(wall_start) {
+            Ok(ahead) => ahead.as_micros() as i64,
+            Err(e) => -(e.duration().as_micros() as i64),
+        };
+        Sample { mono: mono_start.elapsed(), wall }
+    };
+    let mut samples = vec![read()];
+    while samples.last().is_some_and(|s| s.mono < span) {
+        std::thread::sleep(INTERVAL);
+        samples.push(read());
+    }
+    samples
+}
+
+// The smallest step the monotonic clock takes, spinning until it moves a
+// few times over. A coarse clock makes this take a few of its ticks.
+pub fn resolution() -> Duration {
+    (0..20).map(|_| {
+        let start = Instant::now();
+        loop {
+            let step = start.elapsed();
+            if !step.is_zero() { break step; }
+        }
+    }).min().unwrap_or_default()
+}
+
+pub fn analyze(samples: &[Sample], resolution: Duration, source: Option<String>) -> Report {
+    let mut late: Vec<Duration> = Vec::new();
+    let mut jumps = Vec::new();
+    for pair in samples.windows(2) {
+        let mono = pair[1].mono - pair[0].mono;
+        late.push(mono.saturating_sub(INTERVAL));
+        let by = (pair[1].wall - pair[0].wall) - mono.as_micros() as i64;
+        if by.unsigned_abs() > JUMP.as_micros() as u64 { jumps.push(Jump { at: pair[1].mono, by }); }
+    }
+    late.sort();
+    let (first, last) = (samples.first(), samples.last());
+    let span = match (first, last) {
+        (Some(first), Some(last)) => last.mono - first.mono,
+        _ => Duration::ZERO,
+    };
+    let drift_ppm = match (first, last) {
+        (Some(first), Some(last)) if !span.is_zero() => {
+            let jumped: i64 = jumps.iter().map(|j| j.by).sum();
+            let apart = (last.wall - first.wall) - span.as_micros() as i64 - jumped;
+            apart as f64 / span.as_micros() as f64 * 1e6
+        }
+        _ => 0.0,
+    };
+    Report {
+        source,
+        resolution,
+        typical_late: late.get(late.len() / 2).copied().unwrap_or_default(),
+        worst_late: late.last().copied().unwrap_or_default(),
+        drift_ppm,
+        jumps,
+        span,
+    }
+}
+
+impl Report {
+    // What was measured, a line each
+    pub fn lines(&self) -> Vec<String> {
+        let jumps = match self.jumps.len() {
+            0 => "none".to_string(),
+            n => n.to_string(),
+        };
+        vec![
+            format!("Clock source      {}", self.source.as_deref().unwrap_or("unknown")),
+            format!("Resolution        {}", short(self.resolution)),
+            format!("Sleep overrun     {} typical, {} worst", short(self.typical_late), short(self.worst_late)),
+            format!("Wall clock drift  {:+.0} ppm", self.drift_ppm),
+            format!("Wall clock jumps  {}", jumps),
+        ]
+    }
+
+    // What's off and what it does to a timer, empty on a healthy host
+    pub fn anomalies(&self) -> Vec<String> {
+        let mut found = Vec::new();
+        if self.source.as_deref() == Some("jiffies") {
+            found.push("The kernel is keeping time with jiffies, its last resort clock source, \
+                which only ticks every few milliseconds.".to_string());
+        }
+        if self.resolution > COARSE {
+            found.push(format!("The monotonic clock only moves every {}, so the display can update that late.",
+                short(self.resolution)));
+        }
+        for jump in &self.jumps {
+            let way = if jump.by < 0 { "back" } else { "forward" };
+            found.push(format!("The wall clock jumped {} {} at {}. Timers aren't affected, \
+                but --until and the log's times are.", way, short(Duration::from_micros(jump.by.unsigned_abs())), short(jump.at)));
+        }
+        if self.worst_late > LATE {
+            found.push(format!("A {} sleep overran by {}, the host may be overloaded or the VM \
+                short of CPU. The display can stall that long.", short(INTERVAL), short(self.worst_late)));
+        }
+        if self.drift_ppm.abs() > DRIFT_PPM {
+            found.push(format!("The wall clock runs {:.1}% {} than the monotonic one, \
+                so timers and the clock on the wall will disagree.",
+                self.drift_ppm.abs() / 1e4, if self.drift_ppm < 0.0 { "slower" } else { "faster" }));
+        }
+        found
+    }
+}
+
+// "850ns", "12µs", "4.0ms", "1.25s"
+fn short(d: Duration) -> String {
+    match d.as_nanos() {
+        n if n < 1_000 => format!("{}ns", n),
+        n if n < 1_000_000 => format!("{}µs", n / 1_000),
+        n if n < 1_000_000_000 => format!("{:.1}ms", n as f64 / 1e6),
+        n => format!("{:.2}s", n as f64 / 1e9),
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Samples INTERVAL apart on both clocks, as a healthy host gives
+    fn steady(count: u64) -> Vec<Sample> {
+        (0..count).map(|i| Sample { mono: INTERVAL * i as u32, wall: (INTERVAL * i as u32).as_micros() as i64 }).collect()
+    }
+
+    #[test]
+    fn analyze_passes_a_healthy_host() {
+        let report = analyze(&steady(301), Duration::from_nanos(40), Some("tsc".into()));
+        assert_eq!(report.span, Duration::from_secs(3));
+        assert_eq!(report.drift_ppm, 0.0);
+        assert_eq!(report.lines(), vec![
+            "Clock source      tsc",
+            "Resolution        40ns",
+            "Sleep overrun     0ns typical, 0ns worst",
+            "Wall clock drift  +0 ppm",
+            "Wall clock jumps  none",
+        ]);
+        assert!(report.anomalies().is_empty());
+    }
+
+    #[test]
+    fn analyze_finds_jumps_and_overruns() {
+        // Test: The host steps the wall clock back 2s, then the VM misses 300ms
+        let mut samples = steady(101);
+        for s in &mut samples[50..] { s.wall -= 2_000_000; }
+        for s in &mut samples[80..] { s.mono += Duration::from_millis(300); s.wall += 300_000; }
+        let report = analyze(&samples, Duration::from_millis(4), Some("jiffies".into()));
+        assert_eq!(report.jumps, vec![Jump { at: Duration::from_millis(500), by: -2_000_000 }]);
+        assert_eq!(report.worst_late, Duration::from_millis(300));
+        assert_eq!(report.typical_late, Duration::ZERO);
+        // Test: The jump isn't counted as drift
+        assert_eq!(report.drift_ppm, 0.0);
+        let anomalies = report.anomalies();
+        assert_eq!(anomalies.len(), 4);
+        assert!(anomalies[1].starts_with("The monotonic clock only moves every 4.0ms"));
+        assert!(anomalies[2].starts_with("The wall clock jumped back 2.00s at 500.0ms."));
+        assert!(anomalies[3].starts_with("A 10.0ms sleep overran by 300.0ms"));
+    }
+
+    #[test]
+    fn analyze_measures_drift() {
+        // Test: A wall clock 1% fast over 1s is 10000 ppm
+        let samples: Vec<Sample> = steady(101).into_iter().map(|s| Sample { wall: s.wall * 101 / 100, ..s }).collect();
+        let report = analyze(&samples, Duration::from_micros(1), None);
+        assert_eq!(report.drift_ppm.round(), 10000.0);
+        assert_eq!(report.lines()[0], "Clock source      unknown");
+        assert_eq!(report.anomalies(), vec!["The wall clock runs 1.0% faster than the monotonic one, \
+            so timers and the clock on the wall will disagree."]);
+    }
+}
@@ -0,0 +1,77 @@
+// src/duel.rs
+// `timerterm duel`: two countdowns of the same length side by side, one per
+// person, each paused with its own key. Both count the same time, so
+// whoever spends less of the duel paused finishes first.
+use std::time::Duration;
+
+use crate::render::format_duration;
+use crate::text;
+
+// How one side's run went, for the summary
+#[derive(Debug, Clone, PartialEq)]
+pub struct Side {
+    pub name: String,
+    pub elapsed: Duration, // Counted towards the countdown
+    pub paused: Duration,  // Spent paused
+    pub pauses: u32,
+    pub finished: bool,
+}
+
+impl Side {
+    // "Ana  25:00 of 25:00, 2 pauses (1:10)", the name padded to `width`
+    pub fn report(&self, duration: Duration, width: usize) -> String {
+        let pauses = match self.pauses {
+            0 => "no pauses".to_string(),
+            1 => format!("1 pause ({})", format_duration(self.paused.as_secs())),
+            n => format!("{} pauses ({})", n, format_duration(self.paused.as_secs())),
+        };
+        format!("{}  {} of {}, {}", text::pad_right(&self.name, width), format_duration(self.elapsed.as_secs()),
+            format_duration(duration.as_secs()), pauses)
+    }
+}
+
+// Who came out ahead and by how much. A finished side beats one that isn't,
+// two finished sides are split by the time they spent paused, and two
+// unfinished ones by how far they got.
+pub fn verdict(sides: &[Side; 2]) -> String {
+    let [a, b] = sides;
+    let (a_leads, gap, verb) = match (a.finished, b.finished) {
+        (true, true) => (a.paused < b.paused, a.paused.abs_diff(b.paused), "won"),
+        (true, false) => return format!("{} won, {} didn't finish", a.name, b.name),
+        (false, true) => return format!("{} won, {} didn't finish", b.name, a.name),
+        (false, false) => (a.elapsed > b.elapsed, a.elapsed.abs_diff(b.elapsed), "was ahead"),
+    };
+    let secs = gap.as_secs();
+    if secs == 0 { return "A dead heat".to_string(); }
+    let winner = if a_leads { a } else { b };
+    format!("{} {} by {}", winner.name, verb, format_duration(secs))
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn side(name: &str, elapsed: u64, paused: u64, finished: bool) -> Side {
+        Side { name: name.into(), elapsed: Duration::from_secs(elapsed), paused: Duration::from_secs(paused), pauses: u32::from(paused > 0), finished }
+    }
+
+    #[test]
+    fn report_counts_pauses() {
+        let duration = Duration::from_secs(1500);
+        assert_eq!(side("Ana", 1500, 70, true).report(duration, 5), "Ana    25:00 of 25:00, 1 pause (1:10)");
+        assert_eq!(side("Ben", 600, 0, false).report(duration, 3), "Ben  10:00 of 25:00, no pauses");
+    }
+
+    #[test]
+    fn verdict_picks_the_leader() {
+        // Test: Both done, the one paused less won
+        assert_eq!(verdict(&[side("Ana", 1500, 70, true), side("Ben", 1500, 10, true)]), "Ben won by 1:00");
+        assert_eq!(verdict(&[side("Ana", 1500, 5, true), side("Ben", 1500, 65, true)]), "Ana won by 1:00");
+        // Test: Finishing beats not finishing
+        assert_eq!(verdict(&[side("Ana", 1400, 0, false), side("Ben", 1500, 90, true)]), "Ben won, Ana didn't finish");
+        // Test: Neither done, the one further along is ahead
+        assert_eq!(verdict(&[side("Ana", 700, 0, false), side("Ben", 600, 0, false)]), "Ana was ahead by 1:40");
+        assert_eq!(verdict(&[side("Ana", 600, 0, false), side("Ben", 600, 0, false)]), "A dead heat");
+    }
+}
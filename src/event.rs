@@ -0,0 +1,119 @@
+// src/event.rs
+use std::fmt;
+use std::time::Duration;
+
+use crate::render::format_duration;
+use crate::timer::{Direction, Timer};
+
+// Things that happen over a timer's life, in the order they happen
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimerEvent {
+    // Elapsed is a head start, usually zero. Tag is the tmux "session:window" it ran in.
+    Started { duration: Duration, elapsed: Duration, estimate: Option<Duration>, label: Option<String>, tag: Option<String>, dir: Option<String> },
+    Paused { elapsed: Duration, reason: Option<String> }, // Why, if the pause menu was answered
+    Resumed { elapsed: Duration },
+    PhaseStarted { index: usize, label: String },
+    Adjusted { elapsed: Duration, duration: Duration }, // Time added or taken off, the new total
+    Interrupted { elapsed: Duration, note: String }, // Noted with i during a pomodoro work block
+    Reflected { note: String }, // What got done, answered after a work block with pomodoro.reflection
+    Finished { elapsed: Duration },
+    Cancelled { elapsed: Duration },
+}
+
+// What a timer run amounted to, reported on exit
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub label: Option<String>,
+    pub planned: Duration,
+    pub elapsed: Duration,
+    pub completed: bool,
+    pub overtime: Duration, // How long past zero it ran with --overtime
+}
+
+impl Summary {
+    pub fn from_timer(timer: &Timer, label: Option<&str>) -> Self {
+        Summary {
+            label: label.map(str::to_string),
+            planned: timer.duration(),
+            elapsed: timer.elapsed(),
+            completed: timer.is_finished(),
+            overtime: Duration::ZERO,
+        }
+    }
+}
+
+// A running timer at one moment, for status bars and other pollers
+#[derive(Debug, Clone, PartialEq)]
+pub struct Status {
+    pub label: Option<String>,
+    pub direction: Direction,
+    pub duration: Duration,           // Zero for a stopwatch
+    pub elapsed: Duration,
+    pub remaining: Option<Duration>,  // None for a stopwatch
+    pub paused: bool,
+    pub finished: bool,
+}
+
+impl Status {
+    pub fn from_timer(timer: &Timer, label: Option<&str>) -> Self {
+        let down = timer.direction() == Direction::Down;
+        Status {
+            label: label.map(str::to_string),
+            direction: timer.direction(),
+            duration: timer.duration(),
+            elapsed: timer.elapsed(),
+            remaining: down.then(|| timer.remaining()),
+            paused: timer.is_paused(),
+            finished: timer.is_finished(),
+        }
+    }
+}
+
+// "Tea: 3:12 of 10:00", or just "3:12" when nothing was planned (stopwatch).
+// Any overtime goes on the end: "Standup: 15:00 of 15:00, finished, 4:10 over"
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(label) = &self.label { write!(f, "{}: ", label)?; }
+        write!(f, "{}", format_duration(self.elapsed.as_secs()))?;
+        if !self.planned.is_zero() { write!(f, " of {}", format_duration(self.planned.as_secs()))?; }
+        if self.completed { write!(f, ", finished")?; }
+        if !self.overtime.is_zero() { write!(f, ", {} over", format_duration(self.overtime.as_secs()))?; }
+        Ok(())
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_from_unstarted_timer() {
+        // Test: Nothing elapsed and not completed before start
+        let timer = Timer::new(Duration::from_secs(30));
+        let summary = Summary::from_timer(&timer, Some("Tea"));
+        assert_eq!(summary.label.as_deref(), Some("Tea"));
+        assert_eq!(summary.planned, Duration::from_secs(30));
+        assert_eq!(summary.elapsed, Duration::ZERO);
+        assert!(!summary.completed);
+    }
+
+    #[test]
+    fn status_of_a_stopwatch_has_nothing_remaining() {
+        // Test: Only a countdown has time left
+        assert_eq!(Status::from_timer(&Timer::new(Duration::from_secs(30)), None).remaining, Some(Duration::from_secs(30)));
+        let status = Status::from_timer(&Timer::stopwatch(), Some("Run"));
+        assert_eq!((status.direction, status.remaining, status.paused), (Direction::Up, None, false));
+    }
+
+    #[test]
+    fn summary_displays_progress() {
+        // Test: Label, elapsed and planned time, and whether it finished
+        let summary = Summary { label: Some("Tea".into()), planned: Duration::from_secs(600), elapsed: Duration::from_secs(192), completed: false, overtime: Duration::ZERO };
+        assert_eq!(summary.to_string(), "Tea: 3:12 of 10:00");
+        let summary = Summary { label: None, planned: Duration::ZERO, elapsed: Duration::from_secs(75), completed: false, overtime: Duration::ZERO };
+        assert_eq!(summary.to_string(), "1:15");
+        let summary = Summary { label: Some("Standup".into()), planned: Duration::from_secs(900), elapsed: Duration::from_secs(900), completed: true, overtime: Duration::from_secs(250) };
+        assert_eq!(summary.to_string(), "Standup: 15:00 of 15:00, finished, 4:10 over");
+    }
+}
@@ -0,0 +1,99 @@
+// src/git.rs
+// `timerterm git-timebox`: a countdown labeled with the repo and branch it
+// runs in, so the history log says what the time went on, that leaves a
+// "Timeboxed: 30m actual 27m" trailer in the commit message being prepared.
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repo {
+    pub name: String,           // The top level directory's name
+    pub branch: Option<String>, // None on a detached HEAD
+    pub git_dir: PathBuf,       // Where COMMIT_EDITMSG lives
+}
+
+impl Repo {
+    // The repo the current directory is in
+    pub fn current() -> Result<Repo, String> {
+        let top = PathBuf::from(git(&["rev-parse", "--show-toplevel"])?);
+        let git_dir = PathBuf::from(git(&["rev-parse", "--absolute-git-dir"])?);
+        let branch = Some(git(&["branch", "--show-current"])?).filter(|b| !b.is_empty());
+        let name = top.file_name().map_or_else(|| top.display().to_string(), |n| n.to_string_lossy().into_owned());
+        Ok(Repo { name, branch, git_dir })
+    }
+
+    // "timerterm/main", the label the run is logged under
+    pub fn label(&self) -> String {
+        match &self.branch {
+            Some(branch) => format!("{}/{}", self.name, branch),
+            None => self.name.clone(),
+        }
+    }
+
+    pub fn commit_message(&self) -> PathBuf {
+        self.git_dir.join("COMMIT_EDITMSG")
+    }
+}
+
+// The repo's top level, else the current directory: the project a run is
+// logged under for `timerterm stats --by-project`
+pub fn project_dir() -> Option<String> {
+    git(&["rev-parse", "--show-toplevel"]).ok()
+        .or_else(|| Some(std::env::current_dir().ok()?.to_string_lossy().into_owned()))
+}
+
+// "30m actual 27m"
+pub fn trailer(planned: Duration, actual: Duration) -> String {
+    format!("{} actual {}", short(planned), short(actual))
+}
+
+// Add "Timeboxed: VALUE" to the message in `file` the way git would, after
+// any trailers it has and ahead of its comments
+pub fn add_trailer(file: &Path, value: &str) -> Result<(), String> {
+    if !file.is_file() { return Err(format!("{}: no commit message there", file.display())); }
+    let file = file.to_string_lossy();
+    git(&["interpret-trailers", "--in-place", "--trailer", &format!("Timeboxed: {}", value), &file]).map(drop)
+}
+
+// "45s" under a minute, else to the nearest minute: "27m", "1h30m", "2h"
+fn short(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs < 60 { return format!("{}s", secs); }
+    let mins = (secs + 30) / 60;
+    match (mins / 60, mins % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h{}m", h, m),
+    }
+}
+
+// stdout with the newline trimmed, or what git said went wrong
+fn git(args: &[&str]) -> Result<String, String> {
+    let out = Command::new("git").args(args).output().map_err(|e| format!("Couldn't run git: {}", e))?;
+    if !out.status.success() { return Err(String::from_utf8_lossy(&out.stderr).trim().to_string()); }
+    Ok(String::from_utf8_lossy(&out.stdout).trim_end().to_string())
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trailer_rounds_to_minutes() {
+        assert_eq!(trailer(Duration::from_secs(1800), Duration::from_secs(27 * 60 + 10)), "30m actual 27m");
+        assert_eq!(trailer(Duration::from_secs(5400), Duration::from_secs(7200)), "1h30m actual 2h");
+        // Test: Under a minute keeps its seconds
+        assert_eq!(trailer(Duration::from_secs(45), Duration::from_secs(59)), "45s actual 59s");
+    }
+
+    #[test]
+    fn label_names_repo_and_branch() {
+        let repo = Repo { name: "timerterm".into(), branch: Some("main".into()), git_dir: "/src/timerterm/.git".into() };
+        assert_eq!(repo.label(), "timerterm/main");
+        assert_eq!(repo.commit_message(), PathBuf::from("/src/timerterm/.git/COMMIT_EDITMSG"));
+        // Test: A detached HEAD is just the repo
+        assert_eq!(Repo { branch: None, ..repo }.label(), "timerterm");
+    }
+}
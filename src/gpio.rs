@@ -0,0 +1,93 @@
+// src/gpio.rs
+// Physical alarm output through the sysfs GPIO interface (/sys/class/gpio).
+// Pin numbers are as sysfs sees them: BCM numbers on older Pi kernels,
+// offset by the chip base (e.g. 512 + BCM) on newer ones.
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+pub const SYSFS_ROOT: &str = "/sys/class/gpio";
+
+#[derive(Debug, Clone)]
+pub struct Pin {
+    value: PathBuf,
+}
+
+impl Pin {
+    pub fn open(pin: u32) -> io::Result<Pin> {
+        Pin::open_in(Path::new(SYSFS_ROOT), pin)
+    }
+
+    // Export the pin if needed and make it a low output
+    fn open_in(root: &Path, pin: u32) -> io::Result<Pin> {
+        let dir = root.join(format!("gpio{}", pin));
+        if !dir.exists() { std::fs::write(root.join("export"), pin.to_string())?; }
+        // udev may take a moment to hand over a freshly exported pin
+        let mut tries = 0;
+        loop {
+            match std::fs::write(dir.join("direction"), "low") {
+                Ok(()) => break,
+                Err(_) if tries < 10 => thread::sleep(Duration::from_millis(20)),
+                Err(e) => return Err(e),
+            }
+            tries += 1;
+        }
+        Ok(Pin { value: dir.join("value") })
+    }
+
+    pub fn set(&self, high: bool) -> io::Result<()> {
+        std::fs::write(&self.value, if high { "1" } else { "0" })
+    }
+
+    // Drive the pin high for `length` on a background thread
+    pub fn pulse(&self, length: Duration) {
+        let pin = self.clone();
+        thread::spawn(move || {
+            if pin.set(true).is_ok() { thread::sleep(length); }
+            let _ = pin.set(false);
+        });
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Stand-in for /sys/class/gpio with gpio<pin> already exported
+    fn fake_sysfs(name: &str, pin: u32) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("timeterm-gpio-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(root.join(format!("gpio{}", pin))).unwrap();
+        root
+    }
+
+    #[test]
+    fn open_sets_low_output() {
+        // Test: Opening writes "low" so the pin starts as an inactive output
+        let root = fake_sysfs("open", 17);
+        let pin = Pin::open_in(&root, 17).unwrap();
+        assert_eq!(std::fs::read_to_string(root.join("gpio17/direction")).unwrap(), "low");
+        pin.set(true).unwrap();
+        assert_eq!(std::fs::read_to_string(root.join("gpio17/value")).unwrap(), "1");
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn pulse_returns_to_low() {
+        let root = fake_sysfs("pulse", 4);
+        let pin = Pin::open_in(&root, 4).unwrap();
+        pin.pulse(Duration::from_millis(10));
+        thread::sleep(Duration::from_millis(200));
+        assert_eq!(std::fs::read_to_string(root.join("gpio4/value")).unwrap(), "0");
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn unexported_pin_is_an_error() {
+        // Test: If export doesn't produce gpio<pin>, open gives up instead of hanging
+        let root = fake_sysfs("missing", 1);
+        assert!(Pin::open_in(&root, 5).is_err());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
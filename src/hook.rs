@@ -0,0 +1,208 @@
+// src/hook.rs
+// `--exec` commands run when a timer finishes, through `sh -c` (`cmd /C` on
+// Windows) so pipes and quoting work like they would at the prompt.
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader, Read};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// How long a stopped hook gets to clean up after SIGTERM before SIGKILL
+pub const GRACE: Duration = Duration::from_secs(2);
+// How many lines of a watched command's output are kept
+pub const KEPT_LINES: usize = 200;
+
+// What `run` does with segment hooks still going once the schedule ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrphanPolicy {
+    #[default]
+    Wait,   // Wait for them, unless the run was stopped by a signal
+    Kill,   // Stop them and whatever they started
+    Detach, // Leave them running
+}
+
+impl OrphanPolicy {
+    pub const NAMES: [&'static str; 3] = ["wait", "kill", "detach"];
+
+    pub fn parse(name: &str) -> Option<OrphanPolicy> {
+        match name {
+            "wait" => Some(OrphanPolicy::Wait),
+            "kill" => Some(OrphanPolicy::Kill),
+            "detach" => Some(OrphanPolicy::Detach),
+            _ => None,
+        }
+    }
+}
+
+// Run `cmd` with the timer described in TIMERTERM_NAME and TIMERTERM_DURATION
+// (whole seconds), waiting for it to finish
+pub fn run(cmd: &str, name: &str, duration: Duration) -> io::Result<ExitStatus> {
+    command(cmd, name, duration).status()
+}
+
+// Like run without waiting, for a hook that fires while the timer is still
+// on screen. It gets no terminal, output would land on the frame, and a
+// process group of its own, so `stop` can take down whatever it starts.
+pub fn spawn(cmd: &str, name: &str, duration: Duration) -> io::Result<Child> {
+    let mut command = command(cmd, name, duration);
+    crate::platform::own_group(&mut command);
+    command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).spawn()
+}
+
+// Like spawn, but with stdout and stderr read into `Output` as they come, for
+// --watch-exec to show under the timer
+pub fn watch(cmd: &str, name: &str, duration: Duration) -> io::Result<(Child, Output)> {
+    let mut command = command(cmd, name, duration);
+    crate::platform::own_group(&mut command);
+    let mut child = command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    let output = Output::default();
+    if let Some(out) = child.stdout.take() { output.read(out); }
+    if let Some(err) = child.stderr.take() { output.read(err); }
+    Ok((child, output))
+}
+
+// The last KEPT_LINES of a watched command's output, stdout and stderr
+// interleaved as they arrived
+#[derive(Debug, Clone, Default)]
+pub struct Output(Arc<Mutex<Lines>>);
+
+#[derive(Debug, Default)]
+struct Lines {
+    kept: VecDeque<String>,
+    total: usize, // Every line so far, kept or not
+    open: usize,  // Pipes not yet at EOF
+}
+
+impl Output {
+    // A line redrawn with \r, like a progress bar, is its last drawing
+    fn read(&self, pipe: impl Read + Send + 'static) {
+        let lines = Arc::clone(&self.0);
+        lines.lock().unwrap().open += 1;
+        thread::spawn(move || {
+            let mut pipe = BufReader::new(pipe);
+            let mut buf = Vec::new();
+            while matches!(pipe.read_until(b'\n', &mut buf), Ok(n) if n > 0) {
+                let text = String::from_utf8_lossy(&buf);
+                let text = text.trim_end_matches(['\r', '\n']);
+                let line = text.rsplit('\r').next().unwrap_or_default().to_string();
+                let mut lines = lines.lock().unwrap();
+                if lines.kept.len() == KEPT_LINES { lines.kept.pop_front(); }
+                lines.kept.push_back(line);
+                lines.total += 1;
+                buf.clear();
+            }
+            lines.lock().unwrap().open -= 1;
+        });
+    }
+
+    // The last `count` lines
+    pub fn last(&self, count: usize) -> Vec<String> {
+        let lines = self.0.lock().unwrap();
+        lines.kept.iter().skip(lines.kept.len().saturating_sub(count)).cloned().collect()
+    }
+
+    // The lines after the first `seen`, as far as they're still kept, and
+    // the count to pass next time
+    pub fn since(&self, seen: usize) -> (Vec<String>, usize) {
+        let lines = self.0.lock().unwrap();
+        let new = (lines.total - seen).min(lines.kept.len());
+        (lines.kept.iter().skip(lines.kept.len() - new).cloned().collect(), lines.total)
+    }
+
+    // Wait up to `timeout` for both pipes to close, so what a command printed
+    // just before exiting is in. Something it left running may hold them open.
+    pub fn settle(&self, timeout: Duration) {
+        let deadline = Instant::now() + timeout;
+        while self.0.lock().unwrap().open > 0 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+// Stop a spawned hook and its process group: SIGTERM, then SIGKILL once GRACE
+// is up. Its status if it had already finished, None if it had to be stopped.
+pub fn stop(child: &mut Child) -> io::Result<Option<ExitStatus>> {
+    if let Some(status) = child.try_wait()? { return Ok(Some(status)); }
+    crate::platform::stop_group(child, false);
+    let deadline = Instant::now() + GRACE;
+    while child.try_wait()?.is_none() && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(20));
+    }
+    // Also whatever it left behind ignoring SIGTERM
+    crate::platform::stop_group(child, true);
+    child.wait()?;
+    Ok(None)
+}
+
+fn command(cmd: &str, name: &str, duration: Duration) -> Command {
+    let [shell, flag] = crate::platform::SHELL;
+    let mut command = Command::new(shell);
+    command.arg(flag)
+        .arg(cmd)
+        .env("TIMERTERM_NAME", name)
+        .env("TIMERTERM_DURATION", duration.as_secs().to_string());
+    command
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(unix)]
+    fn run_passes_timer_env() {
+        // Test: The child sees the name and duration in seconds
+        let check = r#"[ "$TIMERTERM_NAME" = tea ] && [ "$TIMERTERM_DURATION" = 180 ]"#;
+        assert!(run(check, "tea", Duration::from_secs(180)).unwrap().success());
+    }
+
+    #[test]
+    fn run_reports_exit_status() {
+        assert_eq!(run("exit 3", "timer", Duration::ZERO).unwrap().code(), Some(3));
+        // Test: A spawned hook's status is there once it's waited for
+        assert_eq!(spawn("exit 4", "timer", Duration::ZERO).unwrap().wait().unwrap().code(), Some(4));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn stop_takes_down_the_whole_group() {
+        // Test: A hook ignoring SIGTERM, and the child it left behind, are gone after GRACE
+        let survived = std::env::temp_dir().join(format!("timerterm-hook-survived-{}", std::process::id()));
+        let cmd = format!("trap '' TERM; (sleep 3; touch {}) & sleep 30", survived.display());
+        let started = Instant::now();
+        let mut child = spawn(&cmd, "timer", Duration::ZERO).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(stop(&mut child).unwrap(), None);
+        assert!(started.elapsed() < GRACE + Duration::from_secs(1));
+        thread::sleep(Duration::from_secs(3).saturating_sub(started.elapsed()) + Duration::from_millis(500));
+        assert!(!survived.exists(), "the left behind child should be killed too");
+        // Test: One that already finished just reports how
+        let mut done = spawn("exit 5", "timer", Duration::ZERO).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(stop(&mut done).unwrap().and_then(|s| s.code()), Some(5));
+        assert_eq!(OrphanPolicy::NAMES.map(OrphanPolicy::parse), [Some(OrphanPolicy::Wait), Some(OrphanPolicy::Kill), Some(OrphanPolicy::Detach)]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn watch_keeps_the_last_lines() {
+        let (mut child, output) = watch("echo one; echo two >&2; printf '10%%\\r50%%\\r100%%\\n'", "timer", Duration::ZERO).unwrap();
+        assert!(child.wait().unwrap().success());
+        output.settle(Duration::from_secs(5));
+        let (mut lines, seen) = output.since(0);
+        // Test: A progress bar shows as where it ended, stderr comes in whenever it does
+        lines.sort();
+        assert_eq!(lines, ["100%", "one", "two"]);
+        assert_eq!(output.last(5).len(), 3);
+        assert_eq!(output.since(seen), (vec![], 3));
+        // Test: Past KEPT_LINES the oldest go
+        let (mut child, output) = watch(&format!("seq {}", KEPT_LINES + 5), "timer", Duration::ZERO).unwrap();
+        child.wait().unwrap();
+        output.settle(Duration::from_secs(5));
+        let (lines, seen) = output.since(0);
+        assert_eq!((lines.len(), seen), (KEPT_LINES, KEPT_LINES + 5));
+        assert_eq!(lines[0], "6");
+    }
+}
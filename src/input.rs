@@ -0,0 +1,280 @@
+// src/input.rs
+use std::io::{BufRead, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::{cli, clock, signal};
+
+// What a key press asks the main loop to do, or Wake when a signal needs a look
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    TogglePause,
+    Pause,  // Unlike TogglePause, no-ops if already paused
+    Resume, // Or not paused
+    Restart,
+    Quit,
+    Reason(&'static str), // An answer to the pause menu
+    NextQuote,
+    AddTime,    // One config adjust_step more
+    RemoveTime, // And one less
+    Adjust(i64), // Secs more (or less when negative), from a control command
+    ToggleSide(usize), // Pause or resume one side of a duel, 0 is the left
+    Interrupt, // Start typing an interruption note
+    Kill,      // Stop a --watch-exec command
+    Mute,      // Or unmute, m, which is "meeting" while the pause menu is up
+    Volume(i8), // Steps of sound volume, [ down and ] up
+    Typed(u8), // A byte of text, while typing turns keys into text
+    Wake,
+    Echoed, // A cooked terminal echoed typing over the frame
+    Advance(Duration), // Move the fake clock on, test harness only
+}
+
+// Where a read left off in an escape sequence
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Escape {
+    #[default]
+    None,
+    Started, // After ESC
+    Sequence, // After ESC [ or ESC O, until the final byte
+}
+
+// Set while a note is being typed, the reader sends text instead of keys
+static TYPING: AtomicBool = AtomicBool::new(false);
+
+pub fn set_typing(on: bool) {
+    TYPING.store(on, Ordering::SeqCst);
+}
+
+// The key presses in `bytes`, without the escape sequences arrow and
+// function keys send (ESC [ C would otherwise read as 'c'), or Alt+key.
+// While `typing` every byte is Action::Typed, and ESC pressed alone too.
+pub fn keys(bytes: &[u8], escape: &mut Escape, typing: bool) -> Vec<Action> {
+    let mut actions = Vec::new();
+    for &byte in bytes {
+        *escape = match (*escape, byte) {
+            (_, 0x1b) => Escape::Started,
+            (Escape::Started, b'[' | b'O') => Escape::Sequence,
+            (Escape::Started, _) | (Escape::Sequence, 0x40..=0x7e) => Escape::None,
+            (Escape::Sequence, _) => Escape::Sequence,
+            (Escape::None, byte) if typing => {
+                actions.push(Action::Typed(byte));
+                Escape::None
+            }
+            (Escape::None, byte) => {
+                actions.extend(action_for(byte));
+                Escape::None
+            }
+        };
+    }
+    // A sequence comes in one read, so an ESC left at the end was pressed alone
+    if *escape == Escape::Started {
+        *escape = Escape::None;
+        if typing { actions.push(Action::Typed(0x1b)); }
+    }
+    actions
+}
+
+// Pause menu keys and what they mean
+pub const PAUSE_REASONS: [(u8, &str); 4] = [(b'c', "coffee"), (b'p', "phone"), (b'm', "meeting"), (b'o', "other")];
+
+pub fn action_for(byte: u8) -> Option<Action> {
+    match byte {
+        b' ' => Some(Action::TogglePause),
+        b'r' | b'R' => Some(Action::Restart),
+        b'q' | b'Q' => Some(Action::Quit),
+        b'n' | b'N' => Some(Action::NextQuote),
+        b'+' | b'=' => Some(Action::AddTime), // '=' is '+' without shift
+        b'-' | b'_' => Some(Action::RemoveTime),
+        b'a' | b'A' => Some(Action::ToggleSide(0)),
+        b'l' | b'L' => Some(Action::ToggleSide(1)),
+        b'i' | b'I' => Some(Action::Interrupt),
+        b'k' | b'K' => Some(Action::Kill),
+        b'm' | b'M' => Some(Action::Mute),
+        b'[' => Some(Action::Volume(-1)),
+        b']' => Some(Action::Volume(1)),
+        _ => PAUSE_REASONS.iter().find(|(key, _)| *key == byte.to_ascii_lowercase()).map(|&(_, reason)| Action::Reason(reason)),
+    }
+}
+
+// One test harness command: "advance DURATION" moves the fake clock on,
+// "keys TEXT" presses each key of TEXT in turn, "type TEXT" types TEXT into
+// a note and presses Enter
+pub fn harness_actions(line: &str) -> Result<Vec<Action>, String> {
+    match line.split_once(' ').unwrap_or((line, "")) {
+        ("advance", by) => Ok(vec![Action::Advance(Duration::from_secs(cli::parse_duration(by.trim())? as u64))]),
+        ("keys", text) => Ok(text.bytes().filter_map(action_for).collect()),
+        ("type", text) => Ok(text.bytes().chain([b'\r']).map(Action::Typed).collect()),
+        _ => Err(format!("unknown harness command '{}'", line)),
+    }
+}
+
+// Read stdin on a background thread and forward recognized key presses.
+// The thread ends on EOF (e.g. stdin is /dev/null) or when the receiver is dropped.
+// Caught signals send Action::Wake down the same channel. On a fake clock
+// stdin is harness commands instead. The sender is for any other sources.
+// A `cooked` terminal gets an Action::Echoed after anything typed.
+pub fn spawn_reader(cooked: bool) -> (Sender<Action>, Receiver<Action>) {
+    let (tx, rx) = mpsc::channel();
+    let waker = tx.clone();
+    signal::on_wake(move || { let _ = waker.send(Action::Wake); });
+    if clock::is_fake() {
+        let harness = tx.clone();
+        thread::spawn(move || read_harness(harness));
+        return (tx, rx);
+    }
+    let keys = tx.clone();
+    thread::spawn(move || {
+        let mut stdin = std::io::stdin().lock();
+        let mut buf = [0u8; 64];
+        let mut escape = Escape::None;
+        loop {
+            let n = match stdin.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            let echoed = cooked.then_some(Action::Echoed);
+            let typing = TYPING.load(Ordering::SeqCst);
+            for action in self::keys(&buf[..n], &mut escape, typing).into_iter().chain(echoed) {
+                if keys.send(action).is_err() { return; }
+            }
+        }
+    });
+    (tx, rx)
+}
+
+// What a byte typed into a LineEdit did
+#[derive(Debug, Clone, PartialEq)]
+pub enum Edit {
+    Editing,
+    Done(String), // Enter, with what was typed
+    Cancelled,    // Esc
+}
+
+// A one-line text field fed a byte at a time. Backspace takes off the last
+// character, Ctrl+U the lot, other control keys do nothing.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LineEdit {
+    pub text: String,
+    partial: Vec<u8>, // The start of a character more than a byte long
+}
+
+impl LineEdit {
+    pub fn feed(&mut self, byte: u8) -> Edit {
+        match byte {
+            b'\r' | b'\n' => return Edit::Done(self.text.trim().to_string()),
+            0x1b => return Edit::Cancelled,
+            0x7f | 0x08 => { self.text.pop(); }
+            0x15 => self.text.clear(),
+            0x00..=0x1f => {}
+            byte => {
+                self.partial.push(byte);
+                match std::str::from_utf8(&self.partial) {
+                    Ok(c) => {
+                        self.text.push_str(c);
+                        self.partial.clear();
+                    }
+                    // Not valid however it goes on, so it's dropped
+                    Err(e) if e.error_len().is_some() => self.partial.clear(),
+                    Err(_) => {}
+                }
+            }
+        }
+        Edit::Editing
+    }
+}
+
+// Harness commands one per line. Running out of them quits, a test that
+// forgot to finish the timer shouldn't hang.
+fn read_harness(tx: Sender<Action>) {
+    for line in std::io::stdin().lock().lines() {
+        let Ok(line) = line else { break };
+        match harness_actions(&line) {
+            Ok(actions) => {
+                if actions.into_iter().any(|action| tx.send(action).is_err()) { return; }
+            }
+            Err(e) => eprintln!("timerterm: {}", e),
+        }
+    }
+    let _ = tx.send(Action::Quit);
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_for_maps_keys() {
+        // Test: Space toggles, r restarts, q quits (either case)
+        assert_eq!(action_for(b' '), Some(Action::TogglePause));
+        assert_eq!(action_for(b'r'), Some(Action::Restart));
+        assert_eq!(action_for(b'Q'), Some(Action::Quit));
+        assert_eq!(action_for(b'n'), Some(Action::NextQuote));
+        // Test: Menu keys name their pause reason
+        assert_eq!(action_for(b'C'), Some(Action::Reason("coffee")));
+        assert_eq!(action_for(b'o'), Some(Action::Reason("other")));
+        // Test: a and l are the two sides of a duel
+        assert_eq!(action_for(b'a'), Some(Action::ToggleSide(0)));
+        assert_eq!(action_for(b'L'), Some(Action::ToggleSide(1)));
+        // Test: = and - add and take off time
+        assert_eq!(action_for(b'='), Some(Action::AddTime));
+        assert_eq!(action_for(b'-'), Some(Action::RemoveTime));
+        // Test: i starts an interruption note
+        assert_eq!(action_for(b'i'), Some(Action::Interrupt));
+        assert_eq!(action_for(b'K'), Some(Action::Kill));
+        // Test: m mutes, [ and ] step the volume
+        assert_eq!(action_for(b'm'), Some(Action::Mute));
+        assert_eq!(action_for(b'['), Some(Action::Volume(-1)));
+        assert_eq!(action_for(b']'), Some(Action::Volume(1)));
+        // Test: Other keys are ignored
+        assert_eq!(action_for(b'x'), None);
+        assert_eq!(action_for(b'\n'), None);
+    }
+
+    #[test]
+    fn keys_skip_escape_sequences() {
+        // Test: Arrows, F5 and Alt+q aren't read as their letters
+        let mut escape = Escape::None;
+        assert_eq!(keys(b"\x1b[C\x1b[15~\x1bOAr\x1bq ", &mut escape, false), vec![Action::Restart, Action::TogglePause]);
+        // Test: A sequence split across reads is still skipped
+        assert_eq!(keys(b"q\x1b[1;5", &mut escape, false), vec![Action::Quit]);
+        assert_eq!((escape, keys(b"Dn", &mut escape, false)), (Escape::Sequence, vec![Action::NextQuote]));
+        // Test: ESC on its own doesn't swallow the next key
+        assert!(keys(b"\x1b", &mut escape, false).is_empty());
+        assert_eq!(keys(b"q", &mut escape, false), vec![Action::Quit]);
+    }
+
+    #[test]
+    fn typing_turns_keys_into_text() {
+        // Test: Keys are bytes of text, arrows are still skipped, ESC alone comes through
+        let mut escape = Escape::None;
+        assert_eq!(keys(b"q \x1b[D", &mut escape, true), vec![Action::Typed(b'q'), Action::Typed(b' ')]);
+        assert_eq!(keys(b"\x1b", &mut escape, true), vec![Action::Typed(0x1b)]);
+    }
+
+    #[test]
+    fn line_edit_takes_a_line() {
+        let mut line = LineEdit::default();
+        for &byte in "phoned  Sam\x7f\x7f\x7fbob".as_bytes() { assert_eq!(line.feed(byte), Edit::Editing); }
+        assert_eq!(line.text, "phoned  bob");
+        // Test: Characters past ASCII come a byte at a time
+        for &byte in " café".as_bytes() { line.feed(byte); }
+        assert_eq!(line.feed(b'\r'), Edit::Done("phoned  bob café".to_string()));
+        // Test: Ctrl+U clears, Esc gives up
+        line.feed(0x15);
+        assert_eq!(line.text, "");
+        assert_eq!(line.feed(0x1b), Edit::Cancelled);
+    }
+
+    #[test]
+    fn harness_actions_parse_commands() {
+        // Test: Durations take the usual forms, keys map like real presses
+        assert_eq!(harness_actions("advance 1:30"), Ok(vec![Action::Advance(Duration::from_secs(90))]));
+        assert_eq!(harness_actions("keys  q"), Ok(vec![Action::TogglePause, Action::Quit]));
+        assert_eq!(harness_actions("type hi"), Ok(vec![Action::Typed(b'h'), Action::Typed(b'i'), Action::Typed(b'\r')]));
+        assert_eq!(harness_actions("sleep 5"), Err("unknown harness command 'sleep 5'".to_string()));
+        assert!(harness_actions("advance soon").is_err());
+    }
+}
@@ -0,0 +1,482 @@
+// src/json.rs
+// Minimal JSON (de)serializer for our serde types, compact output only.
+// Just enough for status/event/summary output without pulling in serde_json.
+use std::fmt;
+
+use serde::de::{self, DeserializeOwned, Visitor};
+use serde::ser::{self, Serialize};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "json: {}", self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self { Error(msg.to_string()) }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self { Error(msg.to_string()) }
+}
+
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, Error> {
+    let mut ser = Serializer { out: String::new() };
+    value.serialize(&mut ser)?;
+    Ok(ser.out)
+}
+
+pub fn from_str<T: DeserializeOwned>(input: &str) -> Result<T, Error> {
+    let mut de = Deserializer { input, pos: 0 };
+    let value = T::deserialize(&mut de)?;
+    de.skip_ws();
+    if de.pos != de.input.len() { return Err(de.error("trailing characters")); }
+    Ok(value)
+}
+
+fn write_escaped(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+// ============ Serializer =============
+
+pub struct Serializer {
+    out: String,
+}
+
+// Tracks whether a comma is needed before the next element/field
+pub struct Compound<'a> {
+    ser: &'a mut Serializer,
+    first: bool,
+    // Closing text, e.g. "]" or "}}" for variants wrapped in an object
+    close: &'static str,
+}
+
+impl Compound<'_> {
+    fn comma(&mut self) {
+        if !self.first { self.ser.out.push(','); }
+        self.first = false;
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.out.push_str(if v { "true" } else { "false" });
+        Ok(())
+    }
+    fn serialize_i8(self, v: i8) -> Result<(), Error> { self.serialize_i64(v as i64) }
+    fn serialize_i16(self, v: i16) -> Result<(), Error> { self.serialize_i64(v as i64) }
+    fn serialize_i32(self, v: i32) -> Result<(), Error> { self.serialize_i64(v as i64) }
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.out.push_str(&v.to_string());
+        Ok(())
+    }
+    fn serialize_u8(self, v: u8) -> Result<(), Error> { self.serialize_u64(v as u64) }
+    fn serialize_u16(self, v: u16) -> Result<(), Error> { self.serialize_u64(v as u64) }
+    fn serialize_u32(self, v: u32) -> Result<(), Error> { self.serialize_u64(v as u64) }
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.out.push_str(&v.to_string());
+        Ok(())
+    }
+    fn serialize_f32(self, v: f32) -> Result<(), Error> { self.serialize_f64(v as f64) }
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        // JSON has no NaN/inf
+        if v.is_finite() { self.out.push_str(&v.to_string()); } else { self.out.push_str("null"); }
+        Ok(())
+    }
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        write_escaped(&mut self.out, v.encode_utf8(&mut [0; 4]));
+        Ok(())
+    }
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        write_escaped(&mut self.out, v);
+        Ok(())
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        use ser::SerializeSeq;
+        let mut seq = self.serialize_seq(Some(v.len()))?;
+        for b in v { seq.serialize_element(b)?; }
+        seq.end()
+    }
+    fn serialize_none(self) -> Result<(), Error> { self.serialize_unit() }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.out.push_str("null");
+        Ok(())
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> { self.serialize_unit() }
+    fn serialize_unit_variant(self, _name: &'static str, _idx: u32, variant: &'static str) -> Result<(), Error> {
+        self.serialize_str(variant)
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self, _name: &'static str, _idx: u32, variant: &'static str, value: &T,
+    ) -> Result<(), Error> {
+        self.out.push('{');
+        write_escaped(&mut self.out, variant);
+        self.out.push(':');
+        value.serialize(&mut *self)?;
+        self.out.push('}');
+        Ok(())
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a>, Error> {
+        self.out.push('[');
+        Ok(Compound { ser: self, first: true, close: "]" })
+    }
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'a>, Error> { self.serialize_seq(Some(len)) }
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Compound<'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_variant(
+        self, _name: &'static str, _idx: u32, variant: &'static str, _len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        self.out.push('{');
+        write_escaped(&mut self.out, variant);
+        self.out.push_str(":[");
+        Ok(Compound { ser: self, first: true, close: "]}" })
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a>, Error> {
+        self.out.push('{');
+        Ok(Compound { ser: self, first: true, close: "}" })
+    }
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Compound<'a>, Error> {
+        self.serialize_map(Some(len))
+    }
+    fn serialize_struct_variant(
+        self, _name: &'static str, _idx: u32, variant: &'static str, _len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        self.out.push('{');
+        write_escaped(&mut self.out, variant);
+        self.out.push_str(":{");
+        Ok(Compound { ser: self, first: true, close: "}}" })
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.comma();
+        value.serialize(&mut *self.ser)
+    }
+    fn end(self) -> Result<(), Error> {
+        self.ser.out.push_str(self.close);
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<(), Error> { ser::SerializeSeq::end(self) }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<(), Error> { ser::SerializeSeq::end(self) }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<(), Error> { ser::SerializeSeq::end(self) }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.comma();
+        // Keys must come out as JSON strings, serialize then quote if needed
+        let key = to_string(key)?;
+        if key.starts_with('"') { self.ser.out.push_str(&key); }
+        else { write_escaped(&mut self.ser.out, &key); }
+        Ok(())
+    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.ser.out.push(':');
+        value.serialize(&mut *self.ser)
+    }
+    fn end(self) -> Result<(), Error> { ser::SerializeSeq::end(self) }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        self.comma();
+        write_escaped(&mut self.ser.out, key);
+        self.ser.out.push(':');
+        value.serialize(&mut *self.ser)
+    }
+    fn end(self) -> Result<(), Error> { ser::SerializeSeq::end(self) }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        ser::SerializeStruct::serialize_field(self, key, value)
+    }
+    fn end(self) -> Result<(), Error> { ser::SerializeSeq::end(self) }
+}
+
+// ============ Deserializer =============
+
+pub struct Deserializer<'de> {
+    input: &'de str,
+    pos: usize,
+}
+
+impl<'de> Deserializer<'de> {
+    fn error(&self, msg: &str) -> Error {
+        Error(format!("{} at offset {}", msg, self.pos))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\n' | b'\r' | b'\t')) { self.pos += 1; }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), Error> {
+        self.skip_ws();
+        if self.peek() != Some(c) { return Err(self.error(&format!("expected '{}'", c as char))); }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn eat_literal(&mut self, lit: &str) -> Result<(), Error> {
+        if !self.input[self.pos..].starts_with(lit) { return Err(self.error("invalid literal")); }
+        self.pos += lit.len();
+        Ok(())
+    }
+
+    fn parse_string(&mut self) -> Result<String, Error> {
+        self.expect(b'"')?;
+        let mut out = String::new();
+        let mut chars = self.input[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                '\\' => {
+                    let (_, esc) = chars.next().ok_or_else(|| self.error("unterminated escape"))?;
+                    match esc {
+                        '"' => out.push('"'),
+                        '\\' => out.push('\\'),
+                        '/' => out.push('/'),
+                        'b' => out.push('\u{8}'),
+                        'f' => out.push('\u{c}'),
+                        'n' => out.push('\n'),
+                        'r' => out.push('\r'),
+                        't' => out.push('\t'),
+                        'u' => {
+                            let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                            let code = u32::from_str_radix(&hex, 16).map_err(|_| self.error("bad \\u escape"))?;
+                            // Surrogate pairs are not built, lone surrogates become U+FFFD
+                            out.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                        }
+                        _ => return Err(self.error("unknown escape")),
+                    }
+                }
+                c => out.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn parse_number<V: Visitor<'de>>(&mut self, visitor: V) -> Result<V::Value, Error> {
+        let start = self.pos;
+        while matches!(self.peek(), Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')) { self.pos += 1; }
+        let text = &self.input[start..self.pos];
+        if let Ok(n) = text.parse::<u64>() { return visitor.visit_u64(n); }
+        if let Ok(n) = text.parse::<i64>() { return visitor.visit_i64(n); }
+        match text.parse::<f64>() {
+            Ok(n) => visitor.visit_f64(n),
+            Err(_) => Err(self.error("invalid number")),
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.skip_ws();
+        match self.peek() {
+            Some(b'n') => { self.eat_literal("null")?; visitor.visit_unit() }
+            Some(b't') => { self.eat_literal("true")?; visitor.visit_bool(true) }
+            Some(b'f') => { self.eat_literal("false")?; visitor.visit_bool(false) }
+            Some(b'"') => visitor.visit_string(self.parse_string()?),
+            Some(b'[') => {
+                self.pos += 1;
+                let value = visitor.visit_seq(Access { de: &mut *self, first: true, end: b']' })?;
+                self.expect(b']')?;
+                Ok(value)
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let value = visitor.visit_map(Access { de: &mut *self, first: true, end: b'}' })?;
+                self.expect(b'}')?;
+                Ok(value)
+            }
+            Some(b'-' | b'0'..=b'9') => self.parse_number(visitor),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.skip_ws();
+        if self.peek() == Some(b'n') {
+            self.eat_literal("null")?;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+// Shared SeqAccess/MapAccess, `end` is the closing bracket to look for
+struct Access<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    first: bool,
+    end: u8,
+}
+
+impl Access<'_, '_> {
+    // Returns false at the closing bracket, eats the comma between items
+    fn has_next(&mut self) -> Result<bool, Error> {
+        self.de.skip_ws();
+        if self.de.peek() == Some(self.end) { return Ok(false); }
+        if !self.first { self.de.expect(b',')?; }
+        self.first = false;
+        Ok(true)
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for Access<'_, 'de> {
+    type Error = Error;
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Error> {
+        if !self.has_next()? { return Ok(None); }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Access<'_, 'de> {
+    type Error = Error;
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Error> {
+        if !self.has_next()? { return Ok(None); }
+        self.de.skip_ws();
+        if self.de.peek() != Some(b'"') { return Err(self.de.error("object keys must be strings")); }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        self.de.expect(b':')?;
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn serializes_primitives() {
+        assert_eq!(to_string(&true).unwrap(), "true");
+        assert_eq!(to_string(&-42i32).unwrap(), "-42");
+        assert_eq!(to_string(&1.5f64).unwrap(), "1.5");
+        assert_eq!(to_string(&f64::NAN).unwrap(), "null");
+        assert_eq!(to_string(&None::<u8>).unwrap(), "null");
+        assert_eq!(to_string(&Some(3u8)).unwrap(), "3");
+    }
+
+    #[test]
+    fn escapes_strings() {
+        // Test: Quotes, backslashes and control chars are escaped
+        assert_eq!(to_string("a\"b\\c\n\u{1}").unwrap(), r#""a\"b\\c\n\u0001""#);
+        // Test: Non-ASCII passes through untouched
+        assert_eq!(to_string("☕").unwrap(), "\"☕\"");
+    }
+
+    #[test]
+    fn serializes_collections() {
+        assert_eq!(to_string(&vec![1, 2, 3]).unwrap(), "[1,2,3]");
+        let mut map = BTreeMap::new();
+        map.insert(1u32, "one");
+        map.insert(2u32, "two");
+        // Test: Non-string keys are quoted
+        assert_eq!(to_string(&map).unwrap(), r#"{"1":"one","2":"two"}"#);
+    }
+
+    #[test]
+    fn roundtrips_collections() {
+        let v: Vec<Option<String>> = from_str(r#" [ "a", null , "é\t" ] "#).unwrap();
+        assert_eq!(v, vec![Some("a".into()), None, Some("é\t".into())]);
+        let m: BTreeMap<String, i64> = from_str(r#"{"x": -1, "y": 2}"#).unwrap();
+        assert_eq!(m["x"], -1);
+        assert_eq!(m["y"], 2);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert!(from_str::<Vec<u8>>("[1,2").is_err());
+        assert!(from_str::<u8>("1 2").is_err());
+        assert!(from_str::<String>("\"open").is_err());
+        assert!(from_str::<BTreeMap<String, u8>>("{1: 2}").is_err());
+    }
+}
@@ -1,3 +1,52 @@
 // serc/lib.rs
+pub mod ack;
+pub mod activity;
+pub mod autolabel;
+pub mod calendar;
 pub mod cli;
+pub mod clock;
+pub mod config;
+pub mod control;
+pub mod cron;
+pub mod days;
+pub mod diagnose;
+pub mod duel;
+pub mod event;
+pub mod git;
+#[cfg(feature = "gpio")]
+pub mod gpio;
+pub mod hook;
+pub mod input;
+pub mod json;
+pub mod log;
+pub mod media;
+pub mod morse;
+pub mod notify;
+pub mod pace;
+pub mod plan;
+pub mod platform;
+pub mod power;
+pub mod prompt;
+pub mod quotes;
+pub mod render;
+pub mod schedule;
+pub mod schema;
+pub mod screenshot;
+pub mod serial;
 pub mod signal;
+pub mod sound;
+pub mod sounds;
+pub mod stats;
+pub mod template;
+pub mod terminal;
+pub mod text;
+pub mod timer;
+pub mod today;
+pub mod tone;
+pub mod trigger;
+pub mod wire;
+pub mod zone;
+
+pub use event::{Status, Summary, TimerEvent};
+pub use plan::{Phase, TimerPlan};
+pub use timer::{Direction, Timer, TimerConfig};
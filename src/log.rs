@@ -0,0 +1,167 @@
+// src/log.rs
+// Timer history as syslog-style lines in $XDG_CACHE_HOME/timerterm/timerterm.log
+// (~/.cache when unset), so grep and awk can answer "how often did I pause?":
+//   Jan 15 14:23:45 timerterm[12345]: PAUSE elapsed=192s reason=coffee
+// Failing to write is never fatal, the timer matters more than its log.
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::event::TimerEvent;
+
+// Past this the log is deleted and started over
+pub const MAX_SIZE: u64 = 64 * 1024 * 1024;
+
+const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+pub fn path() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_CACHE_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".cache"),
+    };
+    Some(base.join("timerterm").join("timerterm.log"))
+}
+
+// The part after "timerterm[pid]: "
+pub fn message(event: &TimerEvent) -> String {
+    match event {
+        TimerEvent::Started { duration, elapsed, estimate, label, tag, dir } => {
+            let mut line = format!("START duration={}s", duration.as_secs());
+            if !elapsed.is_zero() { line.push_str(&format!(" elapsed={}s", elapsed.as_secs())); }
+            if let Some(estimate) = estimate { line.push_str(&format!(" estimate={}s", estimate.as_secs())); }
+            if let Some(label) = label { line.push_str(&format!(" label={:?}", label)); }
+            if let Some(tag) = tag { line.push_str(&format!(" tag={:?}", tag)); }
+            if let Some(dir) = dir { line.push_str(&format!(" dir={:?}", dir)); }
+            line
+        }
+        TimerEvent::Paused { elapsed, reason: Some(reason) } => format!("PAUSE elapsed={}s reason={}", elapsed.as_secs(), reason),
+        TimerEvent::Paused { elapsed, reason: None } => format!("PAUSE elapsed={}s", elapsed.as_secs()),
+        TimerEvent::Resumed { elapsed } => format!("RESUME elapsed={}s", elapsed.as_secs()),
+        // Quoted, labels like "Work 1/4" have spaces
+        TimerEvent::PhaseStarted { index, label } => format!("PHASE index={} label={:?}", index, label),
+        TimerEvent::Adjusted { elapsed, duration } => format!("ADJUST elapsed={}s duration={}s", elapsed.as_secs(), duration.as_secs()),
+        TimerEvent::Interrupted { elapsed, note } => format!("INTERRUPT elapsed={}s note={:?}", elapsed.as_secs(), note),
+        TimerEvent::Reflected { note } => format!("REFLECT note={:?}", note),
+        TimerEvent::Finished { elapsed } => format!("COMPLETED elapsed={}s", elapsed.as_secs()),
+        TimerEvent::Cancelled { elapsed } => format!("CANCELLED elapsed={}s", elapsed.as_secs()),
+    }
+}
+
+// One line read back, see format_entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry<'a> {
+    pub day: &'a str,     // "Jan 15"
+    pub secs: u32,        // Since local midnight
+    pub pid: u32,
+    pub message: &'a str, // "PAUSE elapsed=192s"
+}
+
+// None for anything that isn't a line this module wrote
+pub fn parse_entry(line: &str) -> Option<Entry<'_>> {
+    let (day, rest) = (line.get(..6)?, line.get(7..)?);
+    let (time, rest) = rest.split_once(" timerterm[")?;
+    let (pid, message) = rest.split_once("]: ")?;
+    let mut hms = time.split(':').map(|n| n.parse::<u32>().ok());
+    let secs = match (hms.next()??, hms.next()??, hms.next()??, hms.next()) {
+        (h, m, s, None) => h * 3600 + m * 60 + s,
+        _ => return None,
+    };
+    Some(Entry { day, secs, pid: pid.parse().ok()?, message })
+}
+
+// "Jan 15", how entries logged on the day `unix` falls on begin
+pub fn day(unix: i64) -> String {
+    timestamp(unix)[..6].to_string()
+}
+
+// "Jan 15 14:23:45" in local time
+fn timestamp(unix: i64) -> String {
+    let Some(tm) = crate::platform::local_time(unix) else { return "??? ?? ??:??:??".to_string() };
+    format!("{} {:2} {:02}:{:02}:{:02}", MONTHS[tm.tm_mon as usize % 12], tm.tm_mday, tm.tm_hour, tm.tm_min, tm.tm_sec)
+}
+
+pub fn format_entry(unix: i64, pid: u32, event: &TimerEvent) -> String {
+    format!("{} timerterm[{}]: {}", timestamp(unix), pid, message(event))
+}
+
+pub fn write_event(event: &TimerEvent) -> io::Result<()> {
+    let path = path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no $HOME for the log"))?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    append(&path, &format_entry(now, std::process::id(), event), MAX_SIZE)
+}
+
+// Add one line, starting the file over once it has grown past `max` bytes
+fn append(path: &Path, line: &str, max: u64) -> io::Result<()> {
+    if let Some(dir) = path.parent() { std::fs::create_dir_all(dir)?; }
+    if std::fs::metadata(path).is_ok_and(|m| m.len() > max) { std::fs::remove_file(path)?; }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn message_names_event_and_fields() {
+        // Test: Pause reasons ride along with the pause
+        let secs = Duration::from_secs;
+        assert_eq!(message(&TimerEvent::Started { duration: secs(600), elapsed: secs(0), estimate: None, label: None, tag: None, dir: None }), "START duration=600s");
+        assert_eq!(message(&TimerEvent::Started { duration: secs(600), elapsed: secs(420), estimate: None, label: None, tag: None, dir: None }), "START duration=600s elapsed=420s");
+        assert_eq!(message(&TimerEvent::Started { duration: secs(600), elapsed: secs(0), estimate: None, label: Some("Pasta".into()), tag: None, dir: None }), "START duration=600s label=\"Pasta\"");
+        let tagged = TimerEvent::Started { duration: secs(60), elapsed: secs(0), estimate: None, label: None, tag: Some("thesis:vim".into()), dir: None };
+        assert_eq!(message(&tagged), "START duration=60s tag=\"thesis:vim\"");
+        let estimated = TimerEvent::Started { duration: secs(3600), elapsed: secs(0), estimate: Some(secs(2700)), label: None, tag: None, dir: None };
+        assert_eq!(message(&estimated), "START duration=3600s estimate=2700s");
+        let placed = TimerEvent::Started { duration: secs(60), elapsed: secs(0), estimate: None, label: None, tag: None, dir: Some("/home/me/thesis".into()) };
+        assert_eq!(message(&placed), "START duration=60s dir=\"/home/me/thesis\"");
+        assert_eq!(message(&TimerEvent::Paused { elapsed: secs(192), reason: Some("coffee".into()) }), "PAUSE elapsed=192s reason=coffee");
+        assert_eq!(message(&TimerEvent::Paused { elapsed: secs(192), reason: None }), "PAUSE elapsed=192s");
+        assert_eq!(message(&TimerEvent::Adjusted { elapsed: secs(90), duration: secs(660) }), "ADJUST elapsed=90s duration=660s");
+        assert_eq!(message(&TimerEvent::PhaseStarted { index: 0, label: "Work 1/4".into() }), "PHASE index=0 label=\"Work 1/4\"");
+        assert_eq!(message(&TimerEvent::Interrupted { elapsed: secs(420), note: "Sam called".into() }), "INTERRUPT elapsed=420s note=\"Sam called\"");
+        assert_eq!(message(&TimerEvent::Reflected { note: "Fixed the parser".into() }), "REFLECT note=\"Fixed the parser\"");
+    }
+
+    #[test]
+    fn format_entry_is_syslog_style() {
+        // Test: "Mmm dd hh:mm:ss timerterm[pid]: MESSAGE", whatever the local timezone
+        let line = format_entry(1748768400, 12345, &TimerEvent::Finished { elapsed: Duration::from_secs(600) });
+        let (stamp, rest) = line.split_at(15);
+        assert!(MONTHS.contains(&&stamp[..3]), "got {:?}", line);
+        assert_eq!(stamp.as_bytes()[9], b':');
+        assert_eq!(rest, " timerterm[12345]: COMPLETED elapsed=600s");
+    }
+
+    #[test]
+    fn parse_entry_reads_back_format_entry() {
+        // Test: Day, time of day, pid and message come back apart
+        let line = format_entry(1748768400, 12345, &TimerEvent::Finished { elapsed: Duration::from_secs(600) });
+        let entry = parse_entry(&line).unwrap();
+        assert_eq!(entry.day, day(1748768400));
+        assert_eq!((entry.pid, entry.message), (12345, "COMPLETED elapsed=600s"));
+        assert_eq!(parse_entry("Jun  1 09:05:07 timerterm[7]: START duration=5s").unwrap().secs, 9 * 3600 + 5 * 60 + 7);
+        // Test: Other lines are skipped
+        assert_eq!(parse_entry("Jun  1 09:05 timerterm[7]: START"), None);
+        assert_eq!(parse_entry("something else entirely"), None);
+    }
+
+    #[test]
+    fn append_creates_dir_and_rotates() {
+        let dir = std::env::temp_dir().join(format!("timerterm-log-{}", std::process::id()));
+        let path = dir.join("nested").join("timerterm.log");
+        // Test: The directory is created and lines accumulate
+        append(&path, "one", 10).unwrap();
+        append(&path, "two", 10).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "one\ntwo\n");
+        // Test: Past the limit the file starts over
+        append(&path, "three", 7).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "three\n");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,26 +1,1718 @@
 // src/main.rs
-use std::time::Duration;
+use std::cell::{Cell, RefCell};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
 use std::thread;
 
-mod signal;
-mod cli;
+use timeterm::{Direction, Status, Summary, Timer, TimerConfig, TimerEvent, TimerPlan};
+use timeterm::{ack, autolabel, calendar, cli, clock, control, cron, days, diagnose, duel, git, json, log, media};
+use timeterm::{prompt, schema, screenshot, signal, sound, sounds, stats, template, text, today, tone};
+use timeterm::activity::{self, Activity};
+use timeterm::config::{self, Config};
+use timeterm::hook::{self, OrphanPolicy};
+use timeterm::input::{self, Action, Edit};
+use timeterm::morse::{self, Morse};
+use timeterm::notify::{self, Fallback};
+use timeterm::pace::Distance;
+use timeterm::power::{self, PowerSave};
+use timeterm::quotes::Quotes;
+use timeterm::render::{self, Layout};
+use timeterm::schedule::{self, Channel};
+use timeterm::serial::Serial;
+use timeterm::terminal::{self, ansi};
+use timeterm::trigger::Trigger;
+use timeterm::zone::{self, Zone};
 
-fn main() {
-    println!("TimerTerm: Hello, world!");
+// How often an unacknowledged --ack-required timer rings again
+const NAG_INTERVAL: Duration = Duration::from_secs(30);
+// How far [ and ] move the volume, and how long the new one is shown
+const VOLUME_STEP: u8 = 10;
+const VOLUME_SHOWN: Duration = Duration::from_secs(2);
+// Under the time on the --keypad screen
+const KEYPAD_PROMPT: &str = "Type the time, Enter starts it";
+// After a pomodoro work block, with pomodoro.reflection
+const REFLECTION_PROMPT: &str = "What did you accomplish?";
+// Shown after "PAUSED" while waiting for a reason, keys from input::PAUSE_REASONS
+const PAUSE_MENU: &str = "[c]offee [p]hone [m]eeting [o]ther";
 
+fn main() {
     // Parse CLI arguments
-    let args: Vec<String> = std::env::args().collect();
-    let duration = cli::parse_args(args).unwrap_or(600); // TODO: Make sure we handle errors
+    let mut args: Vec<String> = std::env::args().collect();
+    // Hidden, tests/ drive a fake clock through stdin with it, see input::harness_actions
+    if cli::take_test_harness(&mut args) { clock::use_fake(); }
+    // A template preset stands for the countdown it expands to
+    if args.get(1).map(String::as_str) == Some("preset") {
+        if let Err(e) = template::expand_preset(&mut args, &load_config().templates, prompt::ask) {
+            eprintln!("timerterm: {}\nRun 'timerterm --help' for usage.", e);
+            std::process::exit(2);
+        }
+    }
+    let command = match cli::parse_command(args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("timerterm: {}\nRun 'timerterm --help' for usage.", e);
+            std::process::exit(2);
+        }
+    };
+    // CLI args override config file values, loaded only by commands that use it
+    let opts = match command {
+        cli::Command::Schema(name) => return print_schema(name.as_deref()),
+        cli::Command::Ack(name) => return send_ack(&name),
+        cli::Command::Help(text) => return print!("{}", text),
+        cli::Command::Version => return println!("timerterm {}", env!("CARGO_PKG_VERSION")),
+        cli::Command::Presets => return print_presets(&load_config()),
+        cli::Command::Sounds(sounds) => return manage_sounds(&sounds, &load_config()),
+        cli::Command::Today => return print_today(),
+        cli::Command::Stats(opts) => return print_stats(&opts),
+        cli::Command::Days(opts) => return run_days(&opts, &load_config()),
+        cli::Command::Diagnose(secs) => return print_diagnosis(secs),
+        cli::Command::Pomodoro(opts) => return run_pomodoro(&opts, &load_config()),
+        cli::Command::Intervals(opts) => return run_intervals(&opts, &load_config()),
+        cli::Command::Duel(opts) => return run_duel(&opts, &load_config()),
+        cli::Command::Schedule(opts) => return run_schedule(&opts, &load_config()),
+        cli::Command::Run(opts) => *opts,
+    };
+    let mut config = load_config();
+    let preset = opts.preset.as_deref().map(|name| preset_duration(&config, name));
+    // Read once, then moved on by the monotonic clock for each --repeat cycle
+    let launched = (unix_now(), clock::now());
+    let until = opts.until.as_ref().map(until_duration).or(opts.cron.as_ref().map(|cron| cron_duration(cron, launched)));
+    // A --keypad run has none until it's typed in on the screen
+    let duration = if opts.keypad { 0 } else { opts.duration.or(until).or(preset).unwrap_or(config.duration) };
+    if let Some(layout) = opts.layout { config.layout = layout; }
+    if let Some(mode) = opts.power_save { config.power_save = mode; }
+    // A preset run can be acknowledged by the preset's name
+    let name = opts.name.as_deref().or(opts.preset.as_deref()).unwrap_or(ack::DEFAULT_NAME);
+    if !ack::is_valid_name(name) {
+        eprintln!("Invalid timer name '{}', use letters, digits, '-' or '_'", name);
+        std::process::exit(1);
+    }
+
+    // Segments run back to back, the --elapsed head start is into the first
+    let mut segments: Vec<u32> = std::iter::once(duration).chain(opts.then.iter().copied()).collect();
+    let total: u32 = segments.iter().sum();
+    let head_start = opts.elapsed.unwrap_or(0);
+    if !opts.stopwatch && head_start > 0 && head_start >= duration {
+        eprintln!("--elapsed {} leaves nothing of a {} timer", render::format_duration(head_start as u64), render::format_duration(duration as u64));
+        std::process::exit(1);
+    }
+
+    // Looked up before the screen so an error is readable
+    let repo = opts.timebox.as_ref().map(|_| git::Repo::current().unwrap_or_else(|e| {
+        eprintln!("git-timebox: {}", e);
+        std::process::exit(1);
+    }));
+    let label = opts.label.clone().or_else(|| repo.as_ref().map(git::Repo::label)).or_else(|| config.auto_label.label());
+    let timer_config = TimerConfig { label, name: opts.name.clone().or_else(|| opts.preset.clone()) };
+
+    // Typo-looking durations and meeting clashes need a yes, unless --force
+    if !opts.force {
+        let typo = if opts.stopwatch || opts.keypad { None } else { segments.iter().find_map(|&secs| cli::suspicious_duration(secs, config.max_duration)) };
+        if let Some(why) = typo {
+            if !confirm(&opts, &format!("{}, start it anyway?", why)) { return; }
+        }
+        // A stopwatch has no end to clash with
+        let calendar = opts.calendar.as_deref().filter(|_| !opts.stopwatch);
+        if let Some(question) = calendar.and_then(|path| check_calendar(path, total.saturating_sub(head_start))) {
+            if !confirm(&opts, &question) { return; }
+        }
+    }
 
     // Register signal handlers
-    signal::register_sigint_handler();
+    signal::register_exit_handlers();
+
+    let timer = if opts.stopwatch {
+        Timer::stopwatch()
+    } else {
+        Timer::new(Duration::from_secs(duration as u64))
+    };
+    let mut timer = timer.with_elapsed(Duration::from_secs(head_start as u64));
+    let alarm = alarm_for(&config, &opts.alarm);
+    // Checked up front, a result shouldn't be lost to a build without PNG support
+    if let Some(path) = opts.screenshot.as_deref() {
+        if screenshot::format_for(Path::new(path)) == screenshot::Format::Png && !cfg!(feature = "png") {
+            eprintln!("--screenshot {}: PNG screenshots need the png feature, use a .txt file", path);
+            std::process::exit(1);
+        }
+    }
+
+    let start_on = opts.start_on.clone().map(|trigger| trigger.resolve().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }));
+    let quotes = load_quotes(opts.quotes.as_deref(), &config);
+    let control = opts.control_fifo.as_deref().map(open_control);
+    // Both tried before the timer takes the screen, so a bad path is readable
+    let events = opts.events_file.as_deref().map(open_events_file);
+    let status_file = opts.status_file.as_deref().map(PathBuf::from);
+    if let Some(path) = &status_file {
+        if let Err(e) = write_status(path, &Status::from_timer(&timer, timer_config.display_name())) {
+            eprintln!("Couldn't write --status-file {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+    }
+
+    let serial = opts.serial.as_deref().map(|device| Serial::open(device, config.serial_baud, &config.serial_format).unwrap_or_else(|e| {
+        eprintln!("Couldn't open --serial {}", e);
+        std::process::exit(1);
+    }));
+
+    if opts.ack_required || opts.overtime { ack::take(name); } // Drop any stale ack from an earlier run
+    let (mut notify_error, mut notify_stderr) = (None, None);
+    let mut shot = None; // Final frame text and time, saved once the terminal is back
+    let mut overtime = Duration::ZERO;
+    let mut watched = None; // How the --watch-exec command ended, reported once the terminal is back
+    let finished = {
+        let mut screen = Screen::new(&config, alarm, opts.plain);
+        screen.distance = opts.distance;
+        screen.quotes = RefCell::new(quotes);
+        screen.pause_reasons |= opts.pause_reasons;
+        screen.pause_on_suspend |= opts.pause_on_suspend;
+        screen.log &= !opts.no_log;
+        screen.timer_config = timer_config.clone();
+        screen.estimate = opts.estimate.map(|secs| Duration::from_secs(secs as u64));
+        screen.events = events;
+        screen.status_file = status_file.clone();
+        screen.serial = serial;
+        if let Some(fifo) = &control { fifo.listen(screen.actions.clone()); }
+        let keyed = !opts.keypad || match screen.keypad() {
+            Some(secs) => {
+                segments[0] = secs;
+                timer = Timer::new(Duration::from_secs(secs as u64));
+                true
+            }
+            None => false,
+        };
+        // The deadline is only set once the trigger goes off
+        let armed = keyed && match &start_on {
+            Some(trigger) => screen.arm(&timer, trigger),
+            None => true,
+        };
+        let (mut cycle, mut segment) = (1, 0);
+        let finished = armed && loop {
+            let counter = match (opts.repeat, segments.len()) {
+                (Some(repeat), _) => Some(repeat.counter(cycle)),
+                (None, 1) => None,
+                (None, count) => Some(format!("Segment {} of {}", segment + 1, count)),
+            };
+            if segments.len() > 1 {
+                let before: u32 = segments[..segment].iter().sum();
+                screen.chain = Some((Duration::from_secs(before as u64), Duration::from_secs(total as u64)));
+            }
+            let finished = screen.run(&mut timer, counter.as_deref(), None);
+            let more = segment + 1 < segments.len() || opts.repeat.is_some_and(|repeat| !repeat.is_last(cycle));
+            if !finished || !more { break finished; }
+            // Each one is the full planned length, whatever + and - did to the last
+            screen.chime();
+            if opts.repeat.is_some() { cycle += 1; } else { segment += 1; }
+            let secs = match &opts.cron {
+                Some(cron) => cron_duration(cron, launched),
+                None => segments[segment],
+            };
+            timer = Timer::new(Duration::from_secs(secs as u64));
+        };
+        // Quitting early, not a signal, hands over to the --on-cancel-then timer
+        let finished = match opts.on_cancel_then {
+            Some(secs) if armed && !finished && !signal::should_exit() => {
+                screen.chain = None;
+                timer = Timer::new(Duration::from_secs(secs as u64));
+                screen.run(&mut timer, Some("Follow-up"), None)
+            }
+            _ => finished,
+        };
+        let zero = clock::now();
+        if opts.screenshot.is_some() { shot = Some(screen.final_frame(&timer, None)); }
+        if finished {
+            // Held below, so dismissing it is when they start again
+            let paused = if config.pause_media && (opts.ack_required || opts.overtime) { media::pause() } else { Vec::new() };
+            screen.ring();
+            if opts.notify.unwrap_or(config.notify) {
+                let body = screen.timer_config.finished_message(timer.elapsed());
+                if let Err(e) = notify::send_notification("timerterm", &body) {
+                    let via = config.notify_fallback.iter().copied().find(|&via| screen.notify_via(via, &body));
+                    if via == Some(Fallback::Stderr) { notify_stderr = Some(body); }
+                    // No notifier installed only matters if --notify asked for one, or something stood in
+                    if via.is_some() || opts.notify == Some(true) || e.kind() != std::io::ErrorKind::NotFound {
+                        notify_error = Some((e, via));
+                    }
+                }
+            }
+            if opts.ack_required || opts.overtime {
+                let notify = opts.notify.unwrap_or(config.notify);
+                overtime = screen.hold(name, timer.duration(), opts.ack_required, notify, opts.overtime.then_some(zero));
+            }
+            media::resume(&paused);
+            if let (true, Some(cmd)) = (opts.watch_exec && !signal::should_exit(), &opts.exec) {
+                watched = Some(screen.supervise(cmd, name, timer.duration()));
+            }
+        }
+        finished
+    }; // Terminal restored here
+
+    // Reported late, stderr would otherwise land on the timer screen
+    if let Some(body) = notify_stderr { eprintln!("timerterm: {}", body); }
+    match notify_error {
+        Some((e, Some(via))) => eprintln!("Couldn't send notification: {}, used {} instead", e, via.name()),
+        Some((e, None)) => eprintln!("Couldn't send notification: {}", e),
+        None => {}
+    }
+
+    if let (Some(path), Some((text, time))) = (&opts.screenshot, shot) {
+        if let Err(e) = screenshot::save(Path::new(path), &text, &time) {
+            eprintln!("Couldn't save screenshot {}: {}", path, e);
+            std::process::exit(1);
+        }
+    }
+
+    // Only there while it runs, a stale one would look like a stuck timer
+    if let Some(path) = &status_file { let _ = std::fs::remove_file(path); }
+
+    let summary = Summary { overtime, ..Summary::from_timer(&timer, timer_config.display_name()) };
+    if opts.json {
+        match json::to_string(&summary) {
+            Ok(text) => println!("{}", text),
+            Err(e) => eprintln!("Couldn't write the summary: {}", e),
+        }
+    }
+    if let Some(sig) = signal::received() { return report_signal(sig, &summary); }
+
+    if let (Some(timebox), Some(repo)) = (&opts.timebox, &repo) {
+        let file = timebox.message_file.as_ref().map_or_else(|| repo.commit_message(), PathBuf::from);
+        let trailer = git::trailer(Duration::from_secs(duration as u64), timer.elapsed() + overtime);
+        if let Err(e) = git::add_trailer(&file, &trailer) {
+            eprintln!("Couldn't add the Timeboxed trailer: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    if timer.direction() == Direction::Up && !opts.json {
+        println!("Elapsed: {}", render::format_duration(timer.elapsed().as_secs()));
+    }
+    if opts.overtime && finished && !opts.json { println!("{}", summary); }
+
+    match (finished, &opts.exec, watched) {
+        (true, Some(_), Some(watched)) => report_exec(watched),
+        (true, Some(cmd), None) if !opts.watch_exec => report_exec(hook::run(cmd, name, timer.duration()).map(Some)),
+        _ => {}
+    }
+}
+
+// How the --exec command went, passing on a failing exit code. None is
+// killed with k.
+fn report_exec(status: std::io::Result<Option<std::process::ExitStatus>>) {
+    match status {
+        Ok(Some(status)) if status.success() => {}
+        Ok(Some(status)) => {
+            eprintln!("--exec command failed: {}", status);
+            std::process::exit(status.code().unwrap_or(1));
+        }
+        Ok(None) => {
+            eprintln!("--exec command killed");
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Couldn't run --exec command: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+// CLI alarm flags over config, exiting early if a sound file is missing
+fn alarm_for(config: &Config, args: &cli::AlarmArgs) -> sound::Alarm {
+    if args.silent { return sound::Alarm::silent(); }
+    // Either may be a name from the sound library
+    let file = args.sound.as_ref().map(PathBuf::from).or_else(|| config.sound.clone()).map(|f| sounds::resolve(&f));
+    let chime = config.chime.as_deref().map(sounds::resolve);
+    if let Some(file) = file.iter().chain(&chime).find(|f| !f.is_file()) {
+        eprintln!("Sound file {} not found", file.display());
+        std::process::exit(1);
+    }
+    // A [tone] stands in for a missing file
+    let generated = |tone: &Option<tone::Tone>| tone.as_ref().map(|tone| tone::file(tone).unwrap_or_else(|e| {
+        eprintln!("Couldn't write the [tone] sound: {}", e);
+        std::process::exit(1);
+    }));
+    let file = file.or_else(|| generated(&config.tone.alarm));
+    let chime = chime.or_else(|| generated(&config.tone.chime));
+    sound::Alarm { file, volume: args.volume.unwrap_or(config.volume), bell: config.bell, chime }
+}
+
+// --quotes over display.quotes, exiting early if the file is unreadable or empty
+fn load_quotes(arg: Option<&str>, config: &Config) -> Option<Quotes> {
+    let path = arg.map(PathBuf::from).or_else(|| config.quotes.clone())?;
+    match Quotes::load(&path) {
+        Ok(quotes) => Some(quotes),
+        Err(e) => {
+            eprintln!("Couldn't read quotes {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+// Exits if the FIFO can't be made, a timer that ignored its commands would be worse
+fn open_control(path: &str) -> control::Fifo {
+    control::Fifo::open(Path::new(path)).unwrap_or_else(|e| {
+        eprintln!("--control-fifo {}: {}", path, e);
+        std::process::exit(1);
+    })
+}
+
+fn open_events_file(path: &str) -> std::fs::File {
+    std::fs::OpenOptions::new().create(true).append(true).open(path).unwrap_or_else(|e| {
+        eprintln!("Couldn't open --events-file {}: {}", path, e);
+        std::process::exit(1);
+    })
+}
+
+// Written beside it and renamed over it, so a reader never sees half of one
+fn write_status(path: &Path, status: &Status) -> std::io::Result<()> {
+    let text = json::to_string(status).map_err(std::io::Error::other)?;
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".tmp");
+    std::fs::write(&partial, text + "\n")?;
+    std::fs::rename(&partial, path)
+}
+
+fn load_config() -> Config {
+    config::load().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    })
+}
+
+fn manage_sounds(sounds: &cli::Sounds, config: &Config) {
+    let Some(dir) = sounds::dir() else {
+        eprintln!("Couldn't find the sound library, set XDG_DATA_HOME or HOME");
+        std::process::exit(1);
+    };
+    let fail = |e: String| -> ! {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    };
+    match sounds {
+        cli::Sounds::List => match sounds::list(&dir) {
+            Ok(list) if list.is_empty() => println!("No sounds, add some with: timerterm sounds add FILE"),
+            Ok(list) => list.iter().for_each(|(name, _)| println!("{}", name)),
+            Err(e) => fail(format!("Couldn't read {}: {}", dir.display(), e)),
+        },
+        cli::Sounds::Play(name) => {
+            let file = sounds::find(&dir, name).unwrap_or_else(|| fail(format!("No sound called '{}', see: timerterm sounds list", name)));
+            if let Err(e) = sound::play(&file, config.volume) { fail(format!("Couldn't play {}: {}", file.display(), e)); }
+        }
+        cli::Sounds::Add(file) => match sounds::add(&dir, Path::new(file)) {
+            Ok(name) => println!("Added '{}', use it with --sound {}", name, name),
+            Err(e) => fail(format!("Couldn't add {}: {}", file, e)),
+        },
+    }
+}
+
+fn print_presets(config: &Config) {
+    if config.presets.is_empty() && config.templates.is_empty() {
+        let file = config::path().map_or("the config file".to_string(), |p| p.display().to_string());
+        return println!("No presets, add some under [presets] in {}", file);
+    }
+    let templates = config.templates.iter().map(|(name, template)| (name, template.source.clone()));
+    let presets: Vec<(&String, String)> = config.presets.iter()
+        .map(|(name, secs)| (name, render::format_duration(*secs as u64)))
+        .chain(templates)
+        .collect();
+    let width = presets.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    for (name, value) in presets {
+        println!("{:width$}  {}", name, value, width = width);
+    }
+}
+
+// What the clocks did over `secs`, then anything worth knowing about it
+fn print_diagnosis(secs: u32) {
+    println!("Sampling the clocks for {}...", render::format_duration(secs as u64));
+    let resolution = diagnose::resolution();
+    let samples = diagnose::sample(Duration::from_secs(secs as u64));
+    let report = diagnose::analyze(&samples, resolution, timeterm::platform::clock_source());
+    for line in report.lines() { println!("{}", line); }
+    let anomalies = report.anomalies();
+    if anomalies.is_empty() { return println!("\nNo anomalies, timers should keep good time here"); }
+    println!("\nAnomalies:");
+    for anomaly in anomalies { println!("  - {}", anomaly); }
+}
+
+// Today's timeline, colored when stdout is a terminal
+fn print_today() {
+    let now = unix_now();
+    let sessions = today::sessions(&read_history(), &log::day(now));
+    if sessions.is_empty() { return println!("No runs logged today"); }
+    let Some(tm) = timeterm::platform::local_time(now) else { return eprintln!("Couldn't read the local time") };
+    let since_midnight = (tm.tm_hour * 3600 + tm.tm_min * 60 + tm.tm_sec) as u32;
+    let size = terminal::get_size();
+    let width = size.map_or(terminal::FALLBACK_SIZE.0, |(cols, _)| cols) as usize;
+    for line in today::timeline(&sessions, since_midnight, width, size.is_some()) { println!("{}", line); }
+}
+
+// Totals over the whole history log
+fn print_stats(opts: &cli::StatsArgs) {
+    let sessions = today::every_session(&read_history());
+    if sessions.is_empty() { return println!("No runs logged yet"); }
+    let home = std::env::var("HOME").ok();
+    for line in stats::report(&sessions, opts.by_project, home.as_deref()) { println!("{}", line); }
+}
 
-    let start = std::time::Instant::now();
-    // DELETEME: Keep running until we implement proper signal handling
-    loop {
-        if signal::should_exit() || start.elapsed().as_secs() >= duration as u64 {
+// The history log's text, none if there's no log yet
+fn read_history() -> String {
+    match log::path().map(std::fs::read_to_string) {
+        Some(Ok(text)) => text,
+        Some(Err(e)) if e.kind() != std::io::ErrorKind::NotFound => {
+            eprintln!("Couldn't read the history log: {}", e);
+            std::process::exit(1);
+        }
+        _ => String::new(),
+    }
+}
+
+// The days left until a date, counted again once a minute. The wall clock
+// is read once and moved on by the monotonic one, so the harness can jump
+// days ahead.
+fn run_days(opts: &cli::DaysArgs, config: &Config) {
+    let launched = unix_now();
+    let start = clock::now();
+    let left = || days::until(opts.date, launched + clock::now().saturating_duration_since(start).as_secs() as i64);
+    match left() {
+        None => return eprintln!("Couldn't read the local time"),
+        Some(days) if days < 0 => {
+            let (y, m, d) = opts.date;
+            eprintln!("{:04}-{:02}-{:02} has already passed", y, m, d);
+            std::process::exit(1);
+        }
+        Some(_) => {}
+    }
+    signal::register_exit_handlers();
+    {
+        let screen = Screen::new(config, sound::Alarm::silent(), opts.plain);
+        let mut drawn = None;
+        while !signal::should_exit() {
+            if screen.job_control(None) { drawn = None; }
+            if screen.resized() { drawn = None; }
+            let Some(days) = left().filter(|&days| days >= 0) else { break };
+            if drawn != Some(days) {
+                screen.show_in(screen.color, &days.to_string(), None, &[], Some(&days::status(days, opts.date)));
+                drawn = Some(days);
+            }
+            match screen.wait(Some(clock::now() + Duration::from_secs(60))) {
+                Some(Action::Quit) => break,
+                Some(Action::Advance(by)) => clock::advance(by),
+                Some(Action::Echoed) => {
+                    screen.wipe();
+                    drawn = None;
+                }
+                _ => {}
+            }
+        }
+    } // Terminal restored here
+    if let Some(sig) = signal::received() { let _ = writeln!(std::io::stderr(), "Stopped by {}", signal::name(sig)); }
+}
+
+// Secs for a named preset, exiting with the known names if it isn't defined
+fn preset_duration(config: &Config, name: &str) -> u32 {
+    if let Some(secs) = config.preset(name) { return secs; }
+    let known: Vec<&str> = config.presets.iter().map(|(n, _)| n.as_str())
+        .chain(config.templates.iter().map(|(n, _)| n.as_str()))
+        .collect();
+    let known = if known.is_empty() { "(none)".to_string() } else { known.join(", ") };
+    eprintln!("Unknown preset '{}', defined presets: {}", name, known);
+    std::process::exit(1);
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+// Secs left until an --until time, exiting if it can't be counted down to
+fn until_duration(until: &cli::Until) -> u32 {
+    until.secs_from(unix_now()).unwrap_or_else(|e| {
+        eprintln!("--until {}", e);
+        std::process::exit(1);
+    })
+}
+
+// Secs left until a --cron line next runs, going by the wall clock read at
+// `launched` so the harness can jump ahead to it
+fn cron_duration(cron: &cron::Cron, launched: (i64, Instant)) -> u32 {
+    let now = launched.0 + clock::now().saturating_duration_since(launched.1).as_secs() as i64;
+    match cron.next_after(now) {
+        Ok(at) => u32::try_from(at - now).unwrap_or(u32::MAX),
+        Err(e) => {
+            eprintln!("--cron {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn send_ack(name: &str) {
+    if let Err(e) = ack::acknowledge(name) {
+        eprintln!("Couldn't acknowledge '{}': {}", name, e);
+        std::process::exit(1);
+    }
+}
+
+// Cycle through pomodoro rounds until the user quits or interrupts
+fn run_pomodoro(opts: &cli::PomodoroArgs, config: &Config) {
+    let secs = |s: u32| Duration::from_secs(s as u64);
+    let round = TimerPlan::pomodoro(secs(opts.work), secs(opts.short_break), secs(opts.long_break), opts.long_every)
+        .expect("parse_pomodoro rejects a zero long_every");
+
+    signal::register_exit_handlers();
+    let notify = opts.notify.unwrap_or(config.notify);
+    let quotes = load_quotes(opts.quotes.as_deref(), config);
+    let control = opts.control_fifo.as_deref().map(open_control);
+    let mut screen = Screen::new(config, alarm_for(config, &opts.alarm), opts.plain);
+    screen.quotes = RefCell::new(quotes);
+    if let Some(fifo) = &control { fifo.listen(screen.actions.clone()); }
+    screen.pause_reasons |= opts.pause_reasons;
+    screen.pause_on_suspend |= opts.pause_on_suspend;
+    screen.log &= !opts.no_log;
+    let pre_roll = Duration::from_secs(opts.pre_roll as u64);
+    let mut phases = round.phases.iter().enumerate().cycle().peekable();
+    let mut breaks_taken = 0;
+    let mut first = true;
+    while let Some((index, phase)) = phases.next() {
+        // Paused and quit like a phase, but kept out of the log, it isn't
+        // time worked or rested
+        if !std::mem::take(&mut first) && !pre_roll.is_zero() {
+            let pre_roll = phase.pre_roll(pre_roll);
+            let mut timer = Timer::new(pre_roll.duration);
+            let log = std::mem::replace(&mut screen.log, false);
+            screen.journal = false;
+            let finished = screen.run(&mut timer, Some(&pre_roll.label), None);
+            screen.log = log;
+            if !finished { return end_pomodoro(screen, &timer, &pre_roll.label); }
+        }
+        screen.record(TimerEvent::PhaseStarted { index, label: phase.label.clone() });
+        // Rounds alternate work and breaks, each break starting on the next activity
+        let on_break = index % 2 == 1;
+        let mut timer = Timer::new(phase.duration);
+        screen.journal = !on_break;
+        screen.strict = opts.strict_breaks && on_break;
+        if screen.strict {
+            signal::hold_interrupts(true);
+            if let Some(cmd) = &config.dim { run_quietly(cmd, &phase.label, phase.duration); }
+        }
+        let finished = screen.run(&mut timer, Some(&phase.label), on_break.then_some(breaks_taken));
+        if std::mem::take(&mut screen.strict) {
+            if let Some(cmd) = &config.undim { run_quietly(cmd, &phase.label, phase.duration); }
+            signal::hold_interrupts(false); // A Ctrl+C held back ends it now
+        }
+        if !finished || signal::should_exit() { return end_pomodoro(screen, &timer, &phase.label); }
+        screen.ring();
+        if config.reflection && !on_break {
+            if let Some(note) = screen.reflect(&phase.label) { screen.record(TimerEvent::Reflected { note }); }
+            if signal::should_exit() { return end_pomodoro(screen, &timer, &phase.label); }
+        }
+        screen.next_quote();
+        if on_break { breaks_taken += 1; }
+        if let (true, Some((_, next))) = (notify, phases.peek()) {
+            // Best effort, one missed transition isn't worth stopping the cycle for
+            let body = format!("{} done, {} next", phase.label, next.label);
+            let _ = notify::send_notification("timerterm", &body);
+        }
+    }
+}
+
+// A pomodoro.dim or undim command, waited for since the break hangs on it.
+// Best effort, the break goes ahead either way.
+fn run_quietly(cmd: &str, label: &str, duration: Duration) {
+    if let Ok(mut child) = hook::spawn(cmd, label, duration) { let _ = child.wait(); }
+}
+
+// Restore the terminal, then report a signal and list the notes taken with
+// i, each after the work block it came in and how far in
+fn end_pomodoro(screen: Screen, timer: &Timer, label: &str) {
+    let noted = screen.interruptions.take();
+    drop(screen);
+    if let Some(sig) = signal::received() { report_signal(sig, &Summary::from_timer(timer, Some(label))); }
+    if noted.is_empty() { return; }
+    println!("Interruptions:");
+    for (label, elapsed, note) in noted {
+        println!("  {} at {}  {}", label, render::format_duration(elapsed.as_secs()), note);
+    }
+}
+
+// Work and rest in turn, each in its own color and ending on its own sound,
+// then what was done. Quitting early still prints it.
+fn run_intervals(opts: &cli::IntervalArgs, config: &Config) {
+    let secs = |s: u32| Duration::from_secs(s as u64);
+    let plan = TimerPlan::intervals(secs(opts.work), secs(opts.rest), opts.rounds)
+        .expect("parse_intervals rejects zero rounds");
+
+    signal::register_exit_handlers();
+    let control = opts.control_fifo.as_deref().map(open_control);
+    let mut screen = Screen::new(config, alarm_for(config, &opts.alarm), opts.plain);
+    if let Some(fifo) = &control { fifo.listen(screen.actions.clone()); }
+    screen.pause_on_suspend |= opts.pause_on_suspend;
+    screen.log &= !opts.no_log;
+    let (mut worked, mut rested, mut rounds) = (Duration::ZERO, Duration::ZERO, 0);
+    let mut stopped = None;
+    for (index, phase) in plan.phases.iter().enumerate() {
+        let resting = index % 2 == 1;
+        let color = if resting { "green" } else { "red" };
+        screen.color = ansi::fg(color).filter(|_| !screen.line_mode);
+        screen.record(TimerEvent::PhaseStarted { index, label: phase.label.clone() });
+        let mut timer = Timer::new(phase.duration);
+        let finished = screen.run(&mut timer, Some(&phase.label), None);
+        if resting { rested += timer.elapsed() } else { worked += timer.elapsed() }
+        if !finished {
+            stopped = Some(Summary::from_timer(&timer, Some(&phase.label)));
             break;
         }
-        thread::sleep(Duration::from_millis(100));
+        if !resting { rounds += 1; }
+        // The chime sends you to rest, the alarm back to work and at the end
+        if resting || index + 1 == plan.phases.len() { screen.ring() } else { screen.chime() }
+    }
+    if stopped.is_none() && opts.notify.unwrap_or(config.notify) {
+        let _ = notify::send_notification("timerterm", &format!("Intervals done, {} rounds", rounds));
+    }
+    drop(screen); // Restore the terminal before reporting
+    if let (Some(sig), Some(summary)) = (signal::received(), &stopped) { return report_signal(sig, summary); }
+    println!("{} of {} rounds, {} worked, {} rested", rounds, opts.rounds,
+        render::format_duration(worked.as_secs()), render::format_duration(rested.as_secs()));
+}
+
+// A schedule file's segments back to back, each ending on its own sound if
+// it has one and starting its hook, then how far it got. Hooks are waited for
+// (or stopped, per --orphan-policy) once the terminal is back, so a failing
+// one can say so.
+fn run_schedule(opts: &cli::ScheduleArgs, config: &Config) {
+    let schedule = schedule::load(Path::new(&opts.path)).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+    if let Some(file) = schedule.segments.iter().filter_map(|s| s.sound.as_ref()).find(|f| !f.is_file()) {
+        eprintln!("Sound file {} not found", file.display());
+        std::process::exit(1);
+    }
+    let total = schedule.total();
+
+    signal::register_exit_handlers();
+    let mut screen = Screen::new(config, alarm_for(config, &opts.alarm), opts.plain);
+    screen.log &= !opts.no_log;
+    screen.timer_config.label = schedule.name.clone();
+    let (mut done, mut before, mut spent) = (0, Duration::ZERO, Duration::ZERO);
+    let mut hooks = Vec::new();
+    let mut stopped = None;
+    for (index, segment) in schedule.segments.iter().enumerate() {
+        screen.chain = Some((before, total));
+        screen.record(TimerEvent::PhaseStarted { index, label: segment.label.clone() });
+        let mut timer = Timer::new(segment.duration);
+        let finished = screen.run(&mut timer, Some(&segment.label), None);
+        spent += timer.elapsed();
+        if !finished {
+            stopped = Some(Summary::from_timer(&timer, Some(&segment.label)));
+            break;
+        }
+        done += 1;
+        before += segment.duration;
+        let sound = || match (&segment.sound, index + 1 == schedule.segments.len()) {
+            (Some(file), _) if !opts.alarm.silent => screen.play(file),
+            (_, true) => screen.ring(),
+            (_, false) => screen.chime(),
+        };
+        match &segment.alert {
+            // Only what the segment lists, in its order
+            Some(channels) => for channel in channels {
+                match channel {
+                    Channel::Sound => sound(),
+                    Channel::Bell if !opts.alarm.silent && !screen.muted.get() => screen.bell(),
+                    Channel::Desktop if opts.notify != Some(false) => {
+                        let _ = notify::send_notification("timerterm", &format!("{} done", segment.label));
+                    }
+                    Channel::Bell | Channel::Desktop => {}
+                }
+            },
+            None => sound(),
+        }
+        if let Some(cmd) = &segment.exec { hooks.push((&segment.label, hook::spawn(cmd, &segment.label, segment.duration))); }
+    }
+    if stopped.is_none() && opts.notify.unwrap_or(config.notify) {
+        let name = schedule.name.as_deref().unwrap_or("Schedule");
+        let _ = notify::send_notification("timerterm", &format!("{} done, {} segments", name, done));
+    }
+    drop(screen); // Restore the terminal before reporting
+
+    // After a signal it's time to go, not to wait on hooks
+    let policy = match (opts.orphan_policy, signal::received()) {
+        (OrphanPolicy::Wait, Some(_)) => OrphanPolicy::Kill,
+        (policy, _) => policy,
+    };
+    let mut failed = false;
+    for (label, hook) in hooks {
+        let status = hook.and_then(|mut child| match policy {
+            OrphanPolicy::Wait => child.wait().map(Some),
+            OrphanPolicy::Kill => hook::stop(&mut child),
+            OrphanPolicy::Detach => child.try_wait(),
+        });
+        failed |= match status {
+            Ok(Some(status)) if status.success() => false,
+            Ok(Some(status)) => { eprintln!("exec for '{}' failed: {}", label, status); true }
+            Ok(None) if policy == OrphanPolicy::Kill => { eprintln!("exec for '{}' was still running, killed it", label); false }
+            Ok(None) => false,
+            Err(e) => { eprintln!("Couldn't run exec for '{}': {}", label, e); true }
+        };
+    }
+    if let (Some(sig), Some(summary)) = (signal::received(), &stopped) { return report_signal(sig, summary); }
+    println!("{} of {} segments, {} of {}", done, schedule.segments.len(),
+        render::format_duration(spent.as_secs()), render::format_duration(total.as_secs()));
+    if failed { std::process::exit(1); }
+}
+
+// Both sides count down from one start, each paused on its own key, until
+// both reach zero or q. The chime marks the first one done, the alarm the
+// second. Then how each went and who won.
+fn run_duel(opts: &cli::DuelArgs, config: &Config) {
+    let duration = Duration::from_secs(opts.duration as u64);
+    signal::register_exit_handlers();
+    let mut timers = [Timer::new(duration), Timer::new(duration)];
+    let mut pauses = [0; 2];
+    let mut done: [Option<Instant>; 2] = [None; 2]; // When each side reached zero
+    let (start, end) = {
+        let screen = Screen::new(config, alarm_for(config, &opts.alarm), opts.plain);
+        for timer in &mut timers { timer.start(); }
+        let start = clock::now();
+        let mut last_drawn = None;
+        'duel: while !signal::should_exit() {
+            if screen.job_control(None) { last_drawn = None; }
+            if screen.resized() { last_drawn = None; }
+
+            for (i, timer) in timers.iter_mut().enumerate() {
+                if done[i].is_some() || !timer.is_finished() { continue; }
+                done[i] = Some(timer.deadline().unwrap_or_else(clock::now));
+                timer.pause();
+                if done.iter().all(Option::is_some) { screen.ring() } else { screen.chime() }
+            }
+            let next_tick = timers.iter().filter_map(Timer::next_tick).min();
+            let state: [(u64, bool, bool); 2] = std::array::from_fn(|i| {
+                (timers[i].remaining().as_millis().div_ceil(1000) as u64, timers[i].is_paused(), done[i].is_some())
+            });
+            if last_drawn != Some(state) {
+                let times = state.map(|(secs, _, _)| render::format_duration(secs));
+                let statuses: [String; 2] = std::array::from_fn(|i| match state[i] {
+                    (_, _, true) => format!("{} (DONE)", opts.names[i]),
+                    (_, true, _) => format!("{} (PAUSED)", opts.names[i]),
+                    _ => opts.names[i].clone(),
+                });
+                screen.show_duel([(&times[0], Some(&statuses[0])), (&times[1], Some(&statuses[1]))]);
+                last_drawn = Some(state);
+            }
+            if done.iter().all(Option::is_some) { break; }
+
+            let mut woken_by = screen.wait(next_tick);
+            while let Some(action) = woken_by.take().or_else(|| screen.keys.try_recv().ok()) {
+                // Space pauses whichever sides are running, or resumes both
+                let sides = match action {
+                    Action::ToggleSide(i) => vec![i],
+                    Action::TogglePause if timers.iter().zip(&done).any(|(t, d)| d.is_none() && !t.is_paused()) => {
+                        (0..2).filter(|&i| !timers[i].is_paused()).collect()
+                    }
+                    Action::TogglePause => vec![0, 1],
+                    Action::Quit => break 'duel,
+                    Action::Echoed => {
+                        screen.wipe();
+                        last_drawn = None;
+                        vec![]
+                    }
+                    Action::Advance(by) => {
+                        clock::advance(by);
+                        break;
+                    }
+                    _ => vec![],
+                };
+                for i in sides.into_iter().filter(|&i| done[i].is_none()) {
+                    if timers[i].is_paused() {
+                        timers[i].resume();
+                    } else {
+                        timers[i].pause();
+                        pauses[i] += 1;
+                    }
+                }
+            }
+        }
+        (start, clock::now())
+    }; // Terminal restored here
+
+    let sides: [duel::Side; 2] = std::array::from_fn(|i| {
+        let elapsed = timers[i].elapsed().min(duration);
+        let paused = done[i].unwrap_or(end).saturating_duration_since(start).saturating_sub(elapsed);
+        duel::Side { name: opts.names[i].clone(), elapsed, paused, pauses: pauses[i], finished: done[i].is_some() }
+    });
+    let verdict = duel::verdict(&sides);
+    if let Some(sig) = signal::received() {
+        let _ = writeln!(std::io::stderr(), "Stopped by {}: {}", signal::name(sig), verdict);
+        return;
+    }
+    if done.iter().all(Option::is_some) && opts.notify.unwrap_or(config.notify) {
+        let _ = notify::send_notification("timerterm", &format!("Duel over, {}", verdict));
+    }
+    let width = opts.names.iter().map(|name| text::display_width(name)).max().unwrap_or(0);
+    for side in &sides { println!("{}", side.report(duration, width)); }
+    println!("{}", verdict);
+}
+
+// Printed to stderr ignoring errors, after SIGHUP the terminal may be gone
+fn report_signal(sig: i32, summary: &Summary) {
+    let _ = writeln!(std::io::stderr(), "Stopped by {}: {}", signal::name(sig), summary);
+}
+
+// Falls back to 80x24 when stdout isn't a terminal
+fn terminal_size() -> (u16, u16) {
+    terminal::get_size().unwrap_or(terminal::FALLBACK_SIZE)
+}
+
+// Raw-mode alt screen (or line mode output) plus key input, held for a whole run.
+// Dropping it restores the terminal.
+struct Screen {
+    guard: terminal::TerminalGuard,
+    keys: Receiver<Action>,
+    actions: Sender<Action>, // Into `keys`, for a control FIFO
+    size: Cell<(u16, u16)>, // (cols, rows), updated on SIGWINCH
+    line_mode: bool,        // A plain line per update instead of frames
+    shown_notes: RefCell<Vec<String>>, // Line mode prints notes only when they change
+    tick: Duration,
+    power_save: PowerSave,
+    battery: Cell<Option<(Instant, bool)>>, // The last on-battery reading and when, for --power-save auto
+    adjust_step: u32,           // Secs per + or - press
+    color: Option<&'static str>,
+    layout: Layout,
+    distance: Option<Distance>, // Pace shown under a stopwatch
+    chain: Option<(Duration, Duration)>, // Segments before this one and all of them, for chained durations
+    pause_reasons: bool,        // Ask why on pause, logged with the pause
+    pause_on_suspend: bool,     // Ctrl+Z stops the clock too
+    log: bool,                  // Write events to the history log
+    events: Option<std::fs::File>, // --events-file, a JSON line per event
+    status_file: Option<PathBuf>,  // --status-file, rewritten on every redraw
+    serial: Option<Serial>,        // --serial, sent the time as it's shown
+    quotes: RefCell<Option<Quotes>>, // Shown under the status, 'n' cycles
+    world_clock: Vec<Zone>,     // Their clocks shown under the quote
+    activities: Vec<Activity>,  // Suggested on pomodoro breaks
+    journal: bool,              // i notes an interruption, pomodoro work blocks only
+    strict: bool,               // A --strict-breaks break, only running out ends it
+    escalate_after: Option<Duration>, // How long a held timer goes unacknowledged before escalating
+    escalate_exec: Option<String>,    // Run as it does
+    escalation: RefCell<Option<std::process::Child>>, // That escalate.exec, until it's reaped
+    interruptions: RefCell<Vec<(String, Duration, String)>>, // Noted so far: the label, elapsed and the note
+    timer_config: TimerConfig,  // The header and window title show its label
+    tag: Option<String>,        // The tmux session:window, logged with each start
+    dir: Option<String>,        // The project it runs in, logged with each start
+    estimate: Option<Duration>, // --estimate, logged with each start
+    alarm: sound::Alarm,
+    volume: Cell<u8>,           // The alarm's, moved by [ and ] for the rest of the run
+    muted: Cell<bool>,          // m silences the sounds and the bell until pressed again
+    volume_changed: Cell<Option<Instant>>, // When [ or ] was last pressed
+    morse: Morse,
+    keying: RefCell<Vec<thread::JoinHandle<()>>>, // Morse patterns being sent, finished before the terminal is restored
+    #[cfg(feature = "gpio")]
+    gpio: Option<(timeterm::gpio::Pin, Duration)>,
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        for keying in self.keying.take() { let _ = keying.join(); }
+    }
+}
+
+impl Screen {
+    // `plain` is --plain, for lines instead of frames
+    fn new(config: &Config, alarm: sound::Alarm, plain: bool) -> Self {
+        // Before the alt screen, so a failure is readable
+        #[cfg(feature = "gpio")]
+        let gpio = config.gpio_pin.map(|pin| match timeterm::gpio::Pin::open(pin) {
+            Ok(open) => (open, config.gpio_pulse),
+            Err(e) => {
+                eprintln!("Couldn't set up GPIO pin {}: {}", pin, e);
+                std::process::exit(1);
+            }
+        });
+        let world_clock = config.world_clock.iter().map(|name| Zone::load(name)).collect::<Result<Vec<_>, _>>().unwrap_or_else(|e| {
+            eprintln!("Couldn't set up the world clock: {}", e);
+            std::process::exit(1);
+        });
+        let term = std::env::var("TERM").ok();
+        // Tests read frames off a pipe, so the harness goes by TERM alone
+        let piped = terminal::is_piped() && !clock::is_fake();
+        let sizeless = terminal::needs_line_mode(terminal::get_size(), term.as_deref());
+        let plain = plain || config.plain;
+        let line_mode = plain || piped || sizeless;
+        let guard = if line_mode {
+            // Asked for, or what a pipe wants anyway. Only a terminal without
+            // a size is worth explaining.
+            if !plain && !piped {
+                let (cols, rows) = terminal::FALLBACK_SIZE;
+                eprintln!("timerterm: terminal size unknown and TERM is {}, assuming {}x{} and printing a line per update",
+                    term.map_or("unset".to_string(), |t| format!("'{}'", t)), cols, rows);
+            }
+            terminal::TerminalGuard::plain()
+        } else {
+            terminal::TerminalGuard::new().expect("Failed to set up terminal")
+        };
+        signal::register_job_control_handlers();
+        signal::register_resize_handler();
+        let color = config.color.as_deref().and_then(ansi::fg).filter(|_| !line_mode);
+        let (actions, keys) = input::spawn_reader(guard.is_cooked());
+        Screen {
+            guard,
+            keys,
+            actions,
+            size: Cell::new(terminal_size()),
+            line_mode,
+            shown_notes: RefCell::new(Vec::new()),
+            tick: config.tick,
+            power_save: config.power_save,
+            battery: Cell::new(None),
+            adjust_step: config.adjust_step,
+            color,
+            layout: config.layout,
+            distance: None,
+            chain: None,
+            pause_reasons: config.pause_reasons,
+            pause_on_suspend: config.pause_on_suspend,
+            log: config.log,
+            events: None,
+            status_file: None,
+            serial: None,
+            quotes: RefCell::new(None),
+            world_clock,
+            activities: config.break_activities.clone(),
+            journal: false,
+            strict: false,
+            escalate_after: config.escalate_after.map(|secs| Duration::from_secs(secs as u64)),
+            escalate_exec: config.escalate_exec.clone(),
+            escalation: RefCell::new(None),
+            interruptions: RefCell::new(Vec::new()),
+            timer_config: TimerConfig::default(),
+            tag: autolabel::tmux_workspace(),
+            dir: git::project_dir(),
+            estimate: None,
+            volume: Cell::new(alarm.volume),
+            muted: Cell::new(false),
+            volume_changed: Cell::new(None),
+            alarm,
+            morse: config.morse.clone(),
+            keying: RefCell::new(Vec::new()),
+            #[cfg(feature = "gpio")]
+            gpio,
+        }
+    }
+
+    // Ctrl+Z gives the terminal back and stops for real, `fg` takes it over
+    // again. With pause_on_suspend a running timer doesn't count the time
+    // stopped. True when the screen needs redrawing.
+    fn job_control(&self, timer: Option<&mut Timer>) -> bool {
+        if !signal::take_suspend() { return signal::take_continued(); }
+        let mut timer = timer.filter(|t| self.pause_on_suspend && !t.is_paused());
+        if let Some(timer) = &mut timer {
+            timer.pause();
+            self.record(TimerEvent::Paused { elapsed: timer.elapsed(), reason: Some("suspended".to_string()) });
+        }
+        self.guard.suspend();
+        signal::stop_self();
+        self.guard.resume();
+        self.size.set(terminal_size()); // It may have changed while stopped
+        if let Some(timer) = timer {
+            timer.resume();
+            self.record(TimerEvent::Resumed { elapsed: timer.elapsed() });
+        }
+        signal::take_continued();
+        true
+    }
+
+    // After SIGWINCH, pick up the new size and clear what the old layout left.
+    // True when the screen needs redrawing.
+    fn resized(&self) -> bool {
+        if !signal::take_resized() { return false; }
+        self.size.set(terminal_size());
+        if self.line_mode { return false; }
+        self.wipe();
+        true
+    }
+
+    // Clear what a cooked terminal echoed, the caller redraws
+    fn wipe(&self) {
+        if self.line_mode { return; }
+        let mut out = std::io::stdout();
+        let _ = write!(out, "{}", ansi::CLEAR_SCREEN);
+    }
+
+    // Big secs and detail line for the timer as it stands, plus the time left
+    // of a countdown. One clock reading for everything, so the numbers agree.
+    fn readout(&self, timer: &Timer) -> (u64, Option<String>, Option<Duration>) {
+        let (remaining, elapsed) = match timer.direction() {
+            Direction::Down => {
+                let left = timer.remaining();
+                (Some(left), timer.duration().saturating_sub(left))
+            }
+            Direction::Up => (None, timer.elapsed()),
+        };
+        // Round remaining up so "0:00" only shows once the countdown has actually finished
+        let remaining_secs = remaining.map(|left| left.as_millis().div_ceil(1000) as u64);
+        let (secs, detail) = self.layout.readout(remaining_secs, elapsed.as_secs());
+        let detail = detail.or_else(|| self.distance.map(|d| d.pace_text(elapsed)))
+            .or_else(|| self.chain.map(|(before, total)| {
+                format!("{} of {} overall", render::format_duration((before + elapsed).as_secs()), render::format_duration(total.as_secs()))
+            }));
+        (secs, detail, remaining)
+    }
+
+    // The last frame as text, without the pause marker, for --screenshot
+    fn final_frame(&self, timer: &Timer, label: Option<&str>) -> (String, String) {
+        let (secs, detail, _) = self.readout(timer);
+        let (cols, rows) = self.size.get();
+        let text = render::plain_frame(secs, detail.as_deref(), cols, rows, label);
+        (text, render::format_duration(secs))
+    }
+
+    fn next_quote(&self) {
+        if let Some(quotes) = self.quotes.borrow_mut().as_mut() { quotes.advance(); }
+    }
+
+    // What goes under the status: on a break the activity and its countdown,
+    // then the quote, then the world clock. Wrapped narrower than the screen so it reads as a block
+    // rather than a banner.
+    fn notes(&self, timer: &Timer, break_start: Option<usize>, cols: u16) -> Vec<String> {
+        let width = (cols as usize).saturating_sub(4).clamp(1, 60);
+        let mut notes = Vec::new();
+        let elapsed = timer.elapsed().as_secs();
+        if let Some((activity, left)) = break_start.and_then(|start| activity::current(&self.activities, start, elapsed)) {
+            let line = match left {
+                Some(left) => format!("{} ({})", activity.text, render::format_duration(left)),
+                None => activity.text.clone(),
+            };
+            notes.extend(text::wrap(&line, width));
+        }
+        if let Some(quotes) = self.quotes.borrow().as_ref() {
+            if !notes.is_empty() { notes.push(String::new()); }
+            notes.extend(text::wrap(quotes.current(), width));
+        }
+        if !self.world_clock.is_empty() {
+            if !notes.is_empty() { notes.push(String::new()); }
+            notes.push(zone::footer(&self.world_clock, unix_now(), cols as usize));
+        }
+        if let Some(note) = self.volume_note() {
+            if !notes.is_empty() { notes.push(String::new()); }
+            notes.push(note);
+        }
+        notes
+    }
+
+    // Muted for as long as it is, the volume just after it's changed
+    fn volume_note(&self) -> Option<String> {
+        if self.muted.get() { return Some("Muted, m to unmute".to_string()); }
+        let shown = self.volume_changed.get().is_some_and(|at| clock::now().saturating_duration_since(at) < VOLUME_SHOWN);
+        shown.then(|| format!("Volume {}%", self.volume.get()))
+    }
+
+    // m and [ ], on any screen that rings
+    fn adjust_sound(&self, action: Action) {
+        match action {
+            Action::Mute => self.muted.set(!self.muted.get()),
+            Action::Volume(steps) => {
+                let volume = self.volume.get() as i32 + steps as i32 * VOLUME_STEP as i32;
+                self.volume.set(volume.clamp(0, 100) as u8);
+                self.muted.set(false);
+                self.volume_changed.set(Some(clock::now()));
+            }
+            _ => {}
+        }
+    }
+
+    // How long until what can only be polled is looked at again
+    fn poll(&self) -> Duration {
+        self.power_save.tick(self.tick, || {
+            let now = Instant::now();
+            match self.battery.get() {
+                Some((at, on_battery)) if now.duration_since(at) < power::RECHECK => on_battery,
+                _ => {
+                    let on_battery = power::on_battery();
+                    self.battery.set(Some((now, on_battery)));
+                    on_battery
+                }
+            }
+        })
+    }
+
+    // Sleep until `at` (forever for None), or until a key press or signal
+    // cuts it short and is returned
+    fn wait(&self, at: Option<Instant>) -> Option<Action> {
+        // A fake clock only moves on a harness command, so wait for the next one
+        if clock::is_fake() { return self.keys.recv().ok(); }
+        let timeout = at.map_or(Duration::MAX, |at| at.saturating_duration_since(Instant::now()));
+        match self.keys.recv_timeout(timeout) {
+            Ok(action) => Some(action),
+            Err(RecvTimeoutError::Timeout) => None,
+            // Nothing left to send keys or wakes, so fall back to polling
+            Err(RecvTimeoutError::Disconnected) => {
+                thread::sleep(timeout.min(self.poll()));
+                None
+            }
+        }
+    }
+
+    fn record(&self, event: TimerEvent) {
+        if self.log { let _ = log::write_event(&event); }
+        if let (Some(mut file), Ok(text)) = (self.events.as_ref(), json::to_string(&event)) { let _ = writeln!(file, "{}", text); }
+    }
+
+    // Best effort once running, the path was checked before the screen came up
+    fn update_status(&self, timer: &Timer) {
+        let status = Status::from_timer(timer, self.timer_config.display_name());
+        if let Some(path) = &self.status_file { let _ = write_status(path, &status); }
+    }
+
+    fn record_start(&self, timer: &Timer) {
+        self.record(TimerEvent::Started { duration: timer.duration(), elapsed: timer.head_start(), estimate: self.estimate, label: self.timer_config.label.clone(), tag: self.tag.clone(), dir: self.dir.clone() });
+    }
+
+    // Log a note typed after i, and keep it for the end of the session
+    fn interrupted(&self, timer: &Timer, label: Option<&str>, note: String) {
+        self.record(TimerEvent::Interrupted { elapsed: timer.elapsed(), note: note.clone() });
+        self.interruptions.borrow_mut().push((label.unwrap_or_default().to_string(), timer.elapsed(), note));
+    }
+
+    // Drive the display until the timer finishes (true) or the user quits or
+    // interrupts (false). `label` is shown under the time, e.g. the phase name,
+    // and `break_start` is which activity a pomodoro break starts on.
+    // The timer is left frozen when this returns.
+    fn run(&self, timer: &mut Timer, label: Option<&str>, break_start: Option<usize>) -> bool {
+        if let Some(title) = self.timer_config.window_title() { self.guard.set_title(&title); }
+        timer.start();
+        self.record_start(timer);
+        let mut last_drawn = None;
+        // While the pause menu is up the Paused event waits for its reason
+        let mut asking = false;
+        let unanswered = |asking: &mut bool, timer: &Timer| {
+            if std::mem::take(asking) { self.record(TimerEvent::Paused { elapsed: timer.elapsed(), reason: None }); }
+        };
+        // While an interruption note is typed the keys are text, the clock keeps going
+        let mut note: Option<input::LineEdit> = None;
+        let finished = 'main: loop {
+            if signal::should_exit() { break false; }
+            if self.job_control(Some(&mut *timer)) { last_drawn = None; }
+            if self.resized() { last_drawn = None; }
+
+            // Taken before the readout, so a second ticking over in between
+            // wakes the loop at once rather than a second late
+            let next_tick = timer.next_tick();
+            let (secs, detail, remaining) = self.readout(timer);
+            // Only redraw when the displayed seconds, pause state or notes change
+            let typed = note.as_ref().map(|line| line.text.clone());
+            let state = (secs, detail, timer.is_paused(), asking, self.notes(timer, break_start, self.size.get().0), typed);
+            if last_drawn.as_ref() != Some(&state) {
+                let mut status = match (label, timer.is_paused()) {
+                    (Some(label), true) => Some(format!("{} (PAUSED)", label)),
+                    (None, true) => Some("PAUSED".to_string()),
+                    (label, false) => label.map(str::to_string),
+                };
+                if asking { status = status.map(|s| format!("{}  {}", s, PAUSE_MENU)); }
+                if let Some(typed) = &state.5 {
+                    status = Some(match status {
+                        Some(s) => format!("{}  Interruption: {}_", s, typed),
+                        None => format!("Interruption: {}_", typed),
+                    });
+                }
+                self.show(secs, state.1.as_deref(), &state.4, status.as_deref());
+                self.update_status(timer);
+                if let Some(serial) = &self.serial { serial.show(secs); }
+                last_drawn = Some(state);
+            }
+
+            if remaining.is_some_and(|left| left.is_zero()) { break true; }
+
+            // Keys are handled after the frame they were pressed on is up
+            let mut woken_by = self.wait(next_tick);
+            while let Some(action) = woken_by.take().or_else(|| self.keys.try_recv().ok()) {
+                let step = self.adjust_step as i64;
+                // The + and - keys are adjustments by the configured step
+                let action = match action {
+                    Action::AddTime => Action::Adjust(step),
+                    Action::RemoveTime => Action::Adjust(-step),
+                    // m is a menu key too, and the menu comes first
+                    Action::Mute if asking => Action::Reason("meeting"),
+                    // Nothing a strict break can be cut short with
+                    Action::TogglePause | Action::Pause | Action::Restart | Action::Adjust(_) | Action::Quit if self.strict => continue,
+                    other => other,
+                };
+                match action {
+                    Action::Reason(reason) if asking => {
+                        asking = false;
+                        self.record(TimerEvent::Paused { elapsed: timer.elapsed(), reason: Some(reason.to_string()) });
+                    }
+                    Action::Reason(_) => {}
+                    Action::TogglePause | Action::Resume if timer.is_paused() => {
+                        unanswered(&mut asking, timer);
+                        timer.resume();
+                        self.record(TimerEvent::Resumed { elapsed: timer.elapsed() });
+                    }
+                    Action::TogglePause | Action::Pause if !timer.is_paused() => {
+                        timer.pause();
+                        asking = self.pause_reasons;
+                        if !asking { self.record(TimerEvent::Paused { elapsed: timer.elapsed(), reason: None }); }
+                    }
+                    Action::TogglePause | Action::Pause | Action::Resume => {}
+                    Action::Restart => {
+                        unanswered(&mut asking, timer);
+                        timer.restart();
+                        self.record_start(timer);
+                    }
+                    Action::Adjust(secs) if timer.direction() == Direction::Down => {
+                        timer.adjust(secs);
+                        self.record(TimerEvent::Adjusted { elapsed: timer.elapsed(), duration: timer.duration() });
+                    }
+                    Action::Adjust(_) | Action::AddTime | Action::RemoveTime => {}
+                    Action::ToggleSide(_) => {} // Only a duel has sides
+                    Action::Interrupt if self.journal && note.is_none() => {
+                        note = Some(input::LineEdit::default());
+                        input::set_typing(true);
+                    }
+                    Action::Interrupt | Action::Kill => {}
+                    Action::Mute | Action::Volume(_) => self.adjust_sound(action),
+                    // Enter keeps the note unless it's empty, Esc drops it
+                    Action::Typed(byte) => match note.as_mut().map(|line| line.feed(byte)) {
+                        None | Some(Edit::Editing) => {}
+                        Some(edit) => {
+                            note = None;
+                            input::set_typing(false);
+                            if let Edit::Done(text) = edit {
+                                if !text.is_empty() { self.interrupted(timer, label, text); }
+                            }
+                        }
+                    },
+                    Action::NextQuote => {
+                        self.next_quote();
+                        last_drawn = None;
+                    }
+                    Action::Quit => break 'main false,
+                    Action::Wake => {} // Signal flags are checked every time round
+                    Action::Echoed => {
+                        self.wipe();
+                        last_drawn = None;
+                    }
+                    // One step at a time, so a test sees a frame after each
+                    Action::Advance(by) => {
+                        clock::advance(by);
+                        break;
+                    }
+                }
+            }
+        };
+        unanswered(&mut asking, timer);
+        if note.is_some() { input::set_typing(false); } // A note not finished is dropped
+        self.update_status(timer); // Before the freeze, that isn't a pause
+        timer.pause();
+        self.record(match finished {
+            true => TimerEvent::Finished { elapsed: timer.elapsed() },
+            false => TimerEvent::Cancelled { elapsed: timer.elapsed() },
+        });
+        finished
+    }
+
+    // The --keypad screen: digits typed in fill the time from the right,
+    // Backspace takes the last one off and Enter starts it. None when quit
+    // with Esc or q first.
+    fn keypad(&self) -> Option<u32> {
+        if let Some(title) = self.timer_config.window_title() { self.guard.set_title(&title); }
+        let mut digits = String::new();
+        let mut drawn = None;
+        input::set_typing(true);
+        let keyed = loop {
+            if signal::should_exit() { break None; }
+            if self.job_control(None) { drawn = None; }
+            if self.resized() { drawn = None; }
+            let secs = cli::keypad_duration(&digits);
+            if drawn != Some(secs) {
+                self.show_in(self.color, &render::format_duration(secs as u64), None, &[], Some(KEYPAD_PROMPT));
+                drawn = Some(secs);
+            }
+            match self.wait(None) {
+                // Six digits is 99:99:99, and leading zeros would only use them up
+                Some(Action::Typed(byte @ b'0'..=b'9')) if digits.len() < 6 && !(digits.is_empty() && byte == b'0') => {
+                    digits.push(byte as char);
+                }
+                Some(Action::Typed(0x7f | 0x08)) => { digits.pop(); }
+                Some(Action::Typed(b'\r' | b'\n')) if secs > 0 => break Some(secs),
+                Some(Action::Typed(0x1b | b'q' | b'Q') | Action::Quit) => break None,
+                Some(Action::Advance(by)) => clock::advance(by),
+                Some(Action::Echoed) => {
+                    self.wipe();
+                    drawn = None;
+                }
+                _ => {}
+            }
+        };
+        input::set_typing(false);
+        keyed
+    }
+
+    // Ask what got done in the work block `label` just ended, under its
+    // 0:00. None when skipped with Esc or an empty Enter.
+    fn reflect(&self, label: &str) -> Option<String> {
+        let mut line = input::LineEdit::default();
+        let mut drawn = None;
+        input::set_typing(true);
+        let answer = loop {
+            if signal::should_exit() { break None; }
+            if self.job_control(None) { drawn = None; }
+            if self.resized() { drawn = None; }
+            if drawn.as_ref() != Some(&line.text) {
+                let status = format!("{} done. {} {}_", label, REFLECTION_PROMPT, line.text);
+                self.show_in(self.color, &render::format_duration(0), None, &[], Some(&status));
+                drawn = Some(line.text.clone());
+            }
+            match self.wait(None) {
+                Some(Action::Typed(byte)) => match line.feed(byte) {
+                    Edit::Editing => {}
+                    Edit::Done(text) => break Some(text).filter(|text| !text.is_empty()),
+                    Edit::Cancelled => break None,
+                },
+                Some(Action::Advance(by)) => clock::advance(by),
+                Some(Action::Echoed) => {
+                    self.wipe();
+                    drawn = None;
+                }
+                _ => {}
+            }
+        };
+        input::set_typing(false);
+        answer
+    }
+
+    // Show the unstarted timer with the trigger's prompt until the trigger
+    // goes off (true), or the user quits or interrupts (false)
+    fn arm(&self, timer: &Timer, trigger: &Trigger) -> bool {
+        if let Some(title) = self.timer_config.window_title() { self.guard.set_title(&title); }
+        let prompt = trigger.prompt();
+        let mut drawn = false;
+        while !signal::should_exit() {
+            if self.job_control(None) { drawn = false; }
+            if self.resized() { drawn = false; }
+            if !drawn {
+                let (secs, detail, _) = self.readout(timer);
+                self.show(secs, detail.as_deref(), &[], Some(&prompt));
+                self.update_status(timer);
+                drawn = true;
+            }
+            if trigger.fired() { return true; }
+            // A file can only be polled
+            match self.wait(Some(clock::now() + self.poll())) {
+                Some(Action::TogglePause | Action::Resume) if *trigger == Trigger::Key => return true,
+                Some(Action::Quit) => return false,
+                Some(Action::Advance(by)) => clock::advance(by),
+                Some(Action::Echoed) => {
+                    self.wipe();
+                    drawn = false;
+                }
+                _ => {}
+            }
+        }
+        false
+    }
+
+    // Hold the finished screen until a key press, `timerterm ack <name>` or an
+    // interrupt. With `nag` the alarm rings again every NAG_INTERVAL, and with
+    // the instant the countdown hit zero (--overtime) the time since counts up
+    // in red. Held past escalate.after, it escalates once. Returns the time
+    // since zero, zero without it.
+    fn hold(&self, name: &str, duration: Duration, nag: bool, notify: bool, zero: Option<Instant>) -> Duration {
+        let over = || zero.map_or(Duration::ZERO, |zero| clock::now().saturating_duration_since(zero));
+        let status = match zero {
+            Some(_) => format!("OVERTIME - press q or run: timerterm ack {}", name),
+            None => format!("DONE - press q or run: timerterm ack {}", name),
+        };
+        let red = ansi::fg("red").filter(|_| !self.line_mode);
+        let mut next_ring = clock::now() + NAG_INTERVAL;
+        let mut escalate_at = self.escalate_after.map(|after| clock::now() + after);
+        let mut drawn = None;
+        while !signal::should_exit() {
+            if self.job_control(None) { drawn = None; }
+            if self.resized() { drawn = None; }
+            let secs = over().as_secs();
+            let notes: Vec<String> = self.volume_note().into_iter().collect();
+            if drawn.as_ref() != Some(&(secs, notes.clone())) {
+                match zero {
+                    Some(_) => self.show_in(red, &render::overtime(secs), None, &notes, Some(&status)),
+                    None => self.show_in(self.color, "0:00", None, &notes, Some(&status)),
+                }
+                drawn = Some((secs, notes));
+            }
+            if ack::take(name) { break; }
+            // Reaped as soon as it's done, not left a zombie for the rest of the hold
+            let reaped = self.escalation.borrow_mut().as_mut().is_some_and(|child| !matches!(child.try_wait(), Ok(None)));
+            if reaped { self.escalation.take(); }
+            if escalate_at.is_some_and(|at| clock::now() >= at) {
+                escalate_at = None;
+                self.escalate(name, duration, notify);
+                next_ring = clock::now() + NAG_INTERVAL;
+            }
+            if nag && clock::now() >= next_ring {
+                self.ring();
+                next_ring += NAG_INTERVAL;
+            }
+            // The ack marker can only be polled
+            let mut check_at = clock::now() + self.poll();
+            if nag { check_at = check_at.min(next_ring); }
+            if let Some(at) = escalate_at { check_at = check_at.min(at); }
+            match self.wait(Some(check_at)) {
+                Some(Action::Advance(by)) => clock::advance(by),
+                Some(Action::Echoed) => {
+                    self.wipe();
+                    drawn = None;
+                }
+                // Muting the nagging isn't acknowledging it
+                Some(action @ (Action::Mute | Action::Volume(_))) => self.adjust_sound(action),
+                Some(Action::Wake) | None => {}
+                Some(_) => break,
+            }
+        }
+        // Still running once dismissed, it's waited for like a segment's exec,
+        // or stopped with whatever it started after a signal
+        if let Some(mut child) = self.escalation.take() {
+            let _ = if signal::should_exit() { hook::stop(&mut child) } else { child.wait().map(Some) };
+        }
+        over()
+    }
+
+    // Nobody came: the alarm once at full volume (unless muted), a second
+    // notification, and escalate.exec started. Best effort, all of it.
+    fn escalate(&self, name: &str, duration: Duration, notify: bool) {
+        let volume = self.volume.replace(100);
+        self.ring();
+        self.volume.set(volume);
+        if notify {
+            let body = format!("Still waiting to be acknowledged, run: timerterm ack {}", name);
+            let _ = notify::send_notification("timerterm", &body);
+        }
+        if let Some(cmd) = &self.escalate_exec { *self.escalation.borrow_mut() = hook::spawn(cmd, name, duration).ok(); }
+    }
+
+    // Keep the finished screen up while the --exec command runs, with how long
+    // it's been going drawn big over the last lines it printed. k, or an
+    // interrupt, stops it and whatever it started. Line mode prints each
+    // line once as it comes instead.
+    fn supervise(&self, cmd: &str, name: &str, duration: Duration) -> std::io::Result<Option<std::process::ExitStatus>> {
+        let (mut child, output) = hook::watch(cmd, name, duration)?;
+        let status = format!("Running {} - press k to kill it", cmd);
+        let start = clock::now();
+        let (mut drawn, mut seen) = (None, 0);
+        loop {
+            if signal::should_exit() { return hook::stop(&mut child); }
+            let done = child.try_wait()?;
+            if done.is_some() { output.settle(Duration::from_millis(200)); }
+            if self.job_control(None) { drawn = None; }
+            if self.resized() { drawn = None; }
+            let time = render::format_duration(clock::now().saturating_duration_since(start).as_secs());
+            let (new, total) = output.since(seen);
+            if drawn.as_ref() != Some(&time) || total != seen {
+                if self.line_mode {
+                    if drawn.as_ref() != Some(&time) { self.show_in(self.color, &time, None, &[], Some(&status)); }
+                    let mut out = std::io::stdout();
+                    for line in &new { let _ = writeln!(out, "  {}", line); }
+                } else {
+                    let (cols, rows) = self.size.get();
+                    self.show_in(self.color, &time, None, &output.last(render::note_room(&time, None, cols, rows)), Some(&status));
+                }
+                drawn = Some(time);
+                seen = total;
+            }
+            if done.is_some() { return Ok(done); }
+            // The command runs on real time whatever the clock, so poll it
+            match self.keys.recv_timeout(self.poll()) {
+                Ok(Action::Kill) => return hook::stop(&mut child),
+                Ok(Action::Advance(by)) => clock::advance(by),
+                Ok(Action::Echoed) => {
+                    self.wipe();
+                    drawn = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Draw a frame, or in line mode print it as a line with any new notes under it
+    fn show(&self, secs: u64, detail: Option<&str>, notes: &[String], status: Option<&str>) {
+        self.show_in(self.color, &render::format_duration(secs), detail, notes, status);
+    }
+
+    // show for a formatted `time`, drawn in `color` rather than the configured one
+    fn show_in(&self, color: Option<&str>, time: &str, detail: Option<&str>, notes: &[String], status: Option<&str>) {
+        let mut out = std::io::stdout();
+        let header = self.timer_config.label.as_deref();
+        if self.line_mode {
+            let line = render::line(time, detail, status);
+            let _ = match header {
+                Some(header) => writeln!(out, "{}  {}", header, line),
+                None => writeln!(out, "{}", line),
+            };
+            if *self.shown_notes.borrow() != notes {
+                for note in notes { let _ = writeln!(out, "  {}", note); }
+                *self.shown_notes.borrow_mut() = notes.to_vec();
+            }
+        } else {
+            let (cols, rows) = self.size.get();
+            let mut frame = render::noted_frame(time, detail, notes, cols, rows, status);
+            if let Some(header) = header { frame.push_str(&render::header(header, cols, rows)); }
+            let _ = match color {
+                Some(color) => write!(out, "{}{}{}", color, frame, ansi::RESET),
+                None => write!(out, "{}", frame),
+            };
+        }
+        let _ = out.flush();
+    }
+
+    // Both halves of a duel, or in line mode both on one line
+    fn show_duel(&self, sides: [(&str, Option<&str>); 2]) {
+        let mut out = std::io::stdout();
+        if self.line_mode {
+            let [left, right] = sides.map(|(time, status)| render::line(time, None, status));
+            let _ = writeln!(out, "{}  |  {}", left, right);
+        } else {
+            let (cols, rows) = self.size.get();
+            let frame = render::duel_frame(sides, cols, rows);
+            let _ = match self.color {
+                Some(color) => write!(out, "{}{}{}", color, frame, ansi::RESET),
+                None => write!(out, "{}", frame),
+            };
+        }
+        let _ = out.flush();
+    }
+
+    // A --repeat cycle is over: the chime sound, else the terminal bell.
+    // morse.chime keys a configured GPIO pin too.
+    fn chime(&self) {
+        let code = self.morse.chime.as_ref();
+        #[cfg(feature = "gpio")]
+        if let (Some((pin, _)), Some(code)) = (&self.gpio, code) { self.key_pin(pin, code); }
+        if self.muted.get() { return; }
+        if let Some(file) = &self.alarm.chime {
+            if sound::play(file, self.volume.get()).is_ok() { return; }
+        }
+        if self.alarm.bell { self.beep(code); }
+    }
+
+    // A schedule segment's own sound, the bell when it won't play
+    fn play(&self, file: &Path) {
+        if self.muted.get() { return; }
+        if sound::play(file, self.volume.get()).is_err() && self.alarm.bell { self.bell(); }
+    }
+
+    // Play the alarm sound, falling back to the terminal bell. A configured
+    // GPIO pin is pulsed either way, or keys morse.alarm, muted or not.
+    fn ring(&self) {
+        let code = self.morse.alarm.as_ref();
+        #[cfg(feature = "gpio")]
+        if let Some((pin, length)) = &self.gpio {
+            match code {
+                Some(code) => self.key_pin(pin, code),
+                None => pin.pulse(*length),
+            }
+        }
+        if self.muted.get() { return; }
+        if let Some(file) = &self.alarm.file {
+            if sound::play(file, self.volume.get()).is_ok() { return; }
+        }
+        if self.alarm.bell { self.beep(code); }
+    }
+
+    // The bell once, or `code` spelled out on it in the background
+    fn beep(&self, code: Option<&morse::Code>) {
+        let Some(code) = code else { return self.bell() };
+        let beeps = morse::beeps(code, self.morse.unit).into_iter().map(|at| (at, ())).collect();
+        self.send(move || morse::play(beeps, |()| {
+            let mut out = std::io::stdout();
+            let _ = write!(out, "{}", ansi::BELL);
+            let _ = out.flush();
+        }));
+    }
+
+    // Hold `pin` high for each part of `code`, leaving it low even if a
+    // signal cuts it short
+    #[cfg(feature = "gpio")]
+    fn key_pin(&self, pin: &timeterm::gpio::Pin, code: &morse::Code) {
+        let (pin, edges) = (pin.clone(), morse::edges(code, self.morse.unit));
+        self.send(move || {
+            morse::play(edges, |high| { let _ = pin.set(high); });
+            let _ = pin.set(false);
+        });
+    }
+
+    fn send(&self, pattern: impl FnOnce() + Send + 'static) {
+        let mut keying = self.keying.borrow_mut();
+        keying.retain(|keying| !keying.is_finished());
+        keying.push(thread::spawn(pattern));
+    }
+
+    fn bell(&self) {
+        let mut out = std::io::stdout();
+        let _ = write!(out, "{}", ansi::BELL);
+        let _ = out.flush();
+    }
+
+    // Whether `via` can stand in for a desktop notification. Stderr always
+    // can, it's printed once the terminal is back.
+    fn notify_via(&self, via: Fallback, body: &str) -> bool {
+        match via {
+            // Only a terminal reads escapes, a pipe would get them as text
+            Fallback::Osc if self.line_mode => false,
+            Fallback::Osc => {
+                let mut out = std::io::stdout();
+                write!(out, "{}", ansi::notify("timerterm", body)).and_then(|()| out.flush()).is_ok()
+            }
+            Fallback::Bell => { self.bell(); true }
+            Fallback::Stderr => true,
+        }
+    }
+}
+
+// Ask `question` on stderr, or with --no-confirm refuse and exit
+fn confirm(opts: &cli::TimerArgs, question: &str) -> bool {
+    if opts.no_confirm {
+        eprintln!("{} Not starting, --no-confirm is set (use --force to skip checks)", question);
+        std::process::exit(1);
+    }
+    prompt::confirm(question)
+}
+
+// The question to ask if a meeting starts before the timer would end.
+// An unreadable calendar is reported but doesn't block the timer.
+fn check_calendar(path: &str, duration: u32) -> Option<String> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("Couldn't read calendar {}: {}", path, e);
+            return None;
+        }
+    };
+    let now = unix_now();
+    let meetings = calendar::parse_ics(&text);
+    let meeting = calendar::next_conflict(&meetings, now, duration as i64)?;
+
+    let mins = (meeting.start - now + 59) / 60;
+    Some(format!(
+        "Meeting '{}' in {}m — start a {} timer anyway?",
+        meeting.summary, mins, render::format_duration(duration as u64),
+    ))
+}
+
+// Print one schema, or every schema one per line when no name is given
+fn print_schema(name: Option<&str>) {
+    let names = match name {
+        Some(name) => vec![name],
+        None => schema::NAMES.to_vec(),
+    };
+    for name in names {
+        match schema::schema_json(name) {
+            Some(text) => println!("{}", text),
+            None => {
+                eprintln!("Unknown schema '{}', expected one of: {}", name, schema::NAMES.join(", "));
+                std::process::exit(1);
+            }
+        }
     }
 }
@@ -0,0 +1,52 @@
+// src/media.rs
+// sound.pause_media: while a finished timer waits to be acknowledged
+// (--ack-required, --overtime), pause any MPRIS player that's playing and
+// start it again once dismissed. Goes through playerctl, and without it
+// there's nothing to pause. Players already paused stay paused.
+use std::process::{Command, Stdio};
+
+fn playerctl(args: &[&str]) -> Option<String> {
+    let out = Command::new("playerctl").args(args).stdin(Stdio::null()).stderr(Stdio::null()).output().ok()?;
+    out.status.success().then(|| String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+// Of the players `playerctl -l` listed, those `status` says are playing
+pub fn playing(list: &str, status: impl Fn(&str) -> Option<String>) -> Vec<String> {
+    list.lines()
+        .map(str::trim)
+        .filter(|player| !player.is_empty())
+        .filter(|player| status(player).is_some_and(|s| s.trim() == "Playing"))
+        .map(str::to_string)
+        .collect()
+}
+
+// Pause each playing player, returning the ones paused
+pub fn pause() -> Vec<String> {
+    let list = playerctl(&["-l"]).unwrap_or_default();
+    let players = playing(&list, |player| playerctl(&["-p", player, "status"]));
+    players.into_iter().filter(|player| playerctl(&["-p", player, "pause"]).is_some()).collect()
+}
+
+pub fn resume(players: &[String]) {
+    for player in players {
+        let _ = playerctl(&["-p", player, "play"]);
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn playing_skips_paused_and_gone_players() {
+        let status = |player: &str| match player {
+            "spotify" => Some("Playing\n".to_string()),
+            "vlc" => Some("Paused\n".to_string()),
+            _ => None,
+        };
+        assert_eq!(playing("spotify\nvlc\nfirefox.instance42\n\n", status), vec!["spotify".to_string()]);
+        // Test: No players listed is nothing to pause
+        assert!(playing("", status).is_empty());
+    }
+}
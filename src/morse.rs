@@ -0,0 +1,116 @@
+// src/morse.rs
+// Alarms spelled out in Morse code, set per alert in the config file:
+//   [morse]
+//   alarm = "SOS"  # When a timer finishes
+//   chime = "E"    # Between segments, cycles and phases
+//   unit_ms = 100
+// A dot is one unit on and a dash three, with one unit off between the parts
+// of a letter, three between letters and seven between words. A GPIO pin is
+// held on for each part. The terminal bell can't be held, so it beeps once
+// for a dot and twice, a unit apart, for a dash.
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::signal;
+
+// (on, off) units for each part, the last one's off is 0
+pub type Code = Vec<(u32, u32)>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Morse {
+    pub alarm: Option<Code>,
+    pub chime: Option<Code>,
+    pub unit: Duration,
+}
+
+impl Default for Morse {
+    fn default() -> Self {
+        Morse { alarm: None, chime: None, unit: Duration::from_millis(100) }
+    }
+}
+
+// Letters and digits, in either case
+const TABLE: [(char, &str); 36] = [
+    ('A', ".-"), ('B', "-..."), ('C', "-.-."), ('D', "-.."), ('E', "."), ('F', "..-."),
+    ('G', "--."), ('H', "...."), ('I', ".."), ('J', ".---"), ('K', "-.-"), ('L', ".-.."),
+    ('M', "--"), ('N', "-."), ('O', "---"), ('P', ".--."), ('Q', "--.-"), ('R', ".-."),
+    ('S', "..."), ('T', "-"), ('U', "..-"), ('V', "...-"), ('W', ".--"), ('X', "-..-"),
+    ('Y', "-.--"), ('Z', "--.."), ('0', "-----"), ('1', ".----"), ('2', "..---"), ('3', "...--"),
+    ('4', "....-"), ('5', "....."), ('6', "-...."), ('7', "--..."), ('8', "---.."), ('9', "----."),
+];
+
+pub fn encode(text: &str) -> Result<Code, String> {
+    let mut code: Code = Vec::new();
+    for word in text.split_whitespace() {
+        if let Some(last) = code.last_mut() { last.1 = 7; }
+        for c in word.chars() {
+            let (_, dots) = TABLE.iter().find(|(letter, _)| *letter == c.to_ascii_uppercase())
+                .ok_or_else(|| format!("no Morse code for '{}'", c))?;
+            if let Some(last) = code.last_mut() { last.1 = last.1.max(3); }
+            code.extend(dots.chars().map(|d| (if d == '-' { 3 } else { 1 }, 1)));
+            if let Some(last) = code.last_mut() { last.1 = 0; }
+        }
+    }
+    if code.is_empty() { return Err("nothing to spell out".to_string()); }
+    Ok(code)
+}
+
+// When a pin goes on and off, from the start
+pub fn edges(code: &[(u32, u32)], unit: Duration) -> Vec<(Duration, bool)> {
+    let mut at = Duration::ZERO;
+    let mut edges = Vec::new();
+    for &(on, off) in code {
+        edges.push((at, true));
+        at += unit * on;
+        edges.push((at, false));
+        at += unit * off;
+    }
+    edges
+}
+
+// When the bell beeps, from the start
+pub fn beeps(code: &[(u32, u32)], unit: Duration) -> Vec<Duration> {
+    edges(code, unit).chunks(2).flat_map(|part| {
+        let (start, end) = (part[0].0, part[1].0);
+        let dash = end - start > unit;
+        std::iter::once(start).chain(dash.then_some(start + unit))
+    }).collect()
+}
+
+// Do each step at its time from now, stopping early at a signal so the
+// alert doesn't hold up the exit
+pub fn play<T>(steps: Vec<(Duration, T)>, mut act: impl FnMut(T)) {
+    let start = Instant::now();
+    for (at, step) in steps {
+        while let Some(left) = (start + at).checked_duration_since(Instant::now()).filter(|d| !d.is_zero()) {
+            if signal::should_exit() { return; }
+            thread::sleep(left.min(Duration::from_millis(50)));
+        }
+        act(step);
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_spaces_parts_letters_and_words() {
+        // Test: 1 unit inside a letter, 3 between letters, 7 between words
+        assert_eq!(encode("sos").unwrap(), vec![(1, 1), (1, 1), (1, 3), (3, 1), (3, 1), (3, 3), (1, 1), (1, 1), (1, 0)]);
+        assert_eq!(encode("E  T").unwrap(), vec![(1, 7), (3, 0)]);
+        assert_eq!(encode("5!"), Err("no Morse code for '!'".to_string()));
+        assert!(encode(" ").is_err());
+    }
+
+    #[test]
+    fn pins_hold_and_bells_double_a_dash() {
+        let unit = Duration::from_millis(100);
+        let ms = Duration::from_millis;
+        let code = encode("ET").unwrap();
+        assert_eq!(edges(&code, unit), vec![(ms(0), true), (ms(100), false), (ms(400), true), (ms(700), false)]);
+        // Test: Two beeps a unit apart for the dash
+        assert_eq!(beeps(&code, unit), vec![ms(0), ms(400), ms(500)]);
+    }
+}
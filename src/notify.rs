@@ -0,0 +1,116 @@
+// src/notify.rs
+// Desktop notifications by shelling out: osascript on macOS, a PowerShell
+// toast on Windows, notify-send elsewhere. When that fails, notify.fallback
+// lists what to try instead, in order: "osc, bell, stderr".
+use std::io;
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fallback {
+    Osc,    // OSC 777, which foot, WezTerm and others turn into a notification
+    Bell,   // The terminal bell
+    Stderr, // A line on stderr once the terminal is restored
+}
+
+impl Fallback {
+    pub const NAMES: [&'static str; 3] = ["osc", "bell", "stderr"];
+
+    pub fn parse(name: &str) -> Option<Fallback> {
+        match name {
+            "osc" => Some(Fallback::Osc),
+            "bell" => Some(Fallback::Bell),
+            "stderr" => Some(Fallback::Stderr),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Fallback::Osc => "osc",
+            Fallback::Bell => "bell",
+            Fallback::Stderr => "stderr",
+        }
+    }
+}
+
+pub fn send_notification(title: &str, body: &str) -> io::Result<()> {
+    let (program, args) = command(title, body);
+    run_quiet(program, &args)
+}
+
+#[cfg(target_os = "macos")]
+fn command(title: &str, body: &str) -> (&'static str, Vec<String>) {
+    // AppleScript string literals only need quotes and backslashes escaped
+    let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+    let script = format!("display notification {} with title {}", quote(body), quote(title));
+    ("osascript", vec!["-e".to_string(), script])
+}
+
+#[cfg(windows)]
+fn command(title: &str, body: &str) -> (&'static str, Vec<String>) {
+    ("powershell", vec!["-NoProfile".to_string(), "-NonInteractive".to_string(), "-Command".to_string(), toast_script(title, body)])
+}
+
+// A two-line toast from the WinRT notification API. The text goes in through
+// CreateTextNode so it needn't be XML escaped, only quoted for PowerShell.
+#[cfg(any(windows, test))]
+fn toast_script(title: &str, body: &str) -> String {
+    let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+    [
+        "$n = [Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime]".to_string(),
+        "$t = $n::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02)".to_string(),
+        "$x = $t.GetElementsByTagName('text')".to_string(),
+        format!("$x.Item(0).AppendChild($t.CreateTextNode({})) > $null", quote(title)),
+        format!("$x.Item(1).AppendChild($t.CreateTextNode({})) > $null", quote(body)),
+        "$n::CreateToastNotifier('timerterm').Show([Windows.UI.Notifications.ToastNotification]::new($t))".to_string(),
+    ].join("; ")
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn command(title: &str, body: &str) -> (&'static str, Vec<String>) {
+    ("notify-send", vec!["--app-name=timerterm".to_string(), title.to_string(), body.to_string()])
+}
+
+// Run without touching our terminal, a missing program is just an error
+fn run_quiet(program: &str, args: &[String]) -> io::Result<()> {
+    let status = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    if status.success() { Ok(()) } else { Err(io::Error::other(format!("{} exited with {}", program, status))) }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_backend_is_an_error() {
+        // Test: No notifier installed shouldn't panic, just report
+        assert!(run_quiet("timeterm-no-such-notifier", &[]).is_err());
+    }
+
+    #[test]
+    fn toast_script_quotes_text() {
+        // Test: Single quotes are doubled, PowerShell's only escape inside '...'
+        let script = toast_script("Tea's up", "3:00");
+        assert!(script.contains("CreateTextNode('Tea''s up')"));
+        assert!(script.contains("CreateTextNode('3:00')"));
+    }
+
+    #[test]
+    fn fallback_names_round_trip() {
+        for name in Fallback::NAMES { assert_eq!(Fallback::parse(name).map(Fallback::name), Some(name)); }
+        assert_eq!(Fallback::parse("email"), None);
+    }
+
+    #[test]
+    fn failing_backend_is_an_error() {
+        // Test: A notifier that runs but fails is reported too
+        assert!(run_quiet("false", &[]).is_err());
+        assert!(run_quiet("true", &[]).is_ok());
+    }
+}
@@ -0,0 +1,70 @@
+// src/pace.rs
+// Average pace over a fixed distance, the way rowers (per 500m) and
+// runners (per km) read it off a stopwatch
+use std::time::Duration;
+
+use crate::render::format_duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Distance {
+    pub meters: u32,
+    pub split: u32, // Meters the pace is quoted per
+}
+
+impl Distance {
+    // "2000m" is paced per 500m, "5km" (or "5k", "10.5km") per km
+    pub fn parse(s: &str) -> Result<Distance, String> {
+        let bad = || format!("'{}' should be a distance like 2000m or 5km", s);
+        let (meters, split) = if let Some(km) = s.strip_suffix("km").or_else(|| s.strip_suffix('k')) {
+            let km: f64 = km.parse().ok().filter(|km: &f64| km.is_finite()).ok_or_else(bad)?;
+            ((km * 1000.0).round(), 1000)
+        } else {
+            let m: u32 = s.strip_suffix('m').and_then(|m| m.parse().ok()).ok_or_else(bad)?;
+            (m as f64, 500)
+        };
+        if !(1.0..=u32::MAX as f64).contains(&meters) { return Err(bad()); }
+        Ok(Distance { meters: meters as u32, split })
+    }
+
+    // Time per split if the whole distance took `elapsed`
+    pub fn pace(&self, elapsed: Duration) -> Duration {
+        let nanos = elapsed.as_nanos() * self.split as u128 / self.meters as u128;
+        Duration::from_nanos(nanos.min(u64::MAX as u128) as u64)
+    }
+
+    // "1:52/500m" or "5:12/km"
+    pub fn pace_text(&self, elapsed: Duration) -> String {
+        let per = if self.split == 1000 { "km".to_string() } else { format!("{}m", self.split) };
+        format!("{}/{}", format_duration(self.pace(elapsed).as_secs()), per)
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_picks_split_from_unit() {
+        // Test: Meters pace per 500m, kilometers per km
+        assert_eq!(Distance::parse("2000m"), Ok(Distance { meters: 2000, split: 500 }));
+        assert_eq!(Distance::parse("5km"), Ok(Distance { meters: 5000, split: 1000 }));
+        assert_eq!(Distance::parse("10.5k"), Ok(Distance { meters: 10500, split: 1000 }));
+        // Test: No unit, zero and junk are rejected
+        assert!(Distance::parse("2000").is_err());
+        assert!(Distance::parse("0m").is_err());
+        assert!(Distance::parse("-1km").is_err());
+        assert!(Distance::parse("fast").is_err());
+    }
+
+    #[test]
+    fn pace_scales_elapsed_to_split() {
+        // Test: 7:28 for 2000m is 1:52 per 500m
+        let row = Distance::parse("2000m").unwrap();
+        assert_eq!(row.pace(Duration::from_secs(448)), Duration::from_secs(112));
+        assert_eq!(row.pace_text(Duration::from_secs(448)), "1:52/500m");
+        // Test: 26:00 for 5km is 5:12 per km
+        let run = Distance::parse("5km").unwrap();
+        assert_eq!(run.pace_text(Duration::from_secs(1560)), "5:12/km");
+    }
+}
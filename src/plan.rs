@@ -0,0 +1,254 @@
+// src/plan.rs
+use std::time::Duration;
+
+// One named segment of a plan, e.g. "work" for 25 minutes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Phase {
+    pub label: String,
+    pub duration: Duration,
+}
+
+impl Phase {
+    pub fn new(label: &str, duration: Duration) -> Self {
+        Phase { label: label.to_string(), duration }
+    }
+
+    // The countdown into this phase, "Break starts in" for `length`
+    pub fn pre_roll(&self, length: Duration) -> Phase {
+        Phase::new(&format!("{} starts in", self.label), length)
+    }
+}
+
+// An ordered sequence of phases run back-to-back
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TimerPlan {
+    pub phases: Vec<Phase>,
+}
+
+impl TimerPlan {
+    pub fn new(phases: Vec<Phase>) -> Self {
+        TimerPlan { phases }
+    }
+
+    pub fn single(label: &str, duration: Duration) -> Self {
+        TimerPlan::new(vec![Phase::new(label, duration)])
+    }
+
+    // Split one duration into `parts` equal phases that sum EXACTLY to `total`.
+    // Leftover nanoseconds go to the earliest phases, one each.
+    pub fn split_even(label: &str, total: Duration, parts: u32) -> Option<Self> {
+        if parts == 0 { return None; }
+        let nanos = total.as_nanos();
+        let (each, extra) = (nanos / parts as u128, nanos % parts as u128);
+        let phases = (0..parts as u128).map(|i| {
+            let n = each + if i < extra { 1 } else { 0 };
+            Phase::new(label, Duration::from_nanos(n as u64))
+        }).collect();
+        Some(TimerPlan::new(phases))
+    }
+
+    // One pomodoro round: `long_every` work blocks, each followed by a short break
+    // except the last, which gets the long one. None if `long_every` is 0.
+    pub fn pomodoro(work: Duration, short: Duration, long: Duration, long_every: u32) -> Option<Self> {
+        if long_every == 0 { return None; }
+        let mut phases = Vec::new();
+        for i in 1..=long_every {
+            phases.push(Phase::new(&format!("Work {}/{}", i, long_every), work));
+            if i < long_every { phases.push(Phase::new("Break", short)); }
+        }
+        phases.push(Phase::new("Long break", long));
+        Some(TimerPlan::new(phases))
+    }
+
+    // `rounds` of work then rest, without the rest after the last round.
+    // None if `rounds` is 0.
+    pub fn intervals(work: Duration, rest: Duration, rounds: u32) -> Option<Self> {
+        if rounds == 0 { return None; }
+        let mut phases = Vec::new();
+        for i in 1..=rounds {
+            phases.push(Phase::new(&format!("Work {}/{}", i, rounds), work));
+            if i < rounds { phases.push(Phase::new(&format!("Rest {}/{}", i, rounds), rest)); }
+        }
+        Some(TimerPlan::new(phases))
+    }
+
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|p| p.duration).sum()
+    }
+
+    // Cut the plan at an offset from its start. A phase straddling the cut
+    // is divided in two, keeping its label on both sides.
+    pub fn split_at(&self, at: Duration) -> (TimerPlan, TimerPlan) {
+        let (mut head, mut tail) = (Vec::new(), Vec::new());
+        let mut offset = Duration::ZERO;
+        for phase in &self.phases {
+            let end = offset + phase.duration;
+            if end <= at {
+                head.push(phase.clone());
+            } else if offset >= at {
+                tail.push(phase.clone());
+            } else {
+                head.push(Phase::new(&phase.label, at - offset));
+                tail.push(Phase::new(&phase.label, end - at));
+            }
+            offset = end;
+        }
+        (TimerPlan::new(head), TimerPlan::new(tail))
+    }
+
+    // Run `self`, then `next`
+    pub fn merge(mut self, next: TimerPlan) -> TimerPlan {
+        self.phases.extend(next.phases);
+        self
+    }
+
+    // Stretch or shrink every phase by `factor`, None for negative/non-finite factors
+    pub fn scale(&self, factor: f64) -> Option<TimerPlan> {
+        if !factor.is_finite() || factor < 0.0 { return None; }
+        let phases = self.phases.iter()
+            .map(|p| Phase::new(&p.label, p.duration.mul_f64(factor)))
+            .collect();
+        Some(TimerPlan::new(phases))
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(s: u64) -> Duration { Duration::from_secs(s) }
+
+    // Tiny xorshift PRNG so property tests are deterministic without extra deps
+    struct Rng(u64);
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+        fn below(&mut self, n: u64) -> u64 { self.next() % n }
+        fn plan(&mut self) -> TimerPlan {
+            let n = self.below(6);
+            TimerPlan::new((0..n).map(|i| {
+                Phase::new(&format!("p{}", i), Duration::from_millis(self.below(3_600_000)))
+            }).collect())
+        }
+    }
+
+    #[test]
+    fn split_even_divides_evenly() {
+        // Test: 25 minutes into 5 phases of 5 minutes
+        let plan = TimerPlan::split_even("work", secs(1500), 5).unwrap();
+        assert_eq!(plan.phases.len(), 5);
+        assert!(plan.phases.iter().all(|p| p.duration == secs(300)));
+        // Test: Zero parts is rejected
+        assert_eq!(TimerPlan::split_even("work", secs(10), 0), None);
+    }
+
+    #[test]
+    fn pomodoro_round_ends_with_long_break() {
+        // Test: Default 25/5/15 round of 4 is W B W B W B W L
+        let plan = TimerPlan::pomodoro(secs(1500), secs(300), secs(900), 4).unwrap();
+        let labels: Vec<&str> = plan.phases.iter().map(|p| p.label.as_str()).collect();
+        assert_eq!(labels, ["Work 1/4", "Break", "Work 2/4", "Break", "Work 3/4", "Break", "Work 4/4", "Long break"]);
+        assert_eq!(plan.total(), secs(4 * 1500 + 3 * 300 + 900));
+        // Test: A pre-roll is named for the phase it leads into
+        assert_eq!(plan.phases[1].pre_roll(secs(10)), Phase::new("Break starts in", secs(10)));
+        // Test: A round needs at least one work block
+        assert_eq!(TimerPlan::pomodoro(secs(1), secs(1), secs(1), 0), None);
+    }
+
+    #[test]
+    fn intervals_skip_the_last_rest() {
+        // Test: 3 rounds of 40/20 is W R W R W, the workout ends on work
+        let plan = TimerPlan::intervals(secs(40), secs(20), 3).unwrap();
+        let labels: Vec<&str> = plan.phases.iter().map(|p| p.label.as_str()).collect();
+        assert_eq!(labels, ["Work 1/3", "Rest 1/3", "Work 2/3", "Rest 2/3", "Work 3/3"]);
+        assert_eq!(plan.total(), secs(3 * 40 + 2 * 20));
+        assert_eq!(TimerPlan::intervals(secs(40), secs(20), 0), None);
+    }
+
+    #[test]
+    fn split_at_cuts_straddling_phase() {
+        // Test: Cutting at 30m of a 25m + 10m plan splits the second phase 5/5
+        let plan = TimerPlan::new(vec![Phase::new("work", secs(1500)), Phase::new("rest", secs(600))]);
+        let (head, tail) = plan.split_at(secs(1800));
+        assert_eq!(head.phases, vec![Phase::new("work", secs(1500)), Phase::new("rest", secs(300))]);
+        assert_eq!(tail.phases, vec![Phase::new("rest", secs(300))]);
+    }
+
+    #[test]
+    fn merge_appends_in_order() {
+        let a = TimerPlan::single("a", secs(1));
+        let b = TimerPlan::single("b", secs(2));
+        let merged = a.merge(b);
+        assert_eq!(merged.phases[0].label, "a");
+        assert_eq!(merged.phases[1].label, "b");
+        assert_eq!(merged.total(), secs(3));
+    }
+
+    #[test]
+    fn scale_rejects_bad_factors() {
+        let plan = TimerPlan::single("a", secs(60));
+        assert_eq!(plan.scale(0.5).unwrap().total(), secs(30));
+        assert_eq!(plan.scale(-1.0), None);
+        assert_eq!(plan.scale(f64::NAN), None);
+    }
+
+    #[test]
+    fn prop_split_even_preserves_total() {
+        // Property: split_even phases always sum to the original duration
+        let mut rng = Rng(0x5eed);
+        for _ in 0..500 {
+            let total = Duration::from_nanos(rng.next() >> 20);
+            let parts = rng.below(50) as u32 + 1;
+            let plan = TimerPlan::split_even("x", total, parts).unwrap();
+            assert_eq!(plan.total(), total);
+            assert_eq!(plan.phases.len(), parts as usize);
+        }
+    }
+
+    #[test]
+    fn prop_split_at_then_merge_roundtrips_total() {
+        // Property: head.total == min(at, total) and head + tail == original total
+        let mut rng = Rng(0xc0ffee);
+        for _ in 0..500 {
+            let plan = rng.plan();
+            let at = Duration::from_millis(rng.below(4 * 3_600_000));
+            let (head, tail) = plan.split_at(at);
+            assert_eq!(head.total(), at.min(plan.total()));
+            assert_eq!(head.merge(tail).total(), plan.total());
+        }
+    }
+
+    #[test]
+    fn prop_merge_total_is_sum() {
+        // Property: merge never loses or invents time, and keeps phase order
+        let mut rng = Rng(42);
+        for _ in 0..500 {
+            let (a, b) = (rng.plan(), rng.plan());
+            let labels: Vec<String> = a.phases.iter().chain(&b.phases).map(|p| p.label.clone()).collect();
+            let total = a.total() + b.total();
+            let merged = a.merge(b);
+            assert_eq!(merged.total(), total);
+            assert_eq!(merged.phases.iter().map(|p| p.label.clone()).collect::<Vec<_>>(), labels);
+        }
+    }
+
+    #[test]
+    fn prop_scale_is_proportional() {
+        // Property: scaled total is within rounding (1ns per phase) of factor * total
+        let mut rng = Rng(7);
+        for _ in 0..500 {
+            let plan = rng.plan();
+            let factor = rng.below(400) as f64 / 100.0;
+            let scaled = plan.scale(factor).unwrap();
+            let expected = plan.total().as_nanos() as f64 * factor;
+            let diff = (scaled.total().as_nanos() as f64 - expected).abs();
+            assert!(diff <= plan.phases.len() as f64 + 1.0, "diff {} too large", diff);
+            assert_eq!(scaled.phases.len(), plan.phases.len());
+        }
+    }
+}
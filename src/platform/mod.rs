@@ -0,0 +1,17 @@
+// src/platform/mod.rs
+// The OS calls behind signals, the terminal, local time and the clock source,
+// one backend per platform with the same functions. Unix goes through libc,
+// Windows through the console API.
+#[cfg(unix)]
+mod unix;
+#[cfg(unix)]
+pub use unix::*;
+
+#[cfg(windows)]
+mod windows;
+#[cfg(windows)]
+pub use windows::*;
+
+// Called with the signal number, from a signal handler or (on Windows) the
+// console's control thread, so it should only set a flag
+pub type Handler = extern "C" fn(i32);
@@ -0,0 +1,187 @@
+// src/platform/unix.rs
+use std::ffi::CString;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::FileTypeExt;
+use std::path::Path;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Once;
+
+use super::Handler;
+
+pub use libc::{SIGCONT, SIGHUP, SIGINT, SIGTERM, SIGTSTP, SIGWINCH};
+
+// How --exec commands are run
+pub const SHELL: [&str; 2] = ["sh", "-c"];
+
+// Saved terminal settings, put back by restore_mode
+pub type TermState = libc::termios;
+
+pub fn catch(sigs: &[i32], handler: Handler) {
+    for &sig in sigs {
+        unsafe {
+            libc::signal(sig, handler as *const () as libc::sighandler_t);
+        }
+    }
+}
+
+// Self-pipe for waking a thread from a signal handler, -1 until set up
+static WAKE_READ: AtomicI32 = AtomicI32::new(-1);
+static WAKE_WRITE: AtomicI32 = AtomicI32::new(-1);
+
+// Needs calling before any handler's wake() counts
+pub fn init_wake() {
+    static PIPE: Once = Once::new();
+    PIPE.call_once(|| {
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 { return; }
+        // A full pipe already has a wake queued, so a handler never needs to block
+        unsafe { libc::fcntl(fds[1], libc::F_SETFL, libc::O_NONBLOCK); }
+        WAKE_READ.store(fds[0], Ordering::Relaxed);
+        WAKE_WRITE.store(fds[1], Ordering::Relaxed);
+    });
+}
+
+// Only a write(2), so safe inside a signal handler
+pub fn wake() {
+    let fd = WAKE_WRITE.load(Ordering::Relaxed);
+    if fd >= 0 { unsafe { libc::write(fd, [1u8].as_ptr().cast(), 1); } }
+}
+
+// Block until wake() has been called since the last return.
+// False if there's nothing to wait on.
+pub fn wait_for_wake() -> bool {
+    let fd = WAKE_READ.load(Ordering::Relaxed);
+    let mut buf = [0u8; 64];
+    fd >= 0 && unsafe { libc::read(fd, buf.as_mut_ptr().cast(), buf.len()) } > 0
+}
+
+// Start `command` in a process group of its own, so Ctrl+C at the terminal
+// doesn't reach it and stop_group reaches everything it starts
+pub fn own_group(command: &mut std::process::Command) {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0);
+}
+
+// SIGTERM, or SIGKILL when `force`, to every process in `child`'s group
+pub fn stop_group(child: &mut std::process::Child, force: bool) {
+    let sig = if force { libc::SIGKILL } else { libc::SIGTERM };
+    unsafe { libc::kill(-(child.id() as i32), sig); }
+}
+
+// Stop like an unhandled SIGTSTP would, returning after SIGCONT
+pub fn stop_self() {
+    unsafe { libc::kill(libc::getpid(), libc::SIGSTOP); }
+}
+
+// Turn off line buffering and echo on stdin so single keys arrive immediately.
+// ISIG stays on so Ctrl+C still raises SIGINT. Returns the settings to restore,
+// None when stdin isn't a terminal. Some terminals refuse TCSAFLUSH or only
+// take part of the change, see is_raw for whether it worked.
+pub fn enable_raw_mode() -> Option<TermState> {
+    let mut termios: libc::termios = unsafe { std::mem::zeroed() };
+    if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut termios) } != 0 { return None; }
+    let original = termios;
+
+    termios.c_lflag &= !(libc::ICANON | libc::ECHO);
+    termios.c_cc[libc::VMIN] = 1;
+    termios.c_cc[libc::VTIME] = 0;
+    if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &termios) } != 0 {
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios); }
+    }
+    Some(original)
+}
+
+// Whether stdin really is without line buffering and echo. tcsetattr
+// reports success if any part of a change was made, so it's read back.
+pub fn is_raw() -> bool {
+    let mut termios: libc::termios = unsafe { std::mem::zeroed() };
+    if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut termios) } != 0 { return false; }
+    termios.c_lflag & (libc::ICANON | libc::ECHO) == 0
+}
+
+pub fn restore_mode(state: &TermState) {
+    unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, state); }
+}
+
+// Query (cols, rows) of the terminal on stdout via TIOCGWINSZ
+pub fn terminal_size() -> Option<(u16, u16)> {
+    let mut ws: libc::winsize = unsafe { std::mem::zeroed() };
+    let ret = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut ws) };
+    if ret != 0 || ws.ws_col == 0 || ws.ws_row == 0 { return None; }
+    Some((ws.ws_col, ws.ws_row))
+}
+
+// What the kernel reads the time from ("tsc", "kvm-clock"), Linux only
+pub fn clock_source() -> Option<String> {
+    let text = std::fs::read_to_string("/sys/devices/system/clocksource/clocksource0/current_clocksource").ok()?;
+    Some(text.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+// Whether `pid` is still running. One we may not signal is still there.
+pub fn process_exists(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else { return false };
+    let alive = unsafe { libc::kill(pid, 0) } == 0;
+    alive || io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+// Tells users apart in shared temp dirs
+pub fn user_id() -> String {
+    unsafe { libc::getuid() }.to_string()
+}
+
+pub fn local_time(unix: i64) -> Option<libc::tm> {
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    let t = unix as libc::time_t;
+    if unsafe { libc::localtime_r(&t, &mut tm) }.is_null() { return None; }
+    Some(tm)
+}
+
+// Local wall-clock time to UNIX seconds, None if it can't be represented
+pub fn make_time(tm: &mut libc::tm) -> Option<i64> {
+    let t = unsafe { libc::mktime(tm) };
+    if t == -1 { None } else { Some(t as i64) }
+}
+
+// Raw 8N1 at `baud`, one of serial::BAUDS, for a terminal device. Anything
+// else is left alone.
+pub fn set_serial(file: &std::fs::File, baud: u32) -> io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+    let fd = file.as_raw_fd();
+    if unsafe { libc::isatty(fd) } != 1 { return Ok(()); }
+    let speed = match baud {
+        1200 => libc::B1200,
+        2400 => libc::B2400,
+        4800 => libc::B4800,
+        9600 => libc::B9600,
+        19200 => libc::B19200,
+        38400 => libc::B38400,
+        57600 => libc::B57600,
+        115200 => libc::B115200,
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("unsupported baud rate {}", baud))),
+    };
+    let mut termios = unsafe { std::mem::zeroed::<libc::termios>() };
+    if unsafe { libc::tcgetattr(fd, &mut termios) } != 0 { return Err(io::Error::last_os_error()); }
+    unsafe {
+        libc::cfmakeraw(&mut termios);
+        libc::cfsetispeed(&mut termios, speed);
+        libc::cfsetospeed(&mut termios, speed);
+    }
+    // No modem control lines to wait on, most USB adapters have none
+    termios.c_cflag |= libc::CLOCAL;
+    if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &termios) } != 0 { return Err(io::Error::last_os_error()); }
+    Ok(())
+}
+
+// Make `path` a FIFO unless it already is one. True if it was made here.
+pub fn make_fifo(path: &Path) -> io::Result<bool> {
+    match std::fs::metadata(path) {
+        Ok(meta) if meta.file_type().is_fifo() => return Ok(false),
+        Ok(_) => return Err(io::Error::new(io::ErrorKind::AlreadyExists, "exists and isn't a FIFO")),
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        Err(_) => {}
+    }
+    let c_path = CString::new(path.as_os_str().as_bytes()).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+    if unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) } != 0 { return Err(io::Error::last_os_error()); }
+    Ok(true)
+}
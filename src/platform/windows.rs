@@ -0,0 +1,240 @@
+// src/platform/windows.rs
+// Windows has no signals to speak of: Ctrl+C, Ctrl+Break and closing the
+// console window arrive as console control events instead, and there's no
+// job control or SIGWINCH at all. The constants keep Unix numbering so the
+// rest of the crate can match on them either way.
+use std::ffi::c_void;
+use std::io;
+use std::path::Path;
+use std::sync::{Condvar, Mutex, OnceLock};
+
+use super::Handler;
+
+pub use libc::{SIGINT, SIGTERM};
+pub const SIGHUP: i32 = 1;
+// Never raised here
+pub const SIGTSTP: i32 = 20;
+pub const SIGCONT: i32 = 18;
+pub const SIGWINCH: i32 = 28;
+
+pub const SHELL: [&str; 2] = ["cmd", "/C"];
+
+// Console input and output modes, in that order
+pub type TermState = (u32, u32);
+
+type Handle = *mut c_void;
+
+const STD_INPUT_HANDLE: u32 = -10i32 as u32;
+const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+const ENABLE_PROCESSED_INPUT: u32 = 0x0001;
+const ENABLE_LINE_INPUT: u32 = 0x0002;
+const ENABLE_ECHO_INPUT: u32 = 0x0004;
+const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+
+const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+const STILL_ACTIVE: u32 = 259;
+
+const CTRL_C_EVENT: u32 = 0;
+const CTRL_BREAK_EVENT: u32 = 1;
+const CTRL_CLOSE_EVENT: u32 = 2;
+const CTRL_LOGOFF_EVENT: u32 = 5;
+const CTRL_SHUTDOWN_EVENT: u32 = 6;
+
+#[repr(C)]
+struct Coord {
+    x: i16,
+    y: i16,
+}
+
+#[repr(C)]
+struct SmallRect {
+    left: i16,
+    top: i16,
+    right: i16,
+    bottom: i16,
+}
+
+#[repr(C)]
+struct ScreenBufferInfo {
+    size: Coord,
+    cursor: Coord,
+    attributes: u16,
+    window: SmallRect,
+    max_window: Coord,
+}
+
+#[repr(C)]
+struct PowerStatus {
+    ac_line_status: u8, // 0 offline, 1 online, 255 unknown
+    battery_flag: u8,
+    battery_life_percent: u8,
+    system_status_flag: u8,
+    battery_life_time: u32,
+    battery_full_life_time: u32,
+}
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn GetStdHandle(which: u32) -> Handle;
+    fn GetConsoleMode(console: Handle, mode: *mut u32) -> i32;
+    fn SetConsoleMode(console: Handle, mode: u32) -> i32;
+    fn GetConsoleScreenBufferInfo(console: Handle, info: *mut ScreenBufferInfo) -> i32;
+    fn SetConsoleCtrlHandler(handler: Option<unsafe extern "system" fn(u32) -> i32>, add: i32) -> i32;
+    fn OpenProcess(access: u32, inherit: i32, pid: u32) -> Handle;
+    fn GetExitCodeProcess(process: Handle, code: *mut u32) -> i32;
+    fn CloseHandle(handle: Handle) -> i32;
+    fn GetSystemPowerStatus(status: *mut PowerStatus) -> i32;
+}
+
+extern "C" {
+    // libc only has localtime_s, mktime comes straight from the CRT
+    #[link_name = "_mktime64"]
+    fn mktime64(tm: *mut libc::tm) -> i64;
+}
+
+static EXIT_HANDLER: OnceLock<Handler> = OnceLock::new();
+
+unsafe extern "system" fn console_ctrl(event: u32) -> i32 {
+    let sig = match event {
+        CTRL_C_EVENT | CTRL_BREAK_EVENT => SIGINT,
+        CTRL_CLOSE_EVENT => SIGHUP,
+        CTRL_LOGOFF_EVENT | CTRL_SHUTDOWN_EVENT => SIGTERM,
+        _ => return 0,
+    };
+    let Some(handler) = EXIT_HANDLER.get() else { return 0 };
+    handler(sig);
+    // The process ends as soon as a close, logoff or shutdown event returns,
+    // so give the main loop a moment to restore the console first
+    if sig != SIGINT { std::thread::sleep(std::time::Duration::from_millis(500)); }
+    1
+}
+
+// Only the exit signals have a Windows counterpart, the rest are ignored
+pub fn catch(sigs: &[i32], handler: Handler) {
+    if !sigs.iter().any(|&sig| sig == SIGINT || sig == SIGTERM || sig == SIGHUP) { return; }
+    if EXIT_HANDLER.set(handler).is_ok() {
+        unsafe { SetConsoleCtrlHandler(Some(console_ctrl), 1); }
+    }
+}
+
+// Control handlers run on a thread of their own rather than interrupting
+// one, so a plain condvar can do what a self-pipe does on Unix
+static WOKEN: Mutex<bool> = Mutex::new(false);
+static WAKE: Condvar = Condvar::new();
+
+pub fn init_wake() {}
+
+pub fn wake() {
+    *WOKEN.lock().unwrap_or_else(|e| e.into_inner()) = true;
+    WAKE.notify_all();
+}
+
+pub fn wait_for_wake() -> bool {
+    let mut woken = WOKEN.lock().unwrap_or_else(|e| e.into_inner());
+    while !*woken { woken = WAKE.wait(woken).unwrap_or_else(|e| e.into_inner()); }
+    *woken = false;
+    true
+}
+
+// A new process group keeps Ctrl+C at the console from reaching `command`
+pub fn own_group(command: &mut std::process::Command) {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+    command.creation_flags(CREATE_NEW_PROCESS_GROUP);
+}
+
+// There's no SIGTERM to ask with, so `child` is terminated either way, and
+// what it started is left running
+pub fn stop_group(child: &mut std::process::Child, _force: bool) {
+    let _ = child.kill();
+}
+
+// No job control, so nothing to stop for
+pub fn stop_self() {}
+
+// Keys arrive one at a time without echo, Ctrl+C stays a control event, and
+// the console interprets the ANSI sequences the renderer writes. None when
+// stdin isn't a console. A console that refuses the change stays cooked,
+// see is_raw.
+pub fn enable_raw_mode() -> Option<TermState> {
+    let (input, output) = unsafe { (GetStdHandle(STD_INPUT_HANDLE), GetStdHandle(STD_OUTPUT_HANDLE)) };
+    let (mut in_mode, mut out_mode) = (0, 0);
+    if unsafe { GetConsoleMode(input, &mut in_mode) } == 0 { return None; }
+    let raw = (in_mode & !(ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT)) | ENABLE_PROCESSED_INPUT;
+    unsafe { SetConsoleMode(input, raw); }
+    if unsafe { GetConsoleMode(output, &mut out_mode) } != 0 {
+        unsafe { SetConsoleMode(output, out_mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING); }
+    }
+    Some((in_mode, out_mode))
+}
+
+// Whether the console mode change to no line input or echo held
+pub fn is_raw() -> bool {
+    let mut mode = 0;
+    if unsafe { GetConsoleMode(GetStdHandle(STD_INPUT_HANDLE), &mut mode) } == 0 { return false; }
+    mode & (ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT) == 0
+}
+
+pub fn restore_mode(state: &TermState) {
+    unsafe {
+        SetConsoleMode(GetStdHandle(STD_INPUT_HANDLE), state.0);
+        SetConsoleMode(GetStdHandle(STD_OUTPUT_HANDLE), state.1);
+    }
+}
+
+// Size of the visible window, not the whole scrollback buffer
+pub fn terminal_size() -> Option<(u16, u16)> {
+    let mut info: ScreenBufferInfo = unsafe { std::mem::zeroed() };
+    if unsafe { GetConsoleScreenBufferInfo(GetStdHandle(STD_OUTPUT_HANDLE), &mut info) } == 0 { return None; }
+    let cols = info.window.right - info.window.left + 1;
+    let rows = info.window.bottom - info.window.top + 1;
+    if cols <= 0 || rows <= 0 { return None; }
+    Some((cols as u16, rows as u16))
+}
+
+// QueryPerformanceCounter has no name to report
+pub fn clock_source() -> Option<String> {
+    None
+}
+
+// Whether `pid` is still running, by its exit code
+pub fn process_exists(pid: u32) -> bool {
+    let process = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+    if process.is_null() { return false; }
+    let mut code = 0;
+    let ok = unsafe { GetExitCodeProcess(process, &mut code) } != 0;
+    unsafe { CloseHandle(process); }
+    ok && code == STILL_ACTIVE
+}
+
+// Unplugged, an unknown line status counts as mains
+pub fn on_battery() -> bool {
+    let mut status = PowerStatus { ac_line_status: 255, battery_flag: 0, battery_life_percent: 0, system_status_flag: 0, battery_life_time: 0, battery_full_life_time: 0 };
+    unsafe { GetSystemPowerStatus(&mut status) } != 0 && status.ac_line_status == 0
+}
+
+pub fn user_id() -> String {
+    std::env::var("USERNAME").unwrap_or_else(|_| "user".to_string())
+}
+
+pub fn local_time(unix: i64) -> Option<libc::tm> {
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    let t = unix as libc::time_t;
+    if unsafe { libc::localtime_s(&mut tm, &t) } != 0 { return None; }
+    Some(tm)
+}
+
+pub fn make_time(tm: &mut libc::tm) -> Option<i64> {
+    let t = unsafe { mktime64(tm) };
+    if t == -1 { None } else { Some(t) }
+}
+
+// COM ports keep whatever mode mode.com gave them
+pub fn set_serial(_file: &std::fs::File, _baud: u32) -> io::Result<()> {
+    Ok(())
+}
+
+// Windows named pipes live in their own namespace, not the file system
+pub fn make_fifo(_path: &Path) -> io::Result<bool> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "FIFOs need a Unix system"))
+}
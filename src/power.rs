@@ -0,0 +1,112 @@
+// src/power.rs
+// --power-save: on battery, poll for what can't wake the loop (trigger
+// files, ack markers, nag rings) once a second instead of every
+// timer.tick_ms. The display only redraws when a second ticks over anyway.
+use std::path::Path;
+#[cfg(target_os = "macos")]
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+// How often a power-saving poll comes round
+pub const SLOW_TICK: Duration = Duration::from_secs(1);
+// How long an on-battery reading is trusted, you may plug in mid-run
+pub const RECHECK: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PowerSave {
+    Auto, // Slow down only on battery
+    On,
+    #[default]
+    Off,
+}
+
+impl PowerSave {
+    pub const NAMES: [&'static str; 3] = ["auto", "on", "off"];
+
+    pub fn parse(name: &str) -> Option<PowerSave> {
+        match name {
+            "auto" => Some(PowerSave::Auto),
+            "on" => Some(PowerSave::On),
+            "off" => Some(PowerSave::Off),
+            _ => None,
+        }
+    }
+
+    // The poll interval for a configured `tick`, asking `on_battery` only if it matters
+    pub fn tick(self, tick: Duration, on_battery: impl FnOnce() -> bool) -> Duration {
+        let slow = match self {
+            PowerSave::Off => false,
+            PowerSave::On => true,
+            PowerSave::Auto => on_battery(),
+        };
+        if slow { tick.max(SLOW_TICK) } else { tick }
+    }
+}
+
+// True when a battery under `root` (/sys/class/power_supply) is running down
+pub fn discharging(root: &Path) -> bool {
+    let Ok(supplies) = std::fs::read_dir(root) else { return false };
+    supplies.flatten().any(|supply| {
+        let read = |name: &str| std::fs::read_to_string(supply.path().join(name)).unwrap_or_default();
+        read("type").trim() == "Battery" && read("status").trim() == "Discharging"
+    })
+}
+
+#[cfg(target_os = "linux")]
+pub fn on_battery() -> bool {
+    discharging(Path::new("/sys/class/power_supply"))
+}
+
+// "Now drawing from 'Battery Power'"
+#[cfg(target_os = "macos")]
+pub fn on_battery() -> bool {
+    let Ok(out) = Command::new("pmset").args(["-g", "batt"]).stderr(Stdio::null()).output() else { return false };
+    String::from_utf8_lossy(&out.stdout).lines().next().is_some_and(|line| line.contains("'Battery Power'"))
+}
+
+#[cfg(windows)]
+pub fn on_battery() -> bool {
+    crate::platform::on_battery()
+}
+
+// Nothing known to ask, so assume mains
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+pub fn on_battery() -> bool {
+    false
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tick_slows_only_when_saving() {
+        let tick = Duration::from_millis(100);
+        assert_eq!(PowerSave::Off.tick(tick, || true), tick);
+        assert_eq!(PowerSave::On.tick(tick, || false), SLOW_TICK);
+        assert_eq!(PowerSave::Auto.tick(tick, || true), SLOW_TICK);
+        assert_eq!(PowerSave::Auto.tick(tick, || false), tick);
+        // Test: A tick already slower is left alone
+        assert_eq!(PowerSave::On.tick(Duration::from_secs(2), || true), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn discharging_looks_for_a_battery_running_down() {
+        let root = std::env::temp_dir().join(format!("timeterm-power-{}", std::process::id()));
+        let supply = |name: &str, kind: &str, status: &str| {
+            let dir = root.join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("type"), format!("{}\n", kind)).unwrap();
+            std::fs::write(dir.join("status"), format!("{}\n", status)).unwrap();
+        };
+        supply("AC", "Mains", "");
+        supply("BAT0", "Battery", "Charging");
+        assert!(!discharging(&root));
+        supply("BAT0", "Battery", "Discharging");
+        assert!(discharging(&root));
+        let _ = std::fs::remove_dir_all(&root);
+        // Test: No power supplies at all is a desktop
+        assert!(!discharging(&root));
+    }
+}
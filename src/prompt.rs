@@ -0,0 +1,70 @@
+// src/prompt.rs
+use std::io::{self, BufRead, Write};
+
+// Ask a yes/no question on stderr (stdout belongs to the display).
+// Anything but y/yes, including EOF, counts as no.
+pub fn confirm(question: &str) -> bool {
+    confirm_with(&mut io::stdin().lock(), &mut io::stderr(), question)
+}
+
+pub fn confirm_with<R: BufRead, W: Write>(input: &mut R, output: &mut W, question: &str) -> bool {
+    let _ = write!(output, "{} [y/N] ", question);
+    let _ = output.flush();
+    let mut answer = String::new();
+    if input.read_line(&mut answer).is_err() { return false; }
+    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+// Ask for a value on stderr, None for an empty answer or EOF
+pub fn ask(name: &str) -> Option<String> {
+    ask_with(&mut io::stdin().lock(), &mut io::stderr(), name)
+}
+
+pub fn ask_with<R: BufRead, W: Write>(input: &mut R, output: &mut W, name: &str) -> Option<String> {
+    let _ = write!(output, "{}: ", name);
+    let _ = output.flush();
+    let mut answer = String::new();
+    input.read_line(&mut answer).ok()?;
+    Some(answer.trim().to_string()).filter(|a| !a.is_empty())
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ask(answer: &str) -> (bool, String) {
+        let mut out = Vec::new();
+        let yes = confirm_with(&mut answer.as_bytes(), &mut out, "Go?");
+        (yes, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn confirm_accepts_yes() {
+        assert!(ask("y\n").0);
+        assert!(ask(" YES \n").0);
+    }
+
+    #[test]
+    fn confirm_defaults_to_no() {
+        // Test: Empty answer, other text and EOF all mean no
+        assert!(!ask("\n").0);
+        assert!(!ask("nope\n").0);
+        assert!(!ask("").0);
+    }
+
+    #[test]
+    fn confirm_shows_question_with_default() {
+        assert_eq!(ask("y\n").1, "Go? [y/N] ");
+    }
+
+    #[test]
+    fn ask_with_reads_a_value() {
+        let mut out = Vec::new();
+        assert_eq!(ask_with(&mut " 42 \n".as_bytes(), &mut out, "pr"), Some("42".to_string()));
+        assert_eq!(String::from_utf8(out).unwrap(), "pr: ");
+        // Test: Nothing typed is no value
+        assert_eq!(ask_with(&mut "\n".as_bytes(), &mut Vec::new(), "pr"), None);
+        assert_eq!(ask_with(&mut "".as_bytes(), &mut Vec::new(), "pr"), None);
+    }
+}
@@ -0,0 +1,88 @@
+// src/quotes.rs
+// One line at a time from a user's quotes file, shown under the timer.
+// Blank lines and "# comments" are skipped. Picks are random but never the
+// same line twice in a row, so cycling always shows something new.
+use std::io;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quotes {
+    lines: Vec<String>,
+    current: usize,
+    state: u64, // xorshift64, never zero
+}
+
+impl Quotes {
+    // None if there's nothing to show
+    pub fn parse(text: &str, seed: u64) -> Option<Quotes> {
+        let lines: Vec<String> = text.lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+        if lines.is_empty() { return None; }
+        let mut quotes = Quotes { lines, current: 0, state: seed | 1 };
+        quotes.current = quotes.roll() % quotes.lines.len();
+        Some(quotes)
+    }
+
+    // Seeded from the clock, so each run starts somewhere different
+    pub fn load(path: &Path) -> io::Result<Quotes> {
+        let text = std::fs::read_to_string(path)?;
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0) ^ std::process::id() as u64;
+        Quotes::parse(&text, seed).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "no quotes in file"))
+    }
+
+    pub fn current(&self) -> &str {
+        &self.lines[self.current]
+    }
+
+    // Move to a random different line (the same one if there's only one)
+    pub fn advance(&mut self) -> &str {
+        if self.lines.len() > 1 {
+            let skip = 1 + self.roll() % (self.lines.len() - 1);
+            self.current = (self.current + skip) % self.lines.len();
+        }
+        self.current()
+    }
+
+    fn roll(&mut self) -> usize {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state as usize
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_skips_blanks_and_comments() {
+        // Test: Only real lines are quotes, an empty file has none
+        let quotes = Quotes::parse("# mine\n\n  Keep going  \n", 42).unwrap();
+        assert_eq!(quotes.current(), "Keep going");
+        assert_eq!(Quotes::parse("# nothing\n\n", 42), None);
+    }
+
+    #[test]
+    fn advance_never_repeats_a_line() {
+        // Test: Every step lands on a different line, and all of them come up
+        let mut quotes = Quotes::parse("a\nb\nc\n", 7).unwrap();
+        let mut seen = vec![quotes.current().to_string()];
+        for _ in 0..30 {
+            let before = quotes.current().to_string();
+            let after = quotes.advance().to_string();
+            assert_ne!(before, after);
+            seen.push(after);
+        }
+        assert!(["a", "b", "c"].iter().all(|l| seen.iter().any(|s| s == l)));
+        // Test: A single line just stays put
+        assert_eq!(Quotes::parse("only\n", 7).unwrap().advance(), "only");
+    }
+}
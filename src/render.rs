@@ -0,0 +1,312 @@
+// src/render.rs
+use crate::terminal::ansi;
+use crate::text;
+
+pub const DIGIT_HEIGHT: usize = 5;
+
+// 5x5 ASCII font for the big countdown digits
+const DIGITS: [[&str; DIGIT_HEIGHT]; 10] = [
+    [" ### ", "#   #", "#   #", "#   #", " ### "],
+    ["  #  ", " ##  ", "  #  ", "  #  ", " ### "],
+    [" ### ", "#   #", "  ## ", " #   ", "#####"],
+    ["#### ", "    #", " ### ", "    #", "#### "],
+    ["#   #", "#   #", "#####", "    #", "    #"],
+    ["#####", "#    ", "#### ", "    #", "#### "],
+    [" ### ", "#    ", "#### ", "#   #", " ### "],
+    ["#####", "    #", "   # ", "  #  ", "  #  "],
+    [" ### ", "#   #", " ### ", "#   #", " ### "],
+    [" ### ", "#   #", " ####", "    #", " ### "],
+];
+const COLON: [&str; DIGIT_HEIGHT] = ["   ", " # ", "   ", " # ", "   "];
+const MINUS: [&str; DIGIT_HEIGHT] = ["   ", "   ", "###", "   ", "   "];
+
+// Format seconds per SPEC.md: "1:23:45", "23:45", "0:45"
+pub fn format_duration(secs: u64) -> String {
+    let (hrs, mins, secs) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if hrs > 0 { format!("{}:{:02}:{:02}", hrs, mins, secs) }
+    else { format!("{}:{:02}", mins, secs) }
+}
+
+// Time past zero with --overtime, "-0:42"
+pub fn overtime(secs: u64) -> String {
+    format!("-{}", format_duration(secs))
+}
+
+// Render a time string like "12:34" as DIGIT_HEIGHT lines of big ASCII glyphs.
+// Anything that isn't a digit, ':' or '-' is skipped.
+pub fn big_text(s: &str) -> Vec<String> {
+    let glyphs: Vec<&[&str; DIGIT_HEIGHT]> = s.chars().filter_map(|c| match c {
+        ':' => Some(&COLON),
+        '-' => Some(&MINUS),
+        d => d.to_digit(10).map(|d| &DIGITS[d as usize]),
+    }).collect();
+
+    (0..DIGIT_HEIGHT).map(|row| {
+        glyphs.iter().map(|g| g[row]).collect::<Vec<_>>().join(" ")
+    }).collect()
+}
+
+// Which number is drawn big, and whether the other one is shown small below it
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Layout {
+    #[default]
+    Remaining,
+    Elapsed,
+    RemainingElapsed,
+    ElapsedRemaining,
+}
+
+impl Layout {
+    pub const NAMES: [&'static str; 4] = ["remaining", "elapsed", "remaining+elapsed", "elapsed+remaining"];
+
+    pub fn parse(name: &str) -> Option<Layout> {
+        match name {
+            "remaining" => Some(Layout::Remaining),
+            "elapsed" => Some(Layout::Elapsed),
+            "remaining+elapsed" => Some(Layout::RemainingElapsed),
+            "elapsed+remaining" => Some(Layout::ElapsedRemaining),
+            _ => None,
+        }
+    }
+
+    // Big secs and the small detail line. Without a remaining time (stopwatch)
+    // there's only elapsed to show.
+    pub fn readout(self, remaining: Option<u64>, elapsed: u64) -> (u64, Option<String>) {
+        let Some(remaining) = remaining else { return (elapsed, None) };
+        match self {
+            Layout::Remaining => (remaining, None),
+            Layout::Elapsed => (elapsed, None),
+            Layout::RemainingElapsed => (remaining, Some(format!("{} elapsed", format_duration(elapsed)))),
+            Layout::ElapsedRemaining => (elapsed, Some(format!("{} left", format_duration(remaining)))),
+        }
+    }
+}
+
+// One full frame for an already formatted `time` (so it can be "-0:42"),
+// centered in (cols, rows). Falls back to a single plain line when the
+// terminal is too small for big digits. An optional `detail` line (e.g. "3:12
+// elapsed") goes one blank row below the time, then `status` (e.g. "PAUSED"),
+// cleared when None, then `notes` (e.g. a wrapped quote) one blank row below
+// that. Notes that don't fit are dropped, and everything below them is
+// cleared in case the last notes were longer.
+pub fn noted_frame(time: &str, detail: Option<&str>, notes: &[String], cols: u16, rows: u16, status: Option<&str>) -> String {
+    let mut out = draw(time, detail, notes, cols, rows, status);
+    if !notes.is_empty() { out.push_str(ansi::CLEAR_BELOW); }
+    out
+}
+
+// How many notes fit under a noted_frame, for a caller that wants to show
+// the last ones rather than lose them
+pub fn note_room(time: &str, detail: Option<&str>, cols: u16, rows: u16) -> usize {
+    let status_row = rows_for(time, detail, &[], cols, rows, None).last().map_or(0, |&(row, _)| row);
+    (rows as usize).saturating_sub(status_row + 1)
+}
+
+// `text` centered on the top row, above the frame, unless the screen is too
+// short to leave a gap between the two
+pub fn header(text: &str, cols: u16, rows: u16) -> String {
+    if (rows as usize) < DIGIT_HEIGHT + 4 { return String::new(); }
+    format!("{}{}{}", ansi::move_to(1, 1), ansi::CLEAR_LINE, text::center(text, cols as usize))
+}
+
+// Row (1-based) and text of each line of a frame. A None row is cleared.
+fn rows_for(time: &str, detail: Option<&str>, notes: &[String], cols: u16, rows: u16, status: Option<&str>) -> Vec<(usize, Option<String>)> {
+    let big = big_text(time);
+    let fits = big.iter().all(|l| text::display_width(l) <= cols as usize)
+        && DIGIT_HEIGHT <= rows as usize;
+    let lines = if fits { big } else { vec![time.to_string()] };
+
+    let top = (rows as usize).saturating_sub(lines.len()) / 2 + 1;
+    let mut below = top + lines.len() + 1;
+    let mut out: Vec<(usize, Option<String>)> = lines.into_iter().enumerate().map(|(i, line)| (top + i, Some(line))).collect();
+    if let Some(detail) = detail {
+        out.push((below, Some(detail.to_string())));
+        below += 1;
+    }
+    out.push((below, status.map(str::to_string)));
+    let fitting = (rows as usize).saturating_sub(below + 1);
+    out.extend(notes.iter().take(fitting).enumerate().map(|(i, note)| (below + 2 + i, Some(note.clone()))));
+    out
+}
+
+fn draw(time: &str, detail: Option<&str>, notes: &[String], cols: u16, rows: u16, status: Option<&str>) -> String {
+    let mut out = String::new();
+    for (row, line) in rows_for(time, detail, notes, cols, rows, status) {
+        out.push_str(&ansi::move_to(row as u16, 1));
+        out.push_str(ansi::CLEAR_LINE);
+        if let Some(line) = line { out.push_str(&text::center(&line, cols as usize)); }
+    }
+    out
+}
+
+// Two frames side by side for `timerterm duel`, a (time, status) for each
+// half of the screen. Each half falls back to a plain line on its own.
+pub fn duel_frame(sides: [(&str, Option<&str>); 2], cols: u16, rows: u16) -> String {
+    let half = [cols / 2, cols - cols / 2];
+    let mut lines: std::collections::BTreeMap<usize, [Option<String>; 2]> = std::collections::BTreeMap::new();
+    for (i, (time, status)) in sides.into_iter().enumerate() {
+        for (row, line) in rows_for(time, None, &[], half[i], rows, status) {
+            lines.entry(row).or_default()[i] = line;
+        }
+    }
+    let mut out = String::new();
+    for (row, [left, right]) in lines {
+        out.push_str(&ansi::move_to(row as u16, 1));
+        out.push_str(ansi::CLEAR_LINE);
+        let left = text::center(left.as_deref().unwrap_or(""), half[0] as usize);
+        let right = text::center(right.as_deref().unwrap_or(""), half[1] as usize);
+        out.push_str(format!("{}{}", left, right).trim_end());
+    }
+    out
+}
+
+// The same frame as plain text, one line per terminal row with trailing
+// spaces trimmed, as if copied off a freshly cleared screen
+pub fn plain_frame(big_secs: u64, detail: Option<&str>, cols: u16, rows: u16, status: Option<&str>) -> String {
+    let mut screen = vec![String::new(); rows as usize];
+    for (row, line) in rows_for(&format_duration(big_secs), detail, &[], cols, rows, status) {
+        if let (Some(slot), Some(line)) = (screen.get_mut(row - 1), line) {
+            *slot = text::center(&line, cols as usize).trim_end().to_string();
+        }
+    }
+    screen.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+// Line mode's stand-in for a frame, the parts on one line: "24:59  PAUSED"
+pub fn line(time: &str, detail: Option<&str>, status: Option<&str>) -> String {
+    std::iter::once(time).chain(detail).chain(status).collect::<Vec<_>>().join("  ")
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_duration_drops_leading_zeros() {
+        // Test: Formats match SPEC.md display rules
+        assert_eq!(format_duration(5), "0:05");
+        assert_eq!(format_duration(65), "1:05");
+        assert_eq!(format_duration(3665), "1:01:05");
+        assert_eq!(format_duration(600), "10:00");
+        assert_eq!(overtime(42), "-0:42");
+    }
+
+    #[test]
+    fn big_text_has_uniform_rows() {
+        // Test: Every row of a big string is the same width
+        let lines = big_text("10:00");
+        assert_eq!(lines.len(), DIGIT_HEIGHT);
+        // 4 digits (5 wide) + colon (3 wide) + 4 separating spaces
+        assert!(lines.iter().all(|l| l.len() == 4 * 5 + 3 + 4));
+    }
+
+    #[test]
+    fn big_text_draws_digit_glyphs() {
+        // Test: A single "1" is exactly the 1 glyph
+        assert_eq!(big_text("1"), DIGITS[1].to_vec());
+        // Test: Unknown chars are ignored rather than panicking
+        assert_eq!(big_text("x1"), big_text("1"));
+        // Test: Overtime's minus sign has a glyph of its own
+        assert_eq!(big_text("-1")[2], format!("{} {}", MINUS[2], DIGITS[1][2]));
+    }
+
+    #[test]
+    fn frame_centers_big_digits_vertically() {
+        // Test: 24 rows with 5 digit rows starts drawing at row 10
+        let out = noted_frame("1:30", None, &[], 80, 24, None);
+        assert!(out.starts_with(&ansi::move_to(10, 1)));
+        assert!(out.contains(&ansi::move_to(14, 1)));
+        // Test: Row 15 is left blank, row 16 is the (cleared) status row
+        assert!(!out.contains(&ansi::move_to(15, 1)));
+        assert!(out.ends_with(&format!("{}{}", ansi::move_to(16, 1), ansi::CLEAR_LINE)));
+    }
+
+    #[test]
+    fn frame_falls_back_to_plain_when_narrow() {
+        // Test: 10 columns can't fit big digits, so plain "1:30" is drawn
+        let out = noted_frame("1:30", None, &[], 10, 24, None);
+        assert!(out.contains("   1:30   "));
+        assert!(!out.contains('#'));
+    }
+
+    #[test]
+    fn layout_picks_big_and_small_numbers() {
+        // Test: 6:48 left of 10:00 either way round
+        assert_eq!(Layout::Remaining.readout(Some(408), 192), (408, None));
+        assert_eq!(Layout::Elapsed.readout(Some(408), 192), (192, None));
+        assert_eq!(Layout::RemainingElapsed.readout(Some(408), 192), (408, Some("3:12 elapsed".to_string())));
+        assert_eq!(Layout::ElapsedRemaining.readout(Some(408), 192), (192, Some("6:48 left".to_string())));
+        // Test: A stopwatch has no remaining time, so only elapsed is shown
+        assert_eq!(Layout::RemainingElapsed.readout(None, 192), (192, None));
+        assert!(Layout::NAMES.iter().all(|n| Layout::parse(n).is_some()));
+    }
+
+    #[test]
+    fn detail_goes_above_status() {
+        // Test: Detail takes row 16 and pushes the status to row 17
+        let out = noted_frame("1:30", Some("0:30 elapsed"), &[], 80, 24, Some("PAUSED"));
+        let detail = format!("{}{}{}", ansi::move_to(16, 1), ansi::CLEAR_LINE, text::center("0:30 elapsed", 80));
+        assert!(out.contains(&detail));
+        assert!(out.ends_with(&format!("{}{}{}", ansi::move_to(17, 1), ansi::CLEAR_LINE, text::center("PAUSED", 80))));
+    }
+
+    #[test]
+    fn plain_frame_matches_drawn_rows() {
+        // Test: Same rows as noted_frame, without escapes or trailing spaces
+        let text = plain_frame(0, None, 40, 12, Some("tea"));
+        let rows: Vec<&str> = text.lines().collect();
+        assert_eq!(rows.len(), 12);
+        assert_eq!(rows[3], text::center(&big_text("0:00")[0], 40).trim_end());
+        assert_eq!(rows[9], format!("{}tea", " ".repeat(18)));
+        assert!(rows[..3].iter().chain(&rows[10..]).all(|r| r.is_empty()));
+    }
+
+    #[test]
+    fn noted_frame_puts_notes_below_status() {
+        // Test: Notes start two rows under the status (16), then the rest is cleared
+        let notes = vec!["Keep".to_string(), "going".to_string()];
+        let out = noted_frame("1:30", None, &notes, 80, 24, None);
+        assert!(out.contains(&format!("{}{}{}", ansi::move_to(18, 1), ansi::CLEAR_LINE, text::center("Keep", 80))));
+        assert!(out.ends_with(&format!("{}{}{}{}", ansi::move_to(19, 1), ansi::CLEAR_LINE, text::center("going", 80), ansi::CLEAR_BELOW)));
+        // Test: Only as many notes as there are rows left are drawn
+        let many = vec!["x".to_string(); 20];
+        assert!(!noted_frame("1:30", None, &many, 80, 24, None).contains(&ansi::move_to(25, 1)));
+        assert_eq!(note_room("1:30", None, 80, 24), 7);
+        assert!(noted_frame("1:30", None, &many, 80, 24, None).contains(&ansi::move_to(24, 1)));
+    }
+
+    #[test]
+    fn duel_frame_splits_the_screen() {
+        // Test: Each time is centered in its own half, statuses on the same row
+        let out = duel_frame([("1:30", Some("Ana")), ("1:25", Some("Ben (PAUSED)"))], 80, 24);
+        let left = text::center(&big_text("1:30")[0], 40);
+        assert!(out.contains(&format!("{}{}{}{}", ansi::move_to(10, 1), ansi::CLEAR_LINE, left, text::center(&big_text("1:25")[0], 40).trim_end())));
+        assert!(out.ends_with(&format!("{}{}{}{}", ansi::move_to(16, 1), ansi::CLEAR_LINE, text::center("Ana", 40), text::center("Ben (PAUSED)", 40).trim_end())));
+        // Test: A half too narrow for big digits gets a plain line
+        assert!(duel_frame([("1:00:00", None), ("59:59", None)], 60, 24).contains("1:00:00"));
+    }
+
+    #[test]
+    fn header_goes_on_the_top_row() {
+        // Test: Drawn on row 1 when there's room, dropped when there isn't
+        assert_eq!(header("Pasta", 20, 24), format!("{}{}{}", ansi::move_to(1, 1), ansi::CLEAR_LINE, text::center("Pasta", 20)));
+        assert_eq!(header("Pasta", 20, 8), "");
+    }
+
+    #[test]
+    fn line_joins_the_parts_present() {
+        // Test: Missing parts leave no extra gaps
+        assert_eq!(line("1:30", None, None), "1:30");
+        assert_eq!(line("1:30", Some("0:30 elapsed"), Some("PAUSED")), "1:30  0:30 elapsed  PAUSED");
+        assert_eq!(line("1:30", None, Some("Work")), "1:30  Work");
+    }
+
+    #[test]
+    fn frame_draws_status_below_time() {
+        // Test: Status text is centered on the status row
+        let out = noted_frame("1:30", None, &[], 80, 24, Some("PAUSED"));
+        let status_row = format!("{}{}", ansi::move_to(16, 1), ansi::CLEAR_LINE);
+        assert!(out.ends_with(&format!("{}{}", status_row, text::center("PAUSED", 80))));
+    }
+}
@@ -0,0 +1,157 @@
+// src/schedule.rs
+// Schedule files for `timerterm run FILE`: a named routine of segments run
+// back to back, in the config file's TOML subset. Each [section] is one
+// segment, in the order they appear:
+//   name = "Leg day"
+//   [warmup]
+//   duration = "5:00"
+//   label = "Warm up"
+//   sound = "gong.wav"
+//   exec = "say squats"
+//   alert = "bell, desktop"
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::cli;
+use crate::config::{self, Entry, Value};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub label: String,          // Shown under the time, the section name unless set
+    pub duration: Duration,
+    pub sound: Option<PathBuf>, // Played when it ends, instead of the chime or alarm
+    pub exec: Option<String>,   // Started in the background when it ends
+    pub alert: Option<Vec<Channel>>, // Only these fire when it ends, unset is the sound
+}
+
+// One way a segment's end can be announced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Bell,    // The terminal bell alone
+    Sound,   // What ends it without an alert: its sound, the chime or the alarm
+    Desktop, // A desktop notification naming it
+}
+
+impl Channel {
+    pub const NAMES: [&'static str; 3] = ["bell", "sound", "desktop"];
+
+    pub fn parse(name: &str) -> Option<Channel> {
+        match name {
+            "bell" => Some(Channel::Bell),
+            "sound" => Some(Channel::Sound),
+            "desktop" => Some(Channel::Desktop),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+    pub name: Option<String>, // The header while it runs
+    pub segments: Vec<Segment>,
+}
+
+impl Schedule {
+    // Relative sound paths are taken from `dir`, the schedule file's
+    // directory, so a routine can be moved along with its sounds. ~/ is $HOME.
+    pub fn from_toml(text: &str, dir: &Path) -> Result<Schedule, String> {
+        let mut name = None;
+        // Segments by section name, a zero duration until one is given
+        let mut sections: Vec<(String, Segment)> = Vec::new();
+        for Entry { line, key, value } in config::parse_toml(text).map_err(|e| e.message().to_string())? {
+            let bad = |want: &str| Err(format!("line {}: {} must be {}", line, key, want));
+            let Some((section, field)) = key.split_once('.') else {
+                match (key.as_str(), value) {
+                    ("name", Value::Str(s)) => name = Some(s),
+                    ("name", _) => return bad("a string"),
+                    _ => return Err(format!("line {}: unknown key {}, segments go in [sections]", line, key)),
+                }
+                continue;
+            };
+            if !sections.iter().any(|(s, _)| s == section) {
+                let segment = Segment { label: section.to_string(), duration: Duration::ZERO, sound: None, exec: None, alert: None };
+                sections.push((section.to_string(), segment));
+            }
+            let (_, segment) = sections.iter_mut().find(|(s, _)| s == section).expect("pushed above");
+            match (field, value) {
+                ("label", Value::Str(s)) => segment.label = s,
+                ("label", _) => return bad("a string"),
+                ("duration", Value::Str(s)) => match cli::parse_duration(&s) {
+                    Ok(0) => return bad("longer than 0 seconds"),
+                    Ok(secs) => segment.duration = Duration::from_secs(secs as u64),
+                    Err(e) => return bad(&format!("a duration like \"25:00\" ({})", e)),
+                },
+                ("duration", Value::Int(0)) => return bad("longer than 0 seconds"),
+                ("duration", Value::Int(n)) if (1..=u32::MAX as i64).contains(&n) => segment.duration = Duration::from_secs(n as u64),
+                ("duration", _) => return bad("a duration like \"25:00\""),
+                ("sound", Value::Str(s)) => segment.sound = Some(dir.join(config::expand_home(&s))),
+                ("sound", _) => return bad("a file path"),
+                ("exec", Value::Str(s)) => segment.exec = Some(s),
+                ("exec", _) => return bad("a command"),
+                // "none" for a segment that ends quietly
+                ("alert", Value::Str(s)) if s.trim() == "none" => segment.alert = Some(Vec::new()),
+                ("alert", value) => {
+                    let channels = if let Value::Str(s) = value { s.split(',').map(|name| Channel::parse(name.trim())).collect() } else { None };
+                    match channels {
+                        Some(channels) => segment.alert = Some(channels),
+                        None => return bad(&format!("none or a list of {}, like \"bell, desktop\"", Channel::NAMES.join(", "))),
+                    }
+                }
+                _ => return Err(format!("line {}: unknown key {}", line, key)),
+            }
+        }
+        let segments = sections.into_iter().map(|(section, segment)| match segment.duration.is_zero() {
+            true => Err(format!("[{}] has no duration", section)),
+            false => Ok(segment),
+        }).collect::<Result<Vec<_>, String>>()?;
+        if segments.is_empty() { return Err("no segments, add a [section] with a duration".to_string()); }
+        Ok(Schedule { name, segments })
+    }
+
+    pub fn total(&self) -> Duration {
+        self.segments.iter().map(|s| s.duration).sum()
+    }
+}
+
+// Read and parse the file at `path`, errors lead with the path
+pub fn load(path: &Path) -> Result<Schedule, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+    Schedule::from_toml(&text, dir).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_toml_reads_segments_in_order() {
+        let text = "name = \"Legs\"\n[warmup]\nduration = \"5:00\"\nlabel = \"Warm up\"\nsound = \"gong.wav\"\n[squats]\nexec = \"say go\"\nduration = 40\nalert = \"bell, desktop\"\n";
+        let schedule = Schedule::from_toml(text, Path::new("/routines")).unwrap();
+        assert_eq!(schedule.name.as_deref(), Some("Legs"));
+        assert_eq!(schedule.segments, vec![
+            Segment { label: "Warm up".into(), duration: Duration::from_secs(300), sound: Some("/routines/gong.wav".into()), exec: None, alert: None },
+            Segment { label: "squats".into(), duration: Duration::from_secs(40), sound: None, exec: Some("say go".into()), alert: Some(vec![Channel::Bell, Channel::Desktop]) },
+        ]);
+        assert_eq!(schedule.total(), Duration::from_secs(340));
+    }
+
+    #[test]
+    fn from_toml_rejects_bad_segments() {
+        let err = |text: &str| Schedule::from_toml(text, Path::new("")).unwrap_err();
+        assert_eq!(err("[rest]\nlabel = \"Rest\"\n"), "[rest] has no duration");
+        assert_eq!(err("name = \"Empty\"\n"), "no segments, add a [section] with a duration");
+        // Test: A zero is too short, as a number or a string
+        assert_eq!(err("[rest]\nduration = 0\n"), "line 2: rest.duration must be longer than 0 seconds");
+        assert_eq!(err("[rest]\nduration = \"0\"\n"), "line 2: rest.duration must be longer than 0 seconds");
+        assert_eq!(err("[rest]\nduration = -5\n"), "line 2: rest.duration must be a duration like \"25:00\"");
+        assert_eq!(err("[rest]\nreps = 3\n"), "line 2: unknown key rest.reps");
+        assert_eq!(err("[rest]\nalert = \"bell, email\"\n"), "line 2: rest.alert must be none or a list of bell, sound, desktop, like \"bell, desktop\"");
+        let quiet = Schedule::from_toml("[rest]\nduration = 5\nalert = \"none\"\n", Path::new("")).unwrap();
+        assert_eq!(quiet.segments[0].alert, Some(vec![]));
+        assert_eq!(err("duration = 30\n"), "line 1: unknown key duration, segments go in [sections]");
+        // Test: Syntax errors come from the shared TOML reader
+        assert_eq!(err("[rest\n"), "line 1: unclosed section header");
+    }
+}
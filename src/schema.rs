@@ -0,0 +1,462 @@
+// src/schema.rs
+// JSON Schema for our machine-readable output (--status-file, --events-file,
+// --json and the library's wire types), generated by running sample values
+// through their serde Serialize impls. A new field shows up here by itself,
+// but only in the shapes the samples below cover, so each new optional
+// field or event needs a sample too.
+use std::time::Duration;
+
+use serde::ser::{self, Serialize, SerializeMap};
+
+use crate::event::{Status, Summary, TimerEvent};
+use crate::json;
+use crate::plan::{Phase, TimerPlan};
+use crate::timer::Direction;
+
+pub const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+// Which output formats `timerterm schema NAME` knows about
+pub const NAMES: &[&str] = &["plan", "status", "event", "summary"];
+
+// The structural "shape" of serialized values, merged across samples
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Null,
+    Bool,
+    Integer { unsigned: bool },
+    Number,
+    String,
+    Const(String),
+    Array(Option<Box<Shape>>),
+    Object { fields: Vec<(String, Shape)>, required: Vec<String> },
+    AnyOf(Vec<Shape>),
+}
+
+impl Shape {
+    // Shape of a single value
+    pub fn of<T: Serialize>(value: &T) -> Shape {
+        value.serialize(ShapeSerializer).expect("shape serializer never fails")
+    }
+
+    // Union of shapes of every sample: objects merge field-wise, a field is
+    // only required if every sample has it
+    pub fn of_all<T: Serialize>(samples: &[T]) -> Shape {
+        samples.iter().map(Shape::of).reduce(Shape::merge).unwrap_or(Shape::Null)
+    }
+
+    // Tagged union: one object schema per tag value, the tag pinned with "const"
+    pub fn tagged<T: Serialize>(tag: &str, samples: &[(&str, T)]) -> Shape {
+        let mut variants: Vec<(&str, Shape)> = Vec::new();
+        for (kind, sample) in samples {
+            let mut shape = Shape::of(sample);
+            if let Shape::Object { fields, .. } = &mut shape {
+                for (name, field) in fields.iter_mut() {
+                    if name == tag { *field = Shape::Const(kind.to_string()); }
+                }
+            }
+            match variants.iter_mut().find(|(k, _)| k == kind) {
+                Some((_, existing)) => *existing = existing.clone().merge(shape),
+                None => variants.push((kind, shape)),
+            }
+        }
+        Shape::AnyOf(variants.into_iter().map(|(_, s)| s).collect())
+    }
+
+    fn merge(self, other: Shape) -> Shape {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Shape::Object { fields: fa, required: ra }, Shape::Object { fields: fb, required: rb }) => {
+                let mut fields = fa;
+                for (name, shape) in fb {
+                    match fields.iter_mut().find(|(n, _)| *n == name) {
+                        Some((_, existing)) => *existing = existing.clone().merge(shape),
+                        None => fields.push((name, shape)),
+                    }
+                }
+                let required = ra.into_iter().filter(|r| rb.contains(r)).collect();
+                Shape::Object { fields, required }
+            }
+            (Shape::Array(a), Shape::Array(b)) => Shape::Array(match (a, b) {
+                (Some(a), Some(b)) => Some(Box::new(a.merge(*b))),
+                (a, b) => a.or(b),
+            }),
+            (Shape::AnyOf(mut a), b) => {
+                if !a.contains(&b) { a.push(b); }
+                Shape::AnyOf(a)
+            }
+            (a, b) => Shape::AnyOf(vec![a, b]),
+        }
+    }
+
+    fn type_name(&self) -> Option<&'static str> {
+        match self {
+            Shape::Null => Some("null"),
+            Shape::Bool => Some("boolean"),
+            Shape::Integer { .. } => Some("integer"),
+            Shape::Number => Some("number"),
+            Shape::String => Some("string"),
+            _ => None,
+        }
+    }
+}
+
+// Ordered "properties" object
+struct Properties<'a>(&'a [(String, Shape)]);
+
+impl Serialize for Properties<'_> {
+    fn serialize<S: ser::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut map = s.serialize_map(Some(self.0.len()))?;
+        for (name, shape) in self.0 { map.serialize_entry(name, shape)?; }
+        map.end()
+    }
+}
+
+impl Shape {
+    // Write this shape's schema keywords into an already-open JSON object
+    fn entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        match self {
+            Shape::Integer { unsigned: true } => {
+                map.serialize_entry("type", "integer")?;
+                map.serialize_entry("minimum", &0)
+            }
+            Shape::Const(value) => map.serialize_entry("const", value),
+            Shape::Array(items) => {
+                map.serialize_entry("type", "array")?;
+                match items {
+                    Some(items) => map.serialize_entry("items", items),
+                    None => Ok(()),
+                }
+            }
+            Shape::Object { fields, required } => {
+                map.serialize_entry("type", "object")?;
+                map.serialize_entry("properties", &Properties(fields))?;
+                map.serialize_entry("required", required)
+            }
+            Shape::AnyOf(shapes) => {
+                // Plain types collapse to {"type": [..]}, e.g. nullable strings
+                let names: Option<Vec<&str>> = shapes.iter().map(Shape::type_name).collect();
+                match names {
+                    Some(names) => map.serialize_entry("type", &names),
+                    None => map.serialize_entry("anyOf", shapes),
+                }
+            }
+            simple => map.serialize_entry("type", simple.type_name().unwrap_or("null")),
+        }
+    }
+}
+
+impl Serialize for Shape {
+    fn serialize<S: ser::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut map = s.serialize_map(None)?;
+        self.entries(&mut map)?;
+        map.end()
+    }
+}
+
+// ============ Output formats =============
+
+fn secs(s: u64) -> Duration { Duration::from_secs(s) }
+
+fn plan_shape() -> Shape {
+    Shape::of(&TimerPlan::new(vec![Phase::new("work", secs(1500))]))
+}
+
+fn status_shape() -> Shape {
+    Shape::of_all(&[
+        Status { label: Some("Tea".into()), direction: Direction::Down, duration: secs(180), elapsed: secs(60),
+            remaining: Some(secs(120)), paused: false, finished: false },
+        Status { label: None, direction: Direction::Up, duration: secs(0), elapsed: secs(60), remaining: None, paused: true, finished: false },
+    ])
+}
+
+// The sample after `event`, each variant with and without its optional
+// fields. There's no catch-all arm, so a new variant won't build until it
+// has a place in the chain.
+fn next_event_sample(event: &TimerEvent) -> Option<TimerEvent> {
+    Some(match event {
+        TimerEvent::Started { label: None, .. } => TimerEvent::Started { duration: secs(60), elapsed: secs(5), estimate: Some(secs(45)), label: Some("Pasta".into()), tag: Some("thesis:vim".into()), dir: Some("/home/me/thesis".into()) },
+        TimerEvent::Started { label: Some(_), .. } => TimerEvent::Paused { elapsed: secs(1), reason: None },
+        TimerEvent::Paused { reason: None, .. } => TimerEvent::Paused { elapsed: secs(1), reason: Some("coffee".into()) },
+        TimerEvent::Paused { reason: Some(_), .. } => TimerEvent::Resumed { elapsed: secs(1) },
+        TimerEvent::Resumed { .. } => TimerEvent::PhaseStarted { index: 0, label: "work".into() },
+        TimerEvent::PhaseStarted { .. } => TimerEvent::Adjusted { elapsed: secs(10), duration: secs(120) },
+        TimerEvent::Adjusted { .. } => TimerEvent::Interrupted { elapsed: secs(30), note: "Sam called".into() },
+        TimerEvent::Interrupted { .. } => TimerEvent::Reflected { note: "Fixed the parser".into() },
+        TimerEvent::Reflected { .. } => TimerEvent::Finished { elapsed: secs(60) },
+        TimerEvent::Finished { .. } => TimerEvent::Cancelled { elapsed: secs(1) },
+        TimerEvent::Cancelled { .. } => return None,
+    })
+}
+
+fn event_shape() -> Shape {
+    let first = TimerEvent::Started { duration: secs(60), elapsed: secs(0), estimate: None, label: None, tag: None, dir: None };
+    let tagged: Vec<(&str, TimerEvent)> = std::iter::successors(Some(first), next_event_sample).map(|e| (e.kind(), e)).collect();
+    Shape::tagged("event", &tagged)
+}
+
+fn summary_shape() -> Shape {
+    Shape::of_all(&[
+        Summary { label: Some("Tea".into()), planned: secs(180), elapsed: secs(180), completed: true, overtime: secs(42) },
+        Summary { label: None, planned: secs(180), elapsed: secs(10), completed: false, overtime: secs(0) },
+    ])
+}
+
+fn shape_named(name: &str) -> Option<Shape> {
+    match name {
+        "plan" => Some(plan_shape()),
+        "status" => Some(status_shape()),
+        "event" => Some(event_shape()),
+        "summary" => Some(summary_shape()),
+        _ => None,
+    }
+}
+
+// A standalone schema document for one format
+struct Document<'a> {
+    title: &'a str,
+    shape: Shape,
+}
+
+impl Serialize for Document<'_> {
+    fn serialize<S: ser::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut map = s.serialize_map(None)?;
+        map.serialize_entry("$schema", SCHEMA_DIALECT)?;
+        map.serialize_entry("title", self.title)?;
+        self.shape.entries(&mut map)?;
+        map.end()
+    }
+}
+
+// JSON Schema text for one named format, None if the name is unknown
+pub fn schema_json(name: &str) -> Option<String> {
+    let shape = shape_named(name)?;
+    json::to_string(&Document { title: name, shape }).ok()
+}
+
+// ============ Shape Serializer =============
+
+struct ShapeSerializer;
+
+struct ShapeSeq(Option<Shape>);
+
+struct ShapeObject {
+    fields: Vec<(String, Shape)>,
+    next_key: Option<String>,
+}
+
+impl ShapeObject {
+    fn new() -> Self { ShapeObject { fields: Vec::new(), next_key: None } }
+    fn finish(self) -> Shape {
+        let required = self.fields.iter().map(|(n, _)| n.clone()).collect();
+        Shape::Object { fields: self.fields, required }
+    }
+}
+
+type ShapeResult = Result<Shape, json::Error>;
+
+impl ser::Serializer for ShapeSerializer {
+    type Ok = Shape;
+    type Error = json::Error;
+    type SerializeSeq = ShapeSeq;
+    type SerializeTuple = ShapeSeq;
+    type SerializeTupleStruct = ShapeSeq;
+    type SerializeTupleVariant = ShapeSeq;
+    type SerializeMap = ShapeObject;
+    type SerializeStruct = ShapeObject;
+    type SerializeStructVariant = ShapeObject;
+
+    fn serialize_bool(self, _: bool) -> ShapeResult { Ok(Shape::Bool) }
+    fn serialize_i8(self, _: i8) -> ShapeResult { Ok(Shape::Integer { unsigned: false }) }
+    fn serialize_i16(self, _: i16) -> ShapeResult { Ok(Shape::Integer { unsigned: false }) }
+    fn serialize_i32(self, _: i32) -> ShapeResult { Ok(Shape::Integer { unsigned: false }) }
+    fn serialize_i64(self, _: i64) -> ShapeResult { Ok(Shape::Integer { unsigned: false }) }
+    fn serialize_u8(self, _: u8) -> ShapeResult { Ok(Shape::Integer { unsigned: true }) }
+    fn serialize_u16(self, _: u16) -> ShapeResult { Ok(Shape::Integer { unsigned: true }) }
+    fn serialize_u32(self, _: u32) -> ShapeResult { Ok(Shape::Integer { unsigned: true }) }
+    fn serialize_u64(self, _: u64) -> ShapeResult { Ok(Shape::Integer { unsigned: true }) }
+    fn serialize_f32(self, _: f32) -> ShapeResult { Ok(Shape::Number) }
+    fn serialize_f64(self, _: f64) -> ShapeResult { Ok(Shape::Number) }
+    fn serialize_char(self, _: char) -> ShapeResult { Ok(Shape::String) }
+    fn serialize_str(self, _: &str) -> ShapeResult { Ok(Shape::String) }
+    fn serialize_bytes(self, _: &[u8]) -> ShapeResult {
+        Ok(Shape::Array(Some(Box::new(Shape::Integer { unsigned: true }))))
+    }
+    fn serialize_none(self) -> ShapeResult { Ok(Shape::Null) }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> ShapeResult { value.serialize(self) }
+    fn serialize_unit(self) -> ShapeResult { Ok(Shape::Null) }
+    fn serialize_unit_struct(self, _: &'static str) -> ShapeResult { Ok(Shape::Null) }
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> ShapeResult {
+        Ok(Shape::Const(variant.to_string()))
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> ShapeResult {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self, _: &'static str, _: u32, variant: &'static str, value: &T,
+    ) -> ShapeResult {
+        let mut obj = ShapeObject::new();
+        obj.fields.push((variant.to_string(), value.serialize(ShapeSerializer)?));
+        Ok(obj.finish())
+    }
+    fn serialize_seq(self, _: Option<usize>) -> Result<ShapeSeq, json::Error> { Ok(ShapeSeq(None)) }
+    fn serialize_tuple(self, _: usize) -> Result<ShapeSeq, json::Error> { Ok(ShapeSeq(None)) }
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<ShapeSeq, json::Error> {
+        Ok(ShapeSeq(None))
+    }
+    fn serialize_tuple_variant(
+        self, _: &'static str, _: u32, _: &'static str, _: usize,
+    ) -> Result<ShapeSeq, json::Error> {
+        Ok(ShapeSeq(None))
+    }
+    fn serialize_map(self, _: Option<usize>) -> Result<ShapeObject, json::Error> { Ok(ShapeObject::new()) }
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<ShapeObject, json::Error> {
+        Ok(ShapeObject::new())
+    }
+    fn serialize_struct_variant(
+        self, _: &'static str, _: u32, _: &'static str, _: usize,
+    ) -> Result<ShapeObject, json::Error> {
+        Ok(ShapeObject::new())
+    }
+}
+
+impl ShapeSeq {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), json::Error> {
+        let shape = value.serialize(ShapeSerializer)?;
+        self.0 = Some(match self.0.take() {
+            Some(existing) => existing.merge(shape),
+            None => shape,
+        });
+        Ok(())
+    }
+    fn finish(self) -> ShapeResult { Ok(Shape::Array(self.0.map(Box::new))) }
+}
+
+impl ser::SerializeSeq for ShapeSeq {
+    type Ok = Shape;
+    type Error = json::Error;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, v: &T) -> Result<(), json::Error> { self.push(v) }
+    fn end(self) -> ShapeResult { self.finish() }
+}
+
+impl ser::SerializeTuple for ShapeSeq {
+    type Ok = Shape;
+    type Error = json::Error;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, v: &T) -> Result<(), json::Error> { self.push(v) }
+    fn end(self) -> ShapeResult { self.finish() }
+}
+
+impl ser::SerializeTupleStruct for ShapeSeq {
+    type Ok = Shape;
+    type Error = json::Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, v: &T) -> Result<(), json::Error> { self.push(v) }
+    fn end(self) -> ShapeResult { self.finish() }
+}
+
+impl ser::SerializeTupleVariant for ShapeSeq {
+    type Ok = Shape;
+    type Error = json::Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, v: &T) -> Result<(), json::Error> { self.push(v) }
+    fn end(self) -> ShapeResult { self.finish() }
+}
+
+impl ser::SerializeMap for ShapeObject {
+    type Ok = Shape;
+    type Error = json::Error;
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), json::Error> {
+        let key = json::to_string(key)?;
+        self.next_key = Some(key.trim_matches('"').to_string());
+        Ok(())
+    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), json::Error> {
+        let key = self.next_key.take().unwrap_or_default();
+        self.fields.push((key, value.serialize(ShapeSerializer)?));
+        Ok(())
+    }
+    fn end(self) -> ShapeResult { Ok(self.finish()) }
+}
+
+impl ser::SerializeStruct for ShapeObject {
+    type Ok = Shape;
+    type Error = json::Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, v: &T) -> Result<(), json::Error> {
+        self.fields.push((key.to_string(), v.serialize(ShapeSerializer)?));
+        Ok(())
+    }
+    fn end(self) -> ShapeResult { Ok(self.finish()) }
+}
+
+impl ser::SerializeStructVariant for ShapeObject {
+    type Ok = Shape;
+    type Error = json::Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, v: &T) -> Result<(), json::Error> {
+        ser::SerializeStruct::serialize_field(self, key, v)
+    }
+    fn end(self) -> ShapeResult { Ok(self.finish()) }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_schema_lists_phase_fields() {
+        // Test: Nested phase objects show up as array items with their fields
+        let text = schema_json("plan").unwrap();
+        assert!(text.starts_with(&format!(r#"{{"$schema":"{}","title":"plan""#, SCHEMA_DIALECT)));
+        assert!(text.contains(r#""items":{"type":"object","properties":{"label":{"type":"string"},"duration_ms":{"type":"integer","minimum":0}}"#));
+    }
+
+    #[test]
+    fn summary_label_is_nullable() {
+        // Test: Merging Some/None samples makes the label ["string","null"]
+        let text = schema_json("summary").unwrap();
+        assert!(text.contains(r#""label":{"type":["string","null"]}"#));
+        assert!(text.contains(r#""completed":{"type":"boolean"}"#));
+    }
+
+    #[test]
+    fn event_schema_is_tagged_union() {
+        // Test: One variant per kind, each pinned with "const"
+        let text = schema_json("event").unwrap();
+        for kind in crate::wire::EVENT_KINDS {
+            assert!(text.contains(&format!(r#""event":{{"const":"{}"}}"#, kind)), "missing {}", kind);
+        }
+    }
+
+    #[test]
+    fn status_remaining_is_optional() {
+        // Test: A stopwatch sample leaves remaining_ms out of "required"
+        let text = schema_json("status").unwrap();
+        assert!(text.contains(r#""remaining_ms":{"type":"integer","minimum":0}"#));
+        assert!(text.contains(r#""required":["label","direction","duration_ms","elapsed_ms","paused","finished"]"#));
+    }
+
+    #[test]
+    fn every_name_has_a_schema() {
+        for name in NAMES { assert!(schema_json(name).is_some()); }
+        assert_eq!(schema_json("bogus"), None);
+    }
+
+    #[test]
+    fn merge_keeps_only_common_required_fields() {
+        let a = Shape::Object { fields: vec![("x".into(), Shape::Bool)], required: vec!["x".into()] };
+        let b = Shape::Object { fields: vec![("y".into(), Shape::Bool)], required: vec!["y".into()] };
+        match a.merge(b) {
+            Shape::Object { fields, required } => {
+                assert_eq!(fields.len(), 2);
+                assert!(required.is_empty());
+            }
+            other => panic!("expected object, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn real_output_uses_schema_fields() {
+        // Test: Every key in real serialized output is a schema property
+        let summary = Summary { label: None, planned: secs(1), elapsed: secs(1), completed: true, overtime: secs(5) };
+        let out = json::to_string(&summary).unwrap();
+        let Shape::Object { fields, .. } = summary_shape() else { panic!("summary should be an object") };
+        for (name, _) in fields { assert!(out.contains(&format!("\"{}\":", name))); }
+    }
+}
@@ -0,0 +1,161 @@
+// src/screenshot.rs
+// --screenshot FILE: the final screen, saved as plain text, or for .png files
+// (with the png feature) an image of the big digits. The PNG is written by hand:
+// grayscale pixels in uncompressed deflate blocks, so no image crates are needed.
+use std::io;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Png,
+}
+
+// .png (any case) is an image, anything else gets the text dump
+pub fn format_for(path: &Path) -> Format {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("png") => Format::Png,
+        _ => Format::Text,
+    }
+}
+
+// `screen` is the plain text frame, `time` the big time it shows ("0:00")
+pub fn save(path: &Path, screen: &str, time: &str) -> io::Result<()> {
+    match format_for(path) {
+        Format::Text => std::fs::write(path, screen),
+        #[cfg(feature = "png")]
+        Format::Png => {
+            let (width, height, pixels) = rasterize(time, 8);
+            std::fs::write(path, encode_png(width, height, &pixels))
+        }
+        #[cfg(not(feature = "png"))]
+        Format::Png => {
+            let _ = time;
+            Err(io::Error::new(io::ErrorKind::Unsupported, "PNG screenshots need the png feature"))
+        }
+    }
+}
+
+// Each '#' of the big digit font becomes a `scale` pixel white square on
+// black, with a one glyph-cell margin all round
+#[cfg(feature = "png")]
+pub fn rasterize(time: &str, scale: usize) -> (usize, usize, Vec<u8>) {
+    let lines = crate::render::big_text(time);
+    let cells_wide = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) + 2;
+    let cells_high = lines.len() + 2;
+    let (width, height) = (cells_wide * scale, cells_high * scale);
+    let mut pixels = vec![0u8; width * height];
+    for (row, line) in lines.iter().enumerate() {
+        for (col, _) in line.chars().enumerate().filter(|&(_, c)| c == '#') {
+            for y in (row + 1) * scale..(row + 2) * scale {
+                pixels[y * width + (col + 1) * scale..][..scale].fill(0xff);
+            }
+        }
+    }
+    (width, height, pixels)
+}
+
+// 8-bit grayscale PNG, `pixels` row by row
+#[cfg(feature = "png")]
+pub fn encode_png(width: usize, height: usize, pixels: &[u8]) -> Vec<u8> {
+    let mut ihdr = Vec::new();
+    ihdr.extend_from_slice(&(width as u32).to_be_bytes());
+    ihdr.extend_from_slice(&(height as u32).to_be_bytes());
+    ihdr.extend_from_slice(&[8, 0, 0, 0, 0]); // Bit depth 8, grayscale, no interlace
+    // Every scanline starts with filter type 0 (none)
+    let raw: Vec<u8> = pixels.chunks(width.max(1)).flat_map(|row| std::iter::once(0).chain(row.iter().copied())).collect();
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    chunk(&mut png, b"IHDR", &ihdr);
+    chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+#[cfg(feature = "png")]
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+// zlib stream of uncompressed deflate blocks (at most 65535 bytes each)
+#[cfg(feature = "png")]
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = if data.is_empty() { vec![&[]] } else { data.chunks(65535).collect() };
+    for (i, block) in blocks.iter().enumerate() {
+        out.push((i + 1 == blocks.len()) as u8); // BFINAL on the last, BTYPE 00
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+#[cfg(feature = "png")]
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+#[cfg(feature = "png")]
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_follows_extension() {
+        // Test: Only .png is an image, whatever its case
+        assert_eq!(format_for(Path::new("out.png")), Format::Png);
+        assert_eq!(format_for(Path::new("OUT.PNG")), Format::Png);
+        assert_eq!(format_for(Path::new("out.txt")), Format::Text);
+        assert_eq!(format_for(Path::new("result")), Format::Text);
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn checksums_match_known_values() {
+        // Test: Standard check values for "123456789"
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"123456789"), 0x091e_01de);
+        // Test: Blocks split at 65535 bytes, only the last is final
+        let z = zlib_stored(&[7u8; 70000]);
+        assert_eq!(z.len(), 2 + 2 * 5 + 70000 + 4);
+        assert_eq!((z[2], z[2 + 5 + 65535]), (0, 1));
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn png_has_header_and_pixels() {
+        // Test: "1" is 5x5 cells plus a margin, lit where the glyph has '#'
+        let (width, height, pixels) = rasterize("1", 2);
+        assert_eq!((width, height), (14, 14));
+        assert_eq!(pixels[2 * width + 6], 0xff); // Top of the 1, cell (0, 2)
+        assert_eq!(pixels[2 * width + 2], 0);
+        let png = encode_png(width, height, &pixels);
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x0e\0\0\0\x0e"));
+        assert!(png.ends_with(b"\0\0\0\0IEND\xae\x42\x60\x82"));
+    }
+}
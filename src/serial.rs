@@ -0,0 +1,63 @@
+// src/serial.rs
+// --serial DEVICE: the time as it's shown, a line each time it changes, for
+// a seven-segment display or an Arduino on a serial port. The port is set to
+// raw at serial.baud (9600 unless set) and each line is serial.format with
+// {time} ("4:59") and {secs} (299) filled in, "{time}" by default. Anything
+// that isn't a terminal device, a FIFO say, is written as it is.
+use std::cell::Cell;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+
+use crate::platform;
+use crate::render::format_duration;
+
+pub const DEFAULT_BAUD: u32 = 9600;
+// What serial.baud may be, the rates any port can do
+pub const BAUDS: [u32; 8] = [1200, 2400, 4800, 9600, 19200, 38400, 57600, 115200];
+pub const DEFAULT_FORMAT: &str = "{time}";
+
+pub struct Serial {
+    file: File,
+    format: String,
+    sent: Cell<Option<u64>>, // Seconds last written, to skip repeats
+}
+
+impl Serial {
+    pub fn open(device: &str, baud: u32, format: &str) -> Result<Serial, String> {
+        let file = OpenOptions::new().write(true).open(device).map_err(|e| format!("{}: {}", device, e))?;
+        platform::set_serial(&file, baud).map_err(|e| format!("{}: {}", device, e))?;
+        Ok(Serial { file, format: format.to_string(), sent: Cell::new(None) })
+    }
+
+    // Best effort, a display unplugged mid-run shouldn't stop the timer
+    pub fn show(&self, secs: u64) {
+        if self.sent.replace(Some(secs)) == Some(secs) { return; }
+        let _ = (&self.file).write_all(line(&self.format, secs).as_bytes());
+    }
+}
+
+pub fn line(format: &str, secs: u64) -> String {
+    format!("{}\n", format.replace("{time}", &format_duration(secs)).replace("{secs}", &secs.to_string()))
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_fills_in_the_time() {
+        assert_eq!(line(DEFAULT_FORMAT, 299), "4:59\n");
+        assert_eq!(line("T{secs} {time}", 3661), "T3661 1:01:01\n");
+    }
+
+    #[test]
+    fn show_skips_repeats() {
+        let path = std::env::temp_dir().join(format!("timeterm-serial-{}", std::process::id()));
+        std::fs::write(&path, "").unwrap();
+        let serial = Serial::open(path.to_str().unwrap(), DEFAULT_BAUD, DEFAULT_FORMAT).unwrap();
+        for secs in [3, 3, 2, 2, 1] { serial.show(secs); }
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "0:03\n0:02\n0:01\n");
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,21 +1,125 @@
 // src/signal.rs
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::Mutex;
+use std::thread;
 // use std::sync::Arc;
 
-static SHOULD_EXIT: AtomicBool = AtomicBool::new(false);
+use crate::platform;
 
-extern "C" fn sigint_handler(_: i32) {
-    SHOULD_EXIT.store(true, Ordering::Relaxed);
+// Number of the last exit signal received, 0 for none
+static RECEIVED: AtomicI32 = AtomicI32::new(0);
+// Ctrl+C held back by hold_interrupts, 0 for none
+static HELD: AtomicI32 = AtomicI32::new(0);
+static HOLDING: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn exit_handler(sig: i32) {
+    let flag = if sig == platform::SIGINT && HOLDING.load(Ordering::Relaxed) { &HELD } else { &RECEIVED };
+    flag.store(sig, Ordering::Relaxed);
+    platform::wake();
+}
+
+// While on, Ctrl+C is kept for later rather than ending the run, for a
+// --strict-breaks break. Turning it off delivers one that came meanwhile.
+// SIGTERM and SIGHUP still end it at once.
+pub fn hold_interrupts(on: bool) {
+    HOLDING.store(on, Ordering::Relaxed);
+    if on { return; }
+    let held = HELD.swap(0, Ordering::Relaxed);
+    if held != 0 { RECEIVED.store(held, Ordering::Relaxed); }
+}
+
+// Ctrl+C, a supervisor's SIGTERM and a closed terminal's SIGHUP all just
+// flag the main loop, so it can restore the terminal on its way out.
+// On Windows these are Ctrl+C/Ctrl+Break, logoff/shutdown and closing the console.
+pub fn register_exit_handlers() {
+    platform::catch(&[platform::SIGINT, platform::SIGTERM, platform::SIGHUP], exit_handler);
 }
 
-pub fn register_sigint_handler() {
-    unsafe {
-        libc::signal(libc::SIGINT, sigint_handler as libc::sighandler_t);
+// Ctrl+Z (SIGTSTP) and `fg` (SIGCONT)
+static SUSPEND_REQUESTED: AtomicBool = AtomicBool::new(false);
+static CONTINUED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn job_control_handler(sig: i32) {
+    match sig {
+        platform::SIGTSTP => SUSPEND_REQUESTED.store(true, Ordering::Relaxed),
+        _ => CONTINUED.store(true, Ordering::Relaxed),
     }
+    platform::wake();
+}
+
+// Once SIGTSTP is caught the process no longer stops by itself: the main
+// loop restores the terminal and calls stop_self. Only register this while
+// something polls take_suspend, or Ctrl+Z does nothing.
+pub fn register_job_control_handlers() {
+    platform::catch(&[platform::SIGTSTP, platform::SIGCONT], job_control_handler);
+}
+
+static RESIZED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn resize_handler(_: i32) {
+    RESIZED.store(true, Ordering::Relaxed);
+    platform::wake();
+}
+
+pub fn register_resize_handler() {
+    platform::catch(&[platform::SIGWINCH], resize_handler);
+}
+
+type Waker = Box<dyn Fn() + Send>;
+static WAKER: Mutex<Option<Waker>> = Mutex::new(None);
+
+// Call `waker` on an ordinary thread after each caught signal (or burst of
+// them), so a loop asleep until its next tick can come and check the flags.
+// A later call replaces the waker.
+pub fn on_wake(waker: impl Fn() + Send + 'static) {
+    platform::init_wake();
+    let mut slot = WAKER.lock().unwrap_or_else(|e| e.into_inner());
+    if slot.replace(Box::new(waker)).is_some() { return; } // Listener already running
+    thread::spawn(|| {
+        while platform::wait_for_wake() {
+            if let Some(waker) = WAKER.lock().unwrap_or_else(|e| e.into_inner()).as_ref() { waker(); }
+        }
+    });
+}
+
+// True once per burst of SIGWINCH, however many arrived since the last check
+pub fn take_resized() -> bool {
+    RESIZED.swap(false, Ordering::Relaxed)
+}
+
+// True once per Ctrl+Z
+pub fn take_suspend() -> bool {
+    SUSPEND_REQUESTED.swap(false, Ordering::Relaxed)
+}
+
+// True once per SIGCONT, including ones after an uncatchable SIGSTOP
+pub fn take_continued() -> bool {
+    CONTINUED.swap(false, Ordering::Relaxed)
+}
+
+// Stop like an unhandled SIGTSTP would, returning after SIGCONT
+pub fn stop_self() {
+    platform::stop_self();
 }
 
 pub fn should_exit() -> bool {
-    SHOULD_EXIT.load(Ordering::Relaxed)
+    received().is_some()
+}
+
+pub fn received() -> Option<i32> {
+    match RECEIVED.load(Ordering::Relaxed) {
+        0 => None,
+        sig => Some(sig),
+    }
+}
+
+pub fn name(sig: i32) -> &'static str {
+    match sig {
+        platform::SIGINT => "SIGINT",
+        platform::SIGTERM => "SIGTERM",
+        platform::SIGHUP => "SIGHUP",
+        _ => "a signal",
+    }
 }
 
 // ============ Unit Tests =============
@@ -26,26 +130,63 @@ mod tests {
     #[test]
     fn should_exit_initially_false() {
         // Reset the flag for clean test
-        SHOULD_EXIT.store(false, Ordering::Relaxed);
-        assert_eq!(should_exit(), false);
+        RECEIVED.store(0, Ordering::Relaxed);
+        assert!(!should_exit());
     }
 
     #[test]
     fn signal_handler_sets_flag() {
         // Reset the flag for clean test
-        SHOULD_EXIT.store(false, Ordering::Relaxed);
+        RECEIVED.store(0, Ordering::Relaxed);
         // Call signal handler directly
-        sigint_handler(libc::SIGINT);
-        // Verify flag is set
-        assert_eq!(should_exit(), true);
+        exit_handler(platform::SIGTERM);
+        // Verify flag is set, remembering which signal it was
+        assert!(should_exit());
+        assert_eq!(received().map(name), Some("SIGTERM"));
+        // Test: A held Ctrl+C lands once let go of
+        RECEIVED.store(0, Ordering::Relaxed);
+        hold_interrupts(true);
+        exit_handler(platform::SIGINT);
+        assert_eq!(HELD.load(Ordering::Relaxed), platform::SIGINT);
+        hold_interrupts(false);
+        assert_eq!(received().map(name), Some("SIGINT"));
+    }
+
+    #[test]
+    fn job_control_flags_are_taken_once() {
+        // Test: Each Ctrl+Z and fg is seen exactly once
+        job_control_handler(platform::SIGTSTP);
+        assert!(take_suspend());
+        assert!(!take_suspend());
+        job_control_handler(platform::SIGCONT);
+        assert!(take_continued());
+        assert!(!take_continued());
+    }
+
+    #[test]
+    fn resizes_coalesce() {
+        // Test: Several SIGWINCH before a redraw mean one relayout
+        resize_handler(platform::SIGWINCH);
+        resize_handler(platform::SIGWINCH);
+        assert!(take_resized());
+        assert!(!take_resized());
+    }
+
+    #[test]
+    fn handlers_wake_the_waker() {
+        // Test: A caught signal reaches the waker on its own thread
+        let (tx, rx) = std::sync::mpsc::channel();
+        on_wake(move || { let _ = tx.send(()); });
+        resize_handler(platform::SIGWINCH);
+        assert!(rx.recv_timeout(std::time::Duration::from_secs(2)).is_ok());
+        take_resized();
     }
 
     #[test]
     fn register_handler_returns_ok_no_panic() {
         // Harder test since it's a system call,
         // but we can at least verify no panics
-        register_sigint_handler();
+        register_exit_handlers();
         // If we get here, it didn't panic
     }
 }
-
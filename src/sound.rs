@@ -0,0 +1,74 @@
+// src/sound.rs
+// Alarm playback through whatever command-line player the system has:
+// afplay on macOS, paplay (PulseAudio/PipeWire) then aplay (ALSA) elsewhere.
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+// How a finished timer or phase change is announced
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alarm {
+    pub file: Option<PathBuf>, // Sound to play, the bell is the fallback
+    pub volume: u8,            // Percent, 0-100
+    pub bell: bool,            // Terminal bell when there's no file or it can't be played
+    pub chime: Option<PathBuf>, // Between --repeat cycles, the bell is the fallback
+}
+
+impl Alarm {
+    pub fn silent() -> Self {
+        Alarm { file: None, volume: 0, bell: false, chime: None }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn players(file: &Path, volume: u8) -> Vec<(&'static str, Vec<String>)> {
+    let file = file.display().to_string();
+    vec![("afplay", vec!["-v".to_string(), format!("{:.2}", volume as f64 / 100.0), file])]
+}
+
+#[cfg(not(target_os = "macos"))]
+fn players(file: &Path, volume: u8) -> Vec<(&'static str, Vec<String>)> {
+    let file = file.display().to_string();
+    // paplay's 65536 is 100%, aplay has no volume control at all
+    let paplay_volume = format!("--volume={}", 65536 * volume as u32 / 100);
+    vec![("paplay", vec![paplay_volume, file.clone()]), ("aplay", vec!["-q".to_string(), file])]
+}
+
+// Start playing in the background with the first player that exists.
+// Errors only when none of them could be started.
+pub fn play(file: &Path, volume: u8) -> io::Result<()> {
+    let mut last = io::Error::new(io::ErrorKind::NotFound, "no audio player found");
+    for (program, args) in players(file, volume) {
+        let spawned = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match spawned {
+            Ok(mut child) => {
+                // Reap it without blocking the display
+                thread::spawn(move || child.wait());
+                return Ok(());
+            }
+            Err(e) => last = e,
+        }
+    }
+    Err(last)
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn players_scale_volume() {
+        // Test: 50% is half of paplay's 65536, aplay gets the file only
+        let players = players(Path::new("/tmp/ding.wav"), 50);
+        assert_eq!(players[0], ("paplay", vec!["--volume=32768".to_string(), "/tmp/ding.wav".to_string()]));
+        assert_eq!(players[1], ("aplay", vec!["-q".to_string(), "/tmp/ding.wav".to_string()]));
+    }
+}
@@ -0,0 +1,88 @@
+// src/sounds.rs
+// The sound library, $XDG_DATA_HOME/timerterm/sounds (~/.local/share when
+// unset). `timerterm sounds add FILE` copies a file in, named after it
+// without the extension, and that name then works wherever a sound file
+// does: --sound chime, or sound.file = "chime" in the config.
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub fn dir() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_DATA_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".local").join("share"),
+    };
+    Some(base.join("timerterm").join("sounds"))
+}
+
+// The sounds in `dir` by name, with their files, sorted. A missing library is empty.
+pub fn list(dir: &Path) -> io::Result<Vec<(String, PathBuf)>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut sounds = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let name = path.file_stem().map(|stem| stem.to_string_lossy().into_owned());
+        if let (true, Some(name)) = (path.is_file(), name) { sounds.push((name, path)); }
+    }
+    sounds.sort();
+    Ok(sounds)
+}
+
+pub fn find(dir: &Path, name: &str) -> Option<PathBuf> {
+    list(dir).ok()?.into_iter().find(|(sound, _)| sound == name).map(|(_, path)| path)
+}
+
+// Copy `file` in, returning the name it goes by. A name already taken is
+// an error rather than a silent replacement.
+pub fn add(dir: &Path, file: &Path) -> Result<String, String> {
+    let name = file.file_stem().map(|stem| stem.to_string_lossy().into_owned()).filter(|name| !name.is_empty())
+        .ok_or_else(|| format!("{} has no name to go by", file.display()))?;
+    if !file.is_file() { return Err(format!("{} isn't a file", file.display())); }
+    if let Some(taken) = find(dir, &name) {
+        return Err(format!("there's already a sound called '{}', {}", name, taken.display()));
+    }
+    std::fs::create_dir_all(dir).map_err(|e| format!("couldn't create {}: {}", dir.display(), e))?;
+    let to = dir.join(file.file_name().unwrap_or_default());
+    std::fs::copy(file, &to).map_err(|e| format!("couldn't copy {} to {}: {}", file.display(), to.display(), e))?;
+    Ok(name)
+}
+
+// A sound setting as given: a file that exists, else a bare name from the
+// library, else left for the caller to report as not found
+pub fn resolve(sound: &Path) -> PathBuf {
+    // Only a plain name like "chime" can be one
+    let bare = sound.components().count() == 1 && sound.extension().is_none();
+    if sound.is_file() || !bare { return sound.to_path_buf(); }
+    sound.to_str().and_then(|name| find(&dir()?, name)).unwrap_or_else(|| sound.to_path_buf())
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_then_list_and_find_by_name() {
+        let root = std::env::temp_dir().join(format!("timeterm-sounds-{}", std::process::id()));
+        let library = root.join("sounds");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("ding.wav"), b"RIFF").unwrap();
+        std::fs::write(root.join("gong.ogg"), b"OggS").unwrap();
+        // Test: No library yet is no sounds, not an error
+        assert_eq!(list(&library).unwrap(), vec![]);
+        assert_eq!(add(&library, &root.join("gong.ogg")), Ok("gong".to_string()));
+        assert_eq!(add(&library, &root.join("ding.wav")), Ok("ding".to_string()));
+        let names: Vec<String> = list(&library).unwrap().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["ding", "gong"]);
+        assert_eq!(find(&library, "gong"), Some(library.join("gong.ogg")));
+        assert_eq!(find(&library, "bell"), None);
+        // Test: A second ding is refused, whatever its extension
+        std::fs::write(root.join("ding.ogg"), b"OggS").unwrap();
+        assert!(add(&library, &root.join("ding.ogg")).unwrap_err().starts_with("there's already a sound called 'ding'"));
+        assert!(add(&library, &root.join("missing.wav")).is_err());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
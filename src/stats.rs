@@ -0,0 +1,129 @@
+// src/stats.rs
+// `timerterm stats`: totals over the whole history log, how close the runs
+// given an --estimate came to it, and with --by-project the time spent in
+// each project, longest first:
+//   ~/thesis      2:30:00, 6 runs
+//   ~/timerterm   1:20:00, 4 runs
+//   (no project)    15:00, 2 runs
+use crate::render::format_duration;
+use crate::text;
+use crate::today::{Outcome, Session};
+
+// The report's lines. `home` shortens project dirs under it to "~/...".
+pub fn report(sessions: &[Session], by_project: bool, home: Option<&str>) -> Vec<String> {
+    let count = |outcome| sessions.iter().filter(|s| s.outcome == outcome).count();
+    let mut lines = vec![format!("{}, {} completed, {} cancelled: {} in all",
+        runs(sessions.len()), count(Outcome::Completed), count(Outcome::Cancelled), format_duration(spent(sessions) as u64))];
+    if let Some(accuracy) = accuracy(sessions) { lines.push(accuracy); }
+    if !by_project { return lines; }
+
+    let mut projects: Vec<(Option<&str>, u32, usize)> = Vec::new(); // Dir, secs and runs
+    for session in sessions {
+        let dir = session.dir.as_deref();
+        let at = projects.iter().position(|p| p.0 == dir).unwrap_or_else(|| { projects.push((dir, 0, 0)); projects.len() - 1 });
+        projects[at].1 += session.elapsed.unwrap_or(0);
+        projects[at].2 += 1;
+    }
+    projects.sort_by_key(|p| std::cmp::Reverse(p.1));
+    let names: Vec<String> = projects.iter().map(|p| p.0.map_or("(no project)".to_string(), |dir| shorten(dir, home))).collect();
+    let times: Vec<String> = projects.iter().map(|p| format_duration(p.1 as u64)).collect();
+    let name_width = names.iter().map(|n| text::display_width(n)).max().unwrap_or(0);
+    let time_width = times.iter().map(|t| t.len()).max().unwrap_or(0);
+    lines.push(String::new());
+    for ((name, time), project) in names.iter().zip(&times).zip(&projects) {
+        lines.push(format!("{}  {:>width$}, {}", text::pad_right(name, name_width), time, runs(project.2), width = time_width));
+    }
+    lines
+}
+
+// "3 runs estimated: median 1.20x the estimate, 1 within ±10%", over the
+// ended runs that had an estimate. Quitting one early is when it was done,
+// so cancelled runs count too.
+fn accuracy(sessions: &[Session]) -> Option<String> {
+    let mut ratios: Vec<f64> = sessions.iter()
+        .filter_map(|s| Some(s.elapsed? as f64 / s.estimate? as f64))
+        .collect();
+    if ratios.is_empty() { return None; }
+    ratios.sort_by(f64::total_cmp);
+    let mid = ratios.len() / 2;
+    let median = if ratios.len().is_multiple_of(2) { (ratios[mid - 1] + ratios[mid]) / 2.0 } else { ratios[mid] };
+    let within = ratios.iter().filter(|&&r| (r - 1.0).abs() <= 0.1).count();
+    Some(format!("{} estimated: median {:.2}x the estimate, {} within ±10%", runs(ratios.len()), median, within))
+}
+
+// Secs counted by the runs that have ended, running ones have no total yet
+fn spent(sessions: &[Session]) -> u32 {
+    sessions.iter().filter_map(|s| s.elapsed).sum()
+}
+
+fn runs(n: usize) -> String {
+    format!("{} {}", n, if n == 1 { "run" } else { "runs" })
+}
+
+// "/home/me/thesis" as "~/thesis"
+fn shorten(dir: &str, home: Option<&str>) -> String {
+    match home.and_then(|home| dir.strip_prefix(home.trim_end_matches('/'))) {
+        Some("") => "~".to_string(),
+        Some(rest) if rest.starts_with('/') => format!("~{}", rest),
+        _ => dir.to_string(),
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(dir: Option<&str>, outcome: Outcome, elapsed: Option<u32>) -> Session {
+        Session { start: 0, end: None, outcome, label: None, dir: dir.map(String::from), elapsed, estimate: None }
+    }
+
+    #[test]
+    fn report_totals_every_run() {
+        // Test: Running runs are counted but add no time yet
+        let sessions = [
+            run(Some("/home/me/thesis"), Outcome::Completed, Some(1500)),
+            run(Some("/home/me/thesis"), Outcome::Cancelled, Some(300)),
+            run(None, Outcome::Running, None),
+        ];
+        assert_eq!(report(&sessions, false, None), ["3 runs, 1 completed, 1 cancelled: 30:00 in all"]);
+        assert_eq!(report(&[], false, None), ["0 runs, 0 completed, 0 cancelled: 0:00 in all"]);
+    }
+
+    #[test]
+    fn estimated_runs_are_compared_with_how_long_they_took() {
+        let estimated = |outcome, elapsed, estimate| Session { estimate: Some(estimate), ..run(None, outcome, elapsed) };
+        let mut sessions = vec![
+            estimated(Outcome::Completed, Some(3000), 2000), // 1.5x
+            estimated(Outcome::Cancelled, Some(1900), 2000), // Quit when done, 0.95x
+            estimated(Outcome::Running, None, 600),          // Not over yet
+            run(None, Outcome::Completed, Some(60)),
+        ];
+        assert_eq!(report(&sessions, false, None)[1], "2 runs estimated: median 1.23x the estimate, 1 within ±10%");
+        // Test: The middle one of an odd count
+        sessions.push(estimated(Outcome::Completed, Some(600), 600));
+        assert_eq!(accuracy(&sessions).unwrap(), "3 runs estimated: median 1.00x the estimate, 2 within ±10%");
+        // Test: No line without estimates
+        assert_eq!(report(&sessions[3..4], false, None).len(), 1);
+    }
+
+    #[test]
+    fn by_project_lists_the_longest_first() {
+        let sessions = [
+            run(Some("/srv/timerterm"), Outcome::Completed, Some(600)),
+            run(None, Outcome::Completed, Some(60)),
+            run(Some("/home/me/thesis"), Outcome::Completed, Some(3600)),
+            run(Some("/home/me/thesis"), Outcome::Cancelled, Some(1800)),
+        ];
+        assert_eq!(report(&sessions, true, Some("/home/me")), [
+            "4 runs, 3 completed, 1 cancelled: 1:41:00 in all",
+            "",
+            "~/thesis        1:30:00, 2 runs",
+            "/srv/timerterm    10:00, 1 run",
+            "(no project)       1:00, 1 run",
+        ]);
+        // Test: Only whole directory names are shortened
+        assert_eq!(shorten("/home/me", Some("/home/me/")), "~");
+        assert_eq!(shorten("/home/meg/notes", Some("/home/me")), "/home/meg/notes");
+    }
+}
@@ -0,0 +1,196 @@
+// src/template.rs
+// Presets that stand for a whole command line rather than a duration, with
+// {placeholders} filled in at launch:
+//   [presets]
+//   review = "{minutes}m --label 'Review {pr}'"
+// `timerterm preset review --set pr=42` asks for the minutes and runs
+// `timerterm countdown --name review 25m --label 'Review 42'`. Words split
+// like a shell's, on spaces outside '...' or "...", before anything is
+// filled in, so a value with spaces stays one argument. {{ and }} are
+// literal braces.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    pub source: String,  // As written, for `timerterm presets`
+    words: Vec<String>,  // Split, placeholders still in
+}
+
+impl Template {
+    // A preset value is a template once it has a placeholder or more than
+    // one word, a bare "25:00" stays a duration
+    pub fn is_template(value: &str) -> bool {
+        value.contains('{') || value.trim().contains(char::is_whitespace)
+    }
+
+    pub fn parse(source: &str) -> Result<Template, String> {
+        let words = split_words(source)?;
+        for word in &words { fill(word, &mut |_| Some(String::new()))?; }
+        Ok(Template { source: source.to_string(), words })
+    }
+
+    // Each placeholder's name once, in the order they first appear
+    pub fn placeholders(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for word in &self.words {
+            let _ = fill(word, &mut |name| {
+                if !names.iter().any(|n| n == name) { names.push(name.to_string()); }
+                Some(String::new())
+            });
+        }
+        names
+    }
+
+    // The arguments with every placeholder filled from `values`
+    pub fn expand(&self, values: &[(String, String)]) -> Result<Vec<String>, String> {
+        let mut lookup = |name: &str| values.iter().find(|(n, _)| n == name).map(|(_, v)| v.clone());
+        self.words.iter().map(|word| fill(word, &mut lookup)).collect()
+    }
+}
+
+// Rewrite `timerterm preset NAME [--set VAR=VALUE]... [OPTIONS]` for a
+// template NAME into the countdown it stands for, asking for each value not
+// --set. The options come after the template's so they win. Other command
+// lines, and asking for help, are left alone. Nothing is asked with
+// --no-confirm.
+pub fn expand_preset(args: &mut Vec<String>, templates: &[(String, Template)], mut ask: impl FnMut(&str) -> Option<String>) -> Result<(), String> {
+    if args.get(1).map(String::as_str) != Some("preset") { return Ok(()); }
+    let Some((name, template)) = args.get(2).and_then(|name| templates.iter().find(|(n, _)| n == name)) else { return Ok(()) };
+    // Help is about the preset command, not anything to ask for
+    if args[3..].iter().any(|a| a == "-h" || a == "--help") { return Ok(()); }
+    let mut rest = args[3..].iter().cloned();
+    let (mut options, mut values) = (Vec::new(), Vec::new());
+    while let Some(arg) = rest.next() {
+        let set = match arg.strip_prefix("--set=") {
+            Some(set) => set.to_string(),
+            None if arg == "--set" => rest.next().ok_or("--set needs a value like 'pr=42'")?,
+            None => { options.push(arg); continue }
+        };
+        let (var, value) = set.split_once('=').ok_or_else(|| format!("--set needs a value like 'pr=42', not '{}'", set))?;
+        if !template.placeholders().iter().any(|p| p == var) { return Err(format!("preset '{}' has no {{{}}}", name, var)); }
+        values.push((var.to_string(), value.to_string()));
+    }
+    let no_confirm = options.iter().any(|o| o == "--no-confirm");
+    for var in template.placeholders() {
+        if values.iter().any(|(v, _)| *v == var) { continue; }
+        let value = if no_confirm { None } else { ask(&var) }
+            .ok_or_else(|| format!("preset '{}' needs {{{}}}, give it with --set {}=VALUE", name, var, var))?;
+        values.push((var, value));
+    }
+    let expanded = std::iter::once(args[0].clone())
+        .chain(["countdown".to_string(), "--name".to_string(), name.clone()])
+        .chain(template.expand(&values)?)
+        .chain(options)
+        .collect();
+    *args = expanded;
+    Ok(())
+}
+
+// What replaces `{name}` in `word`, None leaves it an error
+fn fill(word: &str, value: &mut impl FnMut(&str) -> Option<String>) -> Result<String, String> {
+    let mut out = String::new();
+    let mut chars = word.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => { chars.next(); out.push('{') }
+            '}' if chars.peek() == Some(&'}') => { chars.next(); out.push('}') }
+            '{' => {
+                let (mut name, mut closed) = (String::new(), false);
+                for c in chars.by_ref() {
+                    if c == '}' { closed = true; break; }
+                    name.push(c);
+                }
+                if !closed || name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    return Err(format!("'{{{}' isn't a placeholder like {{minutes}}", name));
+                }
+                out.push_str(&value(&name).ok_or_else(|| format!("no value for {{{}}}", name))?);
+            }
+            '}' => return Err("'}' without a '{', write }} for a brace".to_string()),
+            c => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+// Spaces split words except inside quotes, which are dropped
+fn split_words(text: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    for c in text.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.get_or_insert_with(String::new).push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if let Some(q) = quote { return Err(format!("unclosed {} quote", q)); }
+    words.extend(word);
+    Ok(words)
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|&(n, v)| (n.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn expand_fills_each_word() {
+        let template = Template::parse("{minutes}m --label 'Review {pr}' --exec \"notify {pr}\"").unwrap();
+        assert_eq!(template.placeholders(), ["minutes", "pr"]);
+        // Test: A value with spaces stays inside its word
+        assert_eq!(template.expand(&values(&[("minutes", "25"), ("pr", "big one")])).unwrap(),
+            ["25m", "--label", "Review big one", "--exec", "notify big one"]);
+        assert_eq!(template.expand(&values(&[("minutes", "25")])), Err("no value for {pr}".to_string()));
+        // Test: Doubled braces are literal, and no placeholder is needed
+        assert_eq!(Template::parse("5m --label {{x}}").unwrap().expand(&[]).unwrap(), ["5m", "--label", "{x}"]);
+    }
+
+    #[test]
+    fn parse_rejects_bad_templates() {
+        assert_eq!(Template::parse("5m --label 'open").unwrap_err(), "unclosed ' quote");
+        assert_eq!(Template::parse("{min utes}m").unwrap_err(), "'{min' isn't a placeholder like {minutes}");
+        assert!(Template::parse("{}m").is_err());
+        assert!(Template::parse("5m }").is_err());
+    }
+
+    #[test]
+    fn expand_preset_rewrites_the_command_line() {
+        let templates = vec![("review".to_string(), Template::parse("{minutes}m --label 'Review {pr}'").unwrap())];
+        let expand = |args: &[&str], answer: Option<&str>| {
+            let mut args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+            let mut asked = Vec::new();
+            expand_preset(&mut args, &templates, |var| { asked.push(var.to_string()); answer.map(String::from) }).map(|_| (args, asked))
+        };
+        // Test: What isn't --set is asked for, and later options still apply
+        let (args, asked) = expand(&["tt", "preset", "review", "--set", "pr=42", "--no-log"], Some("25")).unwrap();
+        assert_eq!(args, ["tt", "countdown", "--name", "review", "25m", "--label", "Review 42", "--no-log"]);
+        assert_eq!(asked, ["minutes"]);
+        // Test: No answer, or --no-confirm, is an error naming the --set to use
+        assert_eq!(expand(&["tt", "preset", "review", "--set=minutes=5"], None),
+            Err("preset 'review' needs {pr}, give it with --set pr=VALUE".to_string()));
+        let (_, asked) = expand(&["tt", "preset", "review", "--set=minutes=5", "--set=pr=1", "--no-confirm"], None).unwrap();
+        assert!(asked.is_empty());
+        assert!(expand(&["tt", "preset", "review", "--no-confirm"], Some("5")).is_err());
+        assert_eq!(expand(&["tt", "preset", "review", "--set", "prr=1"], None), Err("preset 'review' has no {prr}".to_string()));
+        // Test: Plain presets and other commands are left as they are
+        assert_eq!(expand(&["tt", "preset", "tea"], None).unwrap().0, ["tt", "preset", "tea"]);
+        assert_eq!(expand(&["tt", "review"], None).unwrap().0, ["tt", "review"]);
+    }
+
+    #[test]
+    fn is_template_leaves_durations_alone() {
+        assert!(!Template::is_template("25:00"));
+        assert!(!Template::is_template(" 1h30m "));
+        assert!(Template::is_template("{minutes}m"));
+        assert!(Template::is_template("5m --label tea"));
+    }
+}
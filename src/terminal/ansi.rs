@@ -0,0 +1,81 @@
+// src/terminal/ansi.rs
+// Raw ANSI escape sequences, see MECHANISM.md for what each one does
+
+pub const ENTER_ALT_SCREEN: &str = "\x1b[?1049h";
+pub const LEAVE_ALT_SCREEN: &str = "\x1b[?1049l";
+pub const HIDE_CURSOR: &str = "\x1b[?25l";
+pub const SHOW_CURSOR: &str = "\x1b[?25h";
+pub const CLEAR_SCREEN: &str = "\x1b[2J";
+pub const CLEAR_LINE: &str = "\x1b[2K";
+pub const CLEAR_BELOW: &str = "\x1b[J"; // From the cursor to the end of the screen
+pub const BELL: &str = "\x07";
+pub const RESET: &str = "\x1b[0m";
+// xterm's title stack, so the shell's own title comes back afterwards
+pub const PUSH_TITLE: &str = "\x1b[22;0t";
+pub const POP_TITLE: &str = "\x1b[23;0t";
+
+// Foreground SGR for one of the 8 basic color names
+pub fn fg(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "black" => "\x1b[30m",
+        "red" => "\x1b[31m",
+        "green" => "\x1b[32m",
+        "yellow" => "\x1b[33m",
+        "blue" => "\x1b[34m",
+        "magenta" => "\x1b[35m",
+        "cyan" => "\x1b[36m",
+        "white" => "\x1b[37m",
+        _ => return None,
+    })
+}
+
+// OSC 0, the window and icon title. Control characters are dropped so a
+// title can't end the sequence early.
+pub fn set_title(title: &str) -> String {
+    let title: String = title.chars().filter(|c| !c.is_control()).collect();
+    format!("\x1b]0;{}\x1b\\", title)
+}
+
+// OSC 777 notify, a desktop notification through the terminal. The title
+// loses semicolons too, they separate it from the body.
+pub fn notify(title: &str, body: &str) -> String {
+    let title: String = title.chars().filter(|c| !c.is_control() && *c != ';').collect();
+    let body: String = body.chars().filter(|c| !c.is_control()).collect();
+    format!("\x1b]777;notify;{};{}\x1b\\", title, body)
+}
+
+// Move cursor to (row, col), both 1-indexed like the terminal expects
+pub fn move_to(row: u16, col: u16) -> String {
+    format!("\x1b[{};{}H", row, col)
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn move_to_generates_cup_sequence() {
+        // Test: move_to(5, 10) should produce CSI 5;10 H
+        assert_eq!(super::move_to(5, 10), "\x1b[5;10H");
+        assert_eq!(super::move_to(1, 1), "\x1b[1;1H");
+    }
+
+    #[test]
+    fn fg_knows_basic_colors() {
+        // Test: Named colors map to SGR 30-37, anything else is None
+        assert_eq!(super::fg("black"), Some("\x1b[30m"));
+        assert_eq!(super::fg("cyan"), Some("\x1b[36m"));
+        assert_eq!(super::fg("Cyan"), None);
+    }
+
+    #[test]
+    fn set_title_strips_control_chars() {
+        // Test: An escape or bell in a label can't break out of the OSC
+        assert_eq!(super::set_title("Pasta"), "\x1b]0;Pasta\x1b\\");
+        assert_eq!(super::set_title("a\x07b\x1bc"), "\x1b]0;abc\x1b\\");
+    }
+
+    #[test]
+    fn notify_keeps_title_and_body_apart() {
+        assert_eq!(super::notify("a;b", "Done; 5:00\x07"), "\x1b]777;notify;ab;Done; 5:00\x1b\\");
+    }
+}
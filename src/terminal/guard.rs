@@ -0,0 +1,87 @@
+// src/terminal/guard.rs
+use std::cell::RefCell;
+use std::io::{self, Write};
+
+use super::ansi;
+use crate::platform;
+
+// RAII guard: takes over the screen on creation, gives it back on drop.
+// Drop runs on normal exit AND on the SIGINT path since main just breaks its loop.
+pub struct TerminalGuard {
+    // None when stdin isn't a terminal, so there's nothing to restore
+    original_mode: Option<platform::TermState>,
+    plain: bool, // Line mode, no screen to take over
+    cooked: bool, // A terminal that wouldn't go raw, typing is echoed
+    title: RefCell<Option<String>>, // Window title set over the shell's, see set_title
+}
+
+impl TerminalGuard {
+    pub fn new() -> io::Result<Self> {
+        let (original_mode, cooked) = enable_raw_mode();
+        let mut out = io::stdout();
+        write!(out, "{}{}{}", ansi::ENTER_ALT_SCREEN, ansi::HIDE_CURSOR, ansi::CLEAR_SCREEN)?;
+        out.flush()?;
+        Ok(TerminalGuard { original_mode, plain: false, cooked, title: RefCell::new(None) })
+    }
+
+    // Keys without waiting for Enter, but output left alone for line mode
+    pub fn plain() -> Self {
+        let (original_mode, cooked) = enable_raw_mode();
+        TerminalGuard { original_mode, plain: true, cooked, title: RefCell::new(None) }
+    }
+
+    // Typed keys land on the screen (and may wait for Enter), so whatever
+    // reads them should have the frame redrawn after
+    pub fn is_cooked(&self) -> bool {
+        self.cooked
+    }
+
+    // Show `title` in the window title bar until the screen is given back.
+    // The old title is pushed first so giving it back restores it.
+    pub fn set_title(&self, title: &str) {
+        if self.plain { return; }
+        let mut current = self.title.borrow_mut();
+        let mut out = io::stdout();
+        if current.is_none() { let _ = write!(out, "{}", ansi::PUSH_TITLE); }
+        let _ = write!(out, "{}", ansi::set_title(title));
+        let _ = out.flush();
+        *current = Some(title.to_string());
+    }
+
+    // Give the screen back for a while (Ctrl+Z) without dropping the guard
+    pub fn suspend(&self) {
+        // Nothing useful to do with an error while tearing down, so ignore it
+        if !self.plain {
+            let mut out = io::stdout();
+            let pop = if self.title.borrow().is_some() { ansi::POP_TITLE } else { "" };
+            let _ = write!(out, "{}{}{}", ansi::SHOW_CURSOR, ansi::LEAVE_ALT_SCREEN, pop);
+            let _ = out.flush();
+        }
+        if let Some(mode) = &self.original_mode { platform::restore_mode(mode); }
+    }
+
+    // Take the screen over again after `fg`, the caller redraws
+    pub fn resume(&self) {
+        if self.original_mode.is_some() { platform::enable_raw_mode(); }
+        if self.plain { return; }
+        let mut out = io::stdout();
+        let _ = write!(out, "{}{}{}", ansi::ENTER_ALT_SCREEN, ansi::HIDE_CURSOR, ansi::CLEAR_SCREEN);
+        if let Some(title) = self.title.borrow().as_deref() { let _ = write!(out, "{}{}", ansi::PUSH_TITLE, ansi::set_title(title)); }
+        let _ = out.flush();
+    }
+}
+
+// Raw mode if the terminal takes it, and whether it was left cooked. Said on
+// the main screen, where it's still there to read after the run.
+fn enable_raw_mode() -> (Option<platform::TermState>, bool) {
+    let original_mode = platform::enable_raw_mode();
+    let cooked = original_mode.is_some() && !platform::is_raw();
+    if cooked { eprintln!("timerterm: the terminal won't switch to raw mode, keys may echo and need Enter after them"); }
+    (original_mode, cooked)
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        self.suspend();
+    }
+}
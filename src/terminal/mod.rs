@@ -0,0 +1,52 @@
+// src/terminal/mod.rs
+pub mod ansi;
+mod guard;
+
+use std::io::IsTerminal;
+
+pub use guard::TerminalGuard;
+
+// Query (cols, rows) of the terminal on stdout.
+// Returns None when stdout isn't a terminal (pipes, CI, etc).
+pub fn get_size() -> Option<(u16, u16)> {
+    crate::platform::terminal_size()
+}
+
+// Whether stdout goes to a pipe or file (`timerterm 10 | tee log`, cron)
+// rather than a terminal, where escape codes would only be noise
+pub fn is_piped() -> bool {
+    !std::io::stdout().is_terminal()
+}
+
+// What to lay out for when the size is unknown
+pub const FALLBACK_SIZE: (u16, u16) = (80, 24);
+
+// No size and no TERM to go on (some CI runners, init systems) means there
+// may be nothing to draw frames in, so print a line per update instead.
+// Output that isn't a terminal at all gets lines anyway, see is_piped.
+pub fn needs_line_mode(size: Option<(u16, u16)>, term: Option<&str>) -> bool {
+    size.is_none() && term.is_none_or(|t| t.is_empty() || t == "dumb")
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn get_size_handles_non_tty_gracefully() {
+        // Test: Under cargo test stdout may or may not be a tty,
+        // either way we should get None or a sane non-zero size, never panic
+        if let Some((cols, rows)) = super::get_size() {
+            assert!(cols > 0 && rows > 0);
+        }
+    }
+
+    #[test]
+    fn line_mode_needs_no_size_and_no_term() {
+        // Test: Either a size or a real TERM keeps full frames
+        assert!(super::needs_line_mode(None, None));
+        assert!(super::needs_line_mode(None, Some("dumb")));
+        assert!(super::needs_line_mode(None, Some("")));
+        assert!(!super::needs_line_mode(None, Some("xterm-256color")));
+        assert!(!super::needs_line_mode(Some((80, 24)), None));
+    }
+}
@@ -0,0 +1,105 @@
+// src/text.rs
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+// Terminal cell width of a string, NOT its byte or char count.
+// Emoji and CJK take 2 cells, combining marks take 0.
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+// Cut a string down to at most `max` cells without splitting a wide char
+pub fn truncate(s: &str, max: usize) -> &str {
+    let mut used = 0;
+    for (idx, ch) in s.char_indices() {
+        let w = UnicodeWidthChar::width(ch).unwrap_or(0);
+        if used + w > max { return &s[..idx]; }
+        used += w;
+    }
+    s
+}
+
+// Pad with spaces on the right until the string fills `width` cells
+pub fn pad_right(s: &str, width: usize) -> String {
+    let s = truncate(s, width);
+    let fill = width - display_width(s);
+    format!("{}{}", s, " ".repeat(fill))
+}
+
+// Center a string in `width` cells, extra space goes to the right
+pub fn center(s: &str, width: usize) -> String {
+    let s = truncate(s, width);
+    let fill = width - display_width(s);
+    let left = fill / 2;
+    format!("{}{}{}", " ".repeat(left), s, " ".repeat(fill - left))
+}
+
+// Break into lines of at most `width` cells at spaces. A word wider than
+// `width` is cut, since there's nowhere better to break it.
+pub fn wrap(s: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in s.split_whitespace() {
+        let word = truncate(word, width);
+        if !line.is_empty() && display_width(&line) + 1 + display_width(word) > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() { line.push(' '); }
+        line.push_str(word);
+    }
+    if !line.is_empty() { lines.push(line); }
+    lines
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_width_counts_cells() {
+        // Test: ASCII is 1 cell per char
+        assert_eq!(display_width("Break"), 5);
+        // Test: Emoji and CJK are 2 cells wide
+        assert_eq!(display_width("☕ Break"), 8);
+        assert_eq!(display_width("休憩"), 4);
+        // Test: Combining marks take no cells ("e" + combining acute)
+        assert_eq!(display_width("e\u{301}"), 1);
+    }
+
+    #[test]
+    fn truncate_never_splits_wide_chars() {
+        // Test: Wide char that doesn't fit is dropped entirely
+        assert_eq!(truncate("休憩", 3), "休");
+        assert_eq!(truncate("☕ Break", 1), "");
+        // Test: Strings that fit are returned as-is
+        assert_eq!(truncate("Tea", 10), "Tea");
+        // Test: Combining marks stay attached to their base char
+        assert_eq!(truncate("e\u{301}x", 1), "e\u{301}");
+    }
+
+    #[test]
+    fn pad_right_fills_to_width() {
+        assert_eq!(pad_right("☕", 4), "☕  ");
+        assert_eq!(display_width(&pad_right("休憩中", 5)), 5);
+    }
+
+    #[test]
+    fn center_uses_cell_width() {
+        // Test: "☕ Break" is 8 cells, so 12 cells leaves 2 on each side
+        assert_eq!(center("☕ Break", 12), "  ☕ Break  ");
+        // Test: Odd leftover space goes to the right
+        assert_eq!(center("ab", 5), " ab  ");
+        // Test: Too-wide strings get truncated, then padded
+        assert_eq!(display_width(&center("休憩", 3)), 3);
+    }
+
+    #[test]
+    fn wrap_breaks_at_spaces() {
+        // Test: Words stay whole and lines stay within the width
+        assert_eq!(wrap("The best time to start was yesterday", 12), ["The best", "time to", "start was", "yesterday"]);
+        // Test: Wide chars count as 2 cells, overlong words are cut
+        assert_eq!(wrap("休憩 休憩", 4), ["休憩", "休憩"]);
+        assert_eq!(wrap("supercalifragilistic", 5), ["super"]);
+        assert!(wrap("   ", 10).is_empty());
+    }
+}
@@ -0,0 +1,402 @@
+// src/timer.rs
+use std::time::{Duration, Instant};
+
+use crate::clock;
+use crate::render::format_duration;
+
+// Countdown (Down) or stopwatch (Up)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Down,
+    Up,
+}
+
+// What a run is called, as shared by everything that shows or records it:
+// the screen header and window title, notifications and the history log
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TimerConfig {
+    pub label: Option<String>, // --label, free text like "Pasta"
+    pub name: Option<String>,  // --name or the preset's, also used by `timerterm ack`
+}
+
+impl TimerConfig {
+    // The label, else the name
+    pub fn display_name(&self) -> Option<&str> {
+        self.label.as_deref().or(self.name.as_deref())
+    }
+
+    pub fn window_title(&self) -> Option<String> {
+        self.label.as_ref().map(|label| format!("{} - timerterm", label))
+    }
+
+    // Notification body once it's done
+    pub fn finished_message(&self, elapsed: Duration) -> String {
+        let what = match (&self.label, &self.name) {
+            (Some(label), _) => label.clone(),
+            (None, Some(name)) => format!("Timer '{}'", name),
+            (None, None) => "Timer".to_string(),
+        };
+        format!("{} finished after {}", what, format_duration(elapsed.as_secs()))
+    }
+}
+
+// Timer driven by clock::now, the monotonic clock outside the test harness.
+// Time spent paused is tracked separately and doesn't count as elapsed.
+#[derive(Debug, Clone)]
+pub struct Timer {
+    direction: Direction,
+    duration: Duration,
+    head_start: Duration, // Counted as elapsed from the start, see with_elapsed
+    started_at: Option<Instant>,
+    paused_at: Option<Instant>,
+    paused_total: Duration,
+}
+
+impl Timer {
+    pub fn new(duration: Duration) -> Self {
+        Timer {
+            direction: Direction::Down,
+            duration,
+            head_start: Duration::ZERO,
+            started_at: None,
+            paused_at: None,
+            paused_total: Duration::ZERO,
+        }
+    }
+
+    // Counts up from zero and never finishes on its own
+    pub fn stopwatch() -> Self {
+        Timer { direction: Direction::Up, ..Timer::new(Duration::ZERO) }
+    }
+
+    // Start as if it had already been running for `elapsed`, e.g. to pick
+    // up a timer that was closed by accident
+    pub fn with_elapsed(self, elapsed: Duration) -> Self {
+        Timer { head_start: elapsed, ..self }
+    }
+
+    pub fn head_start(&self) -> Duration {
+        self.head_start
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    // (Re)start from the head start, if any, clearing any pause state
+    pub fn start(&mut self) {
+        self.start_at(clock::now());
+    }
+
+    // Start over from zero, dropping any head start
+    pub fn restart(&mut self) {
+        self.head_start = Duration::ZERO;
+        self.start();
+    }
+
+    // No-op if not started or already paused
+    pub fn pause(&mut self) {
+        self.pause_at(clock::now());
+    }
+
+    // No-op if not paused
+    pub fn resume(&mut self) {
+        self.resume_at(clock::now());
+    }
+
+    // Move the deadline `secs` later, or earlier when negative, though not
+    // past the time already elapsed. A stopwatch has no deadline to move.
+    pub fn adjust(&mut self, secs: i64) {
+        self.adjust_at(clock::now(), secs);
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed_at(clock::now())
+    }
+
+    // Always zero for a stopwatch, which has no end
+    pub fn remaining(&self) -> Duration {
+        self.remaining_at(clock::now())
+    }
+
+    // What a display should show: remaining when counting down, elapsed when counting up
+    pub fn shown(&self) -> Duration {
+        self.shown_at(clock::now())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.is_finished_at(clock::now())
+    }
+
+    // When a running countdown reaches zero. None for a stopwatch, or while
+    // paused or not started, since then there's nothing to count toward.
+    pub fn deadline(&self) -> Option<Instant> {
+        if self.direction == Direction::Up || self.paused_at.is_some() { return None; }
+        self.reaches(self.duration)
+    }
+
+    // The next moment the shown whole seconds change: remaining rounds up, so
+    // a countdown ticks on whole seconds before its deadline, and a stopwatch
+    // on whole seconds after its start. None while nothing is changing.
+    pub fn next_tick(&self) -> Option<Instant> {
+        self.next_tick_at(clock::now())
+    }
+
+    // Clock-explicit versions, so behavior can be tested without sleeping
+    fn start_at(&mut self, now: Instant) {
+        self.started_at = Some(now);
+        self.paused_at = None;
+        self.paused_total = Duration::ZERO;
+    }
+
+    fn pause_at(&mut self, now: Instant) {
+        if self.started_at.is_some() && self.paused_at.is_none() {
+            self.paused_at = Some(now);
+        }
+    }
+
+    fn resume_at(&mut self, now: Instant) {
+        if let Some(paused_at) = self.paused_at.take() {
+            self.paused_total += now.saturating_duration_since(paused_at);
+        }
+    }
+
+    fn adjust_at(&mut self, now: Instant, secs: i64) {
+        if self.direction == Direction::Up { return; }
+        let by = Duration::from_secs(secs.unsigned_abs());
+        self.duration = match secs {
+            0.. => self.duration + by,
+            _ => self.duration.saturating_sub(by).max(self.elapsed_at(now)),
+        };
+    }
+
+    fn elapsed_at(&self, now: Instant) -> Duration {
+        let Some(started_at) = self.started_at else { return Duration::ZERO };
+        // While paused, the clock is frozen at the moment of pausing
+        let until = self.paused_at.unwrap_or(now);
+        until.saturating_duration_since(started_at).saturating_sub(self.paused_total) + self.head_start
+    }
+
+    fn remaining_at(&self, now: Instant) -> Duration {
+        self.duration.saturating_sub(self.elapsed_at(now))
+    }
+
+    fn shown_at(&self, now: Instant) -> Duration {
+        match self.direction {
+            Direction::Down => self.remaining_at(now),
+            Direction::Up => self.elapsed_at(now),
+        }
+    }
+
+    fn is_finished_at(&self, now: Instant) -> bool {
+        self.direction == Direction::Down && self.started_at.is_some() && self.remaining_at(now).is_zero()
+    }
+
+    // When a running timer's elapsed time reaches `elapsed`, if it keeps running
+    fn reaches(&self, elapsed: Duration) -> Option<Instant> {
+        Some(self.started_at? + self.paused_total + elapsed.saturating_sub(self.head_start))
+    }
+
+    fn next_tick_at(&self, now: Instant) -> Option<Instant> {
+        if self.paused_at.is_some() { return None; }
+        match self.direction {
+            Direction::Down => {
+                let remaining = self.remaining_at(now);
+                if remaining.is_zero() { return None; }
+                let whole_secs_left = remaining.as_nanos().div_ceil(1_000_000_000) as u64 - 1;
+                Some(self.deadline()? - Duration::from_secs(whole_secs_left))
+            }
+            Direction::Up => self.reaches(Duration::from_secs(self.elapsed_at(now).as_secs() + 1)),
+        }
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(s: u64) -> Duration { Duration::from_secs(s) }
+
+    #[test]
+    fn unstarted_timer_has_full_duration() {
+        // Test: A fresh timer hasn't elapsed anything and isn't finished
+        let timer = Timer::new(secs(10));
+        assert_eq!(timer.remaining(), secs(10));
+        assert_eq!(timer.elapsed(), Duration::ZERO);
+        assert!(!timer.is_finished());
+        assert!(!timer.is_started());
+    }
+
+    #[test]
+    fn remaining_counts_down_from_start() {
+        let t0 = Instant::now();
+        let mut timer = Timer::new(secs(10));
+        timer.start_at(t0);
+        assert_eq!(timer.remaining_at(t0 + secs(3)), secs(7));
+        // Test: Remaining never goes below zero
+        assert_eq!(timer.remaining_at(t0 + secs(30)), Duration::ZERO);
+        assert!(timer.is_finished_at(t0 + secs(10)));
+        assert!(!timer.is_finished_at(t0 + secs(9)));
+    }
+
+    #[test]
+    fn pause_freezes_remaining() {
+        let t0 = Instant::now();
+        let mut timer = Timer::new(secs(10));
+        timer.start_at(t0);
+        timer.pause_at(t0 + secs(2));
+        assert!(timer.is_paused());
+        // Test: While paused remaining stays put however long we wait
+        assert_eq!(timer.remaining_at(t0 + secs(2)), secs(8));
+        assert_eq!(timer.remaining_at(t0 + secs(60)), secs(8));
+    }
+
+    #[test]
+    fn resume_excludes_paused_time() {
+        let t0 = Instant::now();
+        let mut timer = Timer::new(secs(10));
+        timer.start_at(t0);
+        timer.pause_at(t0 + secs(2));
+        timer.resume_at(t0 + secs(7));
+        assert!(!timer.is_paused());
+        // Test: 5s spent paused don't count, so at t0+8 only 3s elapsed
+        assert_eq!(timer.elapsed_at(t0 + secs(8)), secs(3));
+        // Test: Double pause/resume are no-ops
+        timer.resume_at(t0 + secs(9));
+        assert_eq!(timer.elapsed_at(t0 + secs(9)), secs(4));
+    }
+
+    #[test]
+    fn start_resets_pause_state() {
+        let t0 = Instant::now();
+        let mut timer = Timer::new(secs(10));
+        timer.start_at(t0);
+        timer.pause_at(t0 + secs(1));
+        timer.start_at(t0 + secs(5));
+        assert!(!timer.is_paused());
+        assert_eq!(timer.remaining_at(t0 + secs(6)), secs(9));
+    }
+
+    #[test]
+    fn stopwatch_counts_up_and_never_finishes() {
+        let t0 = Instant::now();
+        let mut timer = Timer::stopwatch();
+        assert_eq!(timer.direction(), Direction::Up);
+        timer.start_at(t0);
+        // Test: Shown time is elapsed time, however long it runs
+        assert_eq!(timer.shown_at(t0 + secs(90)), secs(90));
+        assert!(!timer.is_finished_at(t0 + secs(100_000)));
+        // Test: Pausing freezes it just like a countdown
+        timer.pause_at(t0 + secs(10));
+        assert_eq!(timer.shown_at(t0 + secs(50)), secs(10));
+    }
+
+    #[test]
+    fn countdown_shows_remaining() {
+        let t0 = Instant::now();
+        let mut timer = Timer::new(secs(10));
+        timer.start_at(t0);
+        assert_eq!(timer.shown_at(t0 + secs(4)), secs(6));
+    }
+
+    #[test]
+    fn next_tick_lands_on_second_boundaries() {
+        let t0 = Instant::now();
+        let mut timer = Timer::new(secs(10));
+        assert_eq!(timer.next_tick_at(t0), None);
+        timer.start_at(t0);
+        assert_eq!(timer.deadline(), Some(t0 + secs(10)));
+        // Test: "10" shows until 1s in, "1" until the deadline itself
+        assert_eq!(timer.next_tick_at(t0), Some(t0 + secs(1)));
+        assert_eq!(timer.next_tick_at(t0 + Duration::from_millis(1500)), Some(t0 + secs(2)));
+        assert_eq!(timer.next_tick_at(t0 + Duration::from_millis(9999)), Some(t0 + secs(10)));
+        assert_eq!(timer.next_tick_at(t0 + secs(10)), None);
+        // Test: Paused time pushes every boundary back, and nothing ticks while paused
+        timer.pause_at(t0 + secs(2));
+        assert_eq!(timer.next_tick_at(t0 + secs(3)), None);
+        assert_eq!(timer.deadline(), None);
+        timer.resume_at(t0 + Duration::from_millis(2500));
+        assert_eq!(timer.deadline(), Some(t0 + Duration::from_millis(10_500)));
+        assert_eq!(timer.next_tick_at(t0 + secs(3)), Some(t0 + Duration::from_millis(3500)));
+    }
+
+    #[test]
+    fn stopwatch_ticks_after_start() {
+        let t0 = Instant::now();
+        let mut timer = Timer::stopwatch();
+        timer.start_at(t0);
+        // Test: No deadline, just whole seconds since the start
+        assert_eq!(timer.deadline(), None);
+        assert_eq!(timer.next_tick_at(t0 + Duration::from_millis(2300)), Some(t0 + secs(3)));
+    }
+
+    #[test]
+    fn head_start_counts_as_elapsed() {
+        let t0 = Instant::now();
+        let mut timer = Timer::new(secs(600)).with_elapsed(secs(420));
+        timer.start_at(t0);
+        // Test: 7 of 10 minutes are already gone, so the deadline is 3 away
+        assert_eq!(timer.elapsed_at(t0), secs(420));
+        assert_eq!(timer.remaining_at(t0 + secs(60)), secs(120));
+        assert_eq!(timer.deadline(), Some(t0 + secs(180)));
+        assert_eq!(timer.next_tick_at(t0), Some(t0 + secs(1)));
+        // Test: Restarting goes back to the full duration
+        timer.restart();
+        assert!(timer.remaining() > secs(599));
+    }
+
+    #[test]
+    fn adjust_moves_the_deadline() {
+        let t0 = Instant::now();
+        let mut timer = Timer::new(secs(300));
+        timer.start_at(t0);
+        // Test: A minute on and two off, 4:00 in total, even while paused
+        timer.adjust_at(t0 + secs(10), 60);
+        timer.pause_at(t0 + secs(20));
+        timer.adjust_at(t0 + secs(30), -120);
+        assert_eq!(timer.duration(), secs(240));
+        assert_eq!(timer.remaining_at(t0 + secs(40)), secs(220));
+        // Test: Taking off more than is left finishes it without losing elapsed time
+        timer.adjust_at(t0 + secs(40), -600);
+        assert_eq!(timer.duration(), secs(20));
+        assert!(timer.is_finished_at(t0 + secs(40)));
+        // Test: A stopwatch has nothing to adjust
+        let mut stopwatch = Timer::stopwatch();
+        stopwatch.adjust_at(t0, 60);
+        assert_eq!(stopwatch.duration(), Duration::ZERO);
+    }
+
+    #[test]
+    fn timer_config_prefers_label() {
+        // Test: The label wins everywhere, the name is the fallback
+        let named = TimerConfig { label: None, name: Some("tea".into()) };
+        assert_eq!(named.display_name(), Some("tea"));
+        assert_eq!(named.window_title(), None);
+        assert_eq!(named.finished_message(secs(180)), "Timer 'tea' finished after 3:00");
+        let labeled = TimerConfig { label: Some("Pasta".into()), ..named };
+        assert_eq!(labeled.display_name(), Some("Pasta"));
+        assert_eq!(labeled.window_title().as_deref(), Some("Pasta - timerterm"));
+        assert_eq!(labeled.finished_message(secs(600)), "Pasta finished after 10:00");
+        assert_eq!(TimerConfig::default().finished_message(secs(5)), "Timer finished after 0:05");
+    }
+
+    #[test]
+    fn pause_before_start_is_ignored() {
+        let mut timer = Timer::new(secs(10));
+        timer.pause();
+        assert!(!timer.is_paused());
+    }
+}
@@ -0,0 +1,233 @@
+// src/today.rs
+// `timerterm today`: the day's runs from the history log as one row of
+// blocks, from the hour of the first start up to now. Blocks take their
+// label's color, and their shade says how the run ended:
+//   09        10        11        12
+//   ████████  ░░░██████████       ▓▓▓▓
+use std::collections::HashMap;
+
+use crate::log;
+use crate::render::format_duration;
+use crate::terminal::ansi;
+use crate::text;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Completed,
+    Cancelled,
+    Running, // No end logged yet
+}
+
+impl Outcome {
+    fn glyph(self) -> char {
+        match self {
+            Outcome::Completed => '█',
+            Outcome::Cancelled => '░',
+            Outcome::Running => '▓',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    pub start: u32,            // Secs since local midnight
+    pub end: Option<u32>,      // None while running
+    pub outcome: Outcome,
+    pub label: Option<String>, // --label, or the pomodoro phase
+    pub dir: Option<String>,   // The project it started in, see git::project_dir
+    pub elapsed: Option<u32>,  // Secs counted, from the line that ended it
+    pub estimate: Option<u32>, // Secs it was expected to take, --estimate
+}
+
+impl Session {
+    fn end_or(&self, now: u32) -> u32 {
+        self.end.unwrap_or(now).max(self.start)
+    }
+}
+
+// Label colors, handed out in order of first appearance
+const PALETTE: [&str; 6] = ["cyan", "green", "yellow", "magenta", "blue", "red"];
+
+// Runs in the `log` text that started on `day` ("Jan 15"), by start time.
+// One process can log several runs (pomodoro phases, --repeat, r), so each
+// START begins a new one and a START with one still open cancels it.
+pub fn sessions(log: &str, day: &str) -> Vec<Session> {
+    let mut done = sessions_where(log, |entry| entry.day == day);
+    done.sort_by_key(|s| s.start);
+    done
+}
+
+// Every run in the log, in the order they ended (still running last)
+pub fn every_session(log: &str) -> Vec<Session> {
+    sessions_where(log, |_| true)
+}
+
+fn sessions_where(log: &str, keep: impl Fn(&log::Entry) -> bool) -> Vec<Session> {
+    let mut done = Vec::new();
+    let mut open: HashMap<u32, Session> = HashMap::new();
+    let mut phases: HashMap<u32, String> = HashMap::new(); // The phase each pid is starting
+    for entry in log.lines().filter_map(log::parse_entry).filter(|e| keep(e)) {
+        let (word, fields) = entry.message.split_once(' ').unwrap_or((entry.message, ""));
+        let mut close = |outcome, elapsed| if let Some(mut session) = open.remove(&entry.pid) {
+            (session.end, session.outcome, session.elapsed) = (Some(entry.secs), outcome, elapsed);
+            done.push(session);
+        };
+        match word {
+            "PHASE" => { if let Some(label) = quoted(fields, "label") { phases.insert(entry.pid, label); } }
+            "START" => {
+                close(Outcome::Cancelled, None);
+                let label = quoted(fields, "label").or_else(|| phases.remove(&entry.pid));
+                let session = Session { start: entry.secs, end: None, outcome: Outcome::Running, label, dir: quoted(fields, "dir"), elapsed: None,
+                    estimate: secs(fields, "estimate") };
+                open.insert(entry.pid, session);
+            }
+            "COMPLETED" => close(Outcome::Completed, secs(fields, "elapsed")),
+            "CANCELLED" => close(Outcome::Cancelled, secs(fields, "elapsed")),
+            _ => {}
+        }
+    }
+    let mut running: Vec<(u32, Session)> = open.into_iter().collect();
+    running.sort_by_key(|&(pid, _)| pid); // Secs are a time of day, so they can't order days
+    done.extend(running.into_iter().map(|(_, s)| s));
+    done
+}
+
+// The value of `key=42s` in a message
+fn secs(fields: &str, key: &str) -> Option<u32> {
+    fields.split(' ').find_map(|field| field.strip_prefix(key)?.strip_prefix('=')?.strip_suffix('s')?.parse().ok())
+}
+
+// The value of `key="..."` in a message, as log::message quoted it
+fn quoted(fields: &str, key: &str) -> Option<String> {
+    let start = fields.find(&format!("{}=\"", key))? + key.len() + 2;
+    let mut value = String::new();
+    let mut chars = fields[start..].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(value),
+            '\\' => value.push(match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                other => other,
+            }),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+// The hour marks, the row of blocks `width` wide, then a line per label and
+// the totals. `now` is secs since midnight, `color` adds the label colors.
+pub fn timeline(sessions: &[Session], now: u32, width: usize, color: bool) -> Vec<String> {
+    let width = width.max(1);
+    let first = sessions.iter().map(|s| s.start).min().unwrap_or(now) / 3600 * 3600;
+    let last = sessions.iter().map(|s| s.end_or(now)).chain([now]).max().unwrap_or(now).div_ceil(3600) * 3600;
+    let span = (last - first).max(3600) as f64;
+    let at = |col: usize| first + (span * col as f64 / width as f64) as u32;
+
+    let mut labels: Vec<Option<&str>> = Vec::new();
+    for session in sessions {
+        if !labels.contains(&session.label.as_deref()) { labels.push(session.label.as_deref()); }
+    }
+    let paint = |label: Option<&str>, text: String| -> String {
+        let index = labels.iter().position(|l| *l == label).unwrap_or(0);
+        match (color, label) {
+            (true, Some(_)) => format!("{}{}{}", ansi::fg(PALETTE[index % PALETTE.len()]).unwrap_or(""), text, ansi::RESET),
+            _ => text,
+        }
+    };
+
+    // Hour numbers where they start, unless the previous one is in the way
+    let mut axis = String::new();
+    for hour in (first / 3600)..(last / 3600) {
+        let col = ((hour * 3600 - first) as f64 / span * width as f64) as usize;
+        if col >= axis.chars().count() + usize::from(!axis.is_empty()) && col + 2 <= width {
+            axis.push_str(&" ".repeat(col - axis.chars().count()));
+            axis.push_str(&format!("{:02}", hour % 24));
+        }
+    }
+
+    // Each column shows the run that covers most of it
+    let mut row = String::new();
+    for col in 0..width {
+        let (from, to) = (at(col), at(col + 1).max(at(col) + 1));
+        let covering = sessions.iter()
+            .map(|s| (s.end_or(now).min(to).saturating_sub(s.start.max(from)), s))
+            .filter(|&(overlap, _)| overlap > 0)
+            .max_by_key(|&(overlap, _)| overlap);
+        row.push_str(&match covering {
+            Some((_, s)) => paint(s.label.as_deref(), s.outcome.glyph().to_string()),
+            None => " ".to_string(),
+        });
+    }
+
+    let mut lines = vec![axis, row, String::new()];
+    let name_width = labels.iter().map(|l| text::display_width(l.unwrap_or("(no label)"))).max().unwrap_or(0);
+    for &label in &labels {
+        let runs: Vec<&Session> = sessions.iter().filter(|s| s.label.as_deref() == label).collect();
+        let secs: u32 = runs.iter().map(|s| s.end_or(now) - s.start).sum();
+        let name = text::pad_right(label.unwrap_or("(no label)"), name_width);
+        let what = if runs.len() == 1 { "run" } else { "runs" };
+        lines.push(format!("{} {}  {}, {} {}", paint(label, "█".to_string()), name, format_duration(secs as u64), runs.len(), what));
+    }
+    let count = |outcome| sessions.iter().filter(|s| s.outcome == outcome).count();
+    lines.push(format!("{} completed, {} cancelled, {} running  (█ ░ ▓)",
+        count(Outcome::Completed), count(Outcome::Cancelled), count(Outcome::Running)));
+    lines
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "\
+Jun  1 09:00:00 timerterm[10]: START duration=1500s label=\"Thesis \\\"ch. 2\\\"\" dir=\"/home/me/thesis\"
+Jun  1 09:25:00 timerterm[10]: COMPLETED elapsed=1500s
+Jun  1 09:30:00 timerterm[11]: PHASE index=0 label=\"Work 1/4\"
+Jun  1 09:30:00 timerterm[11]: START duration=1500s
+Jun  1 09:31:00 timerterm[12]: START duration=600s estimate=300s
+Jun  1 09:35:00 timerterm[12]: PAUSE elapsed=240s reason=tea
+Jun  1 09:40:00 timerterm[12]: CANCELLED elapsed=240s
+Jun  2 08:00:00 timerterm[13]: START duration=60s
+not a log line
+";
+
+    #[test]
+    fn sessions_pair_starts_with_ends() {
+        // Test: Runs are matched by pid, labels come from START or the PHASE before it
+        let sessions = sessions(LOG, "Jun  1");
+        assert_eq!(sessions, vec![
+            Session { start: 9 * 3600, end: Some(9 * 3600 + 1500), outcome: Outcome::Completed, label: Some("Thesis \"ch. 2\"".into()),
+                dir: Some("/home/me/thesis".into()), elapsed: Some(1500), estimate: None },
+            Session { start: 9 * 3600 + 1800, end: None, outcome: Outcome::Running, label: Some("Work 1/4".into()), dir: None, elapsed: None, estimate: None },
+            Session { start: 9 * 3600 + 1860, end: Some(9 * 3600 + 2400), outcome: Outcome::Cancelled, label: None, dir: None, elapsed: Some(240),
+                estimate: Some(300) },
+        ]);
+        // Test: Every day's runs, the one still going last
+        let every: Vec<u32> = every_session(LOG).iter().map(|s| s.start).collect();
+        assert_eq!(every, [9 * 3600, 9 * 3600 + 1860, 9 * 3600 + 1800, 8 * 3600]);
+        // Test: A second START from the same process ends the first (r restarts)
+        let restarted = "Jun  1 09:00:00 timerterm[5]: START duration=60s\nJun  1 09:00:30 timerterm[5]: START duration=60s\n";
+        assert_eq!(super::sessions(restarted, "Jun  1")[0].outcome, Outcome::Cancelled);
+    }
+
+    #[test]
+    fn timeline_draws_hours_blocks_and_totals() {
+        // Test: 09:00-11:00 over 24 columns is 5 minutes a column
+        let sessions = vec![
+            Session { start: 9 * 3600, end: Some(9 * 3600 + 1800), outcome: Outcome::Completed, label: Some("Pasta".into()), dir: None, elapsed: Some(1800), estimate: None },
+            Session { start: 10 * 3600, end: None, outcome: Outcome::Running, label: None, dir: None, elapsed: None, estimate: None },
+        ];
+        let lines = timeline(&sessions, 10 * 3600 + 600, 24, false);
+        assert_eq!(lines[0], "09          10");
+        assert_eq!(lines[1], format!("{}{}▓▓{}", "█".repeat(6), " ".repeat(6), " ".repeat(10)));
+        assert_eq!(lines[3], "█ Pasta       30:00, 1 run");
+        assert_eq!(lines[4], "█ (no label)  10:00, 1 run");
+        assert_eq!(lines[5], "1 completed, 0 cancelled, 1 running  (█ ░ ▓)");
+        // Test: Labeled blocks are colored, unlabeled ones aren't
+        let lines = timeline(&sessions, 10 * 3600 + 600, 24, true);
+        assert!(lines[1].starts_with(&format!("{}█{}", ansi::fg("cyan").unwrap(), ansi::RESET)));
+        assert!(lines[1].contains("  ▓▓  "));
+    }
+}
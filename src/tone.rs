@@ -0,0 +1,121 @@
+// src/tone.rs
+// Alarms synthesized from the config file, for when there's no sound file:
+//   [tone]
+//   alarm = "880/200 0/100 880/200 0/100 660+880+1320/600"
+//   chime = "1320/150"
+// Each note is its frequencies in Hz, joined with + for a chord, then / and
+// how many milliseconds it lasts. 0 Hz is a rest. The notes are rendered
+// into a WAV file under the temp dir, which plays like any other sound.
+use std::f64::consts::TAU;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+// Frequencies in Hz (none for a rest) and how long they sound
+pub type Tone = Vec<(Vec<u32>, Duration)>;
+
+// Played when there's no sound file for the same alert
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Tones {
+    pub alarm: Option<Tone>,
+    pub chime: Option<Tone>,
+}
+
+const RATE: u32 = 22_050;
+// Faded in and out over this long, a note starting at full volume clicks
+const RAMP: Duration = Duration::from_millis(5);
+
+pub fn parse(text: &str) -> Result<Tone, String> {
+    let tone: Tone = text.split_whitespace().map(|note| {
+        let bad = || format!("'{}' isn't HZ/MS like 880/200", note);
+        let (freqs, ms) = note.split_once('/').ok_or_else(bad)?;
+        let ms: u64 = ms.parse().ok().filter(|ms| (1..=10_000).contains(ms)).ok_or_else(bad)?;
+        let freqs = freqs.split('+').map(|hz| hz.parse::<u32>().ok().filter(|hz| *hz <= 20_000).ok_or_else(bad))
+            .collect::<Result<Vec<u32>, String>>()?;
+        Ok((freqs.into_iter().filter(|&hz| hz > 0).collect(), Duration::from_millis(ms)))
+    }).collect::<Result<_, String>>()?;
+    if tone.is_empty() { return Err("nothing to play".to_string()); }
+    Ok(tone)
+}
+
+// 16-bit mono samples, a chord's notes summed at an equal share each
+pub fn samples(tone: &Tone) -> Vec<i16> {
+    let ramp = (RAMP.as_secs_f64() * RATE as f64) as usize;
+    let mut out = Vec::new();
+    for (freqs, length) in tone {
+        let count = (length.as_secs_f64() * RATE as f64) as usize;
+        for i in 0..count {
+            let edge = i.min(count - 1 - i);
+            let envelope = if edge < ramp { edge as f64 / ramp as f64 } else { 1.0 };
+            let t = i as f64 / RATE as f64;
+            let wave: f64 = freqs.iter().map(|&hz| (TAU * hz as f64 * t).sin()).sum::<f64>() / freqs.len().max(1) as f64;
+            out.push((wave * envelope * 0.8 * i16::MAX as f64) as i16);
+        }
+    }
+    out
+}
+
+pub fn wav(samples: &[i16]) -> Vec<u8> {
+    let data = samples.len() as u32 * 2;
+    let mut out = Vec::with_capacity(44 + data as usize);
+    out.extend(b"RIFF");
+    out.extend((36 + data).to_le_bytes());
+    out.extend(b"WAVEfmt ");
+    out.extend(16u32.to_le_bytes());
+    out.extend(1u16.to_le_bytes()); // PCM
+    out.extend(1u16.to_le_bytes()); // Mono
+    out.extend(RATE.to_le_bytes());
+    out.extend((RATE * 2).to_le_bytes()); // Bytes a second
+    out.extend(2u16.to_le_bytes()); // Bytes a frame
+    out.extend(16u16.to_le_bytes());
+    out.extend(b"data");
+    out.extend(data.to_le_bytes());
+    for sample in samples { out.extend(sample.to_le_bytes()); }
+    out
+}
+
+// The tone's WAV file, written once and then shared by every run playing
+// the same notes. Written aside and renamed, so a player never reads half.
+pub fn file(tone: &Tone) -> io::Result<PathBuf> {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    tone.hash(&mut hasher);
+    let path = std::env::temp_dir().join(format!("timerterm-tone-{:016x}.wav", hasher.finish()));
+    if !path.is_file() {
+        let part = path.with_extension(format!("{}.part", std::process::id()));
+        std::fs::write(&part, wav(&samples(tone)))?;
+        std::fs::rename(&part, &path)?;
+    }
+    Ok(path)
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_notes_chords_and_rests() {
+        let ms = Duration::from_millis;
+        assert_eq!(parse("880/200 0/100 440+660/50").unwrap(), vec![(vec![880], ms(200)), (vec![], ms(100)), (vec![440, 660], ms(50))]);
+        assert_eq!(parse("880"), Err("'880' isn't HZ/MS like 880/200".to_string()));
+        assert!(parse("880/0").is_err());
+        assert!(parse("a/100").is_err());
+        assert!(parse(" ").is_err());
+    }
+
+    #[test]
+    fn samples_fade_in_and_out() {
+        let tone = parse("1000/100 0/50").unwrap();
+        let samples = samples(&tone);
+        // Test: 150ms at 22050 a second, the rest silent
+        assert_eq!(samples.len(), 3307);
+        assert!(samples[2205..].iter().all(|&s| s == 0));
+        assert_eq!(samples[0], 0);
+        let loudest = samples.iter().map(|s| s.unsigned_abs()).max().unwrap();
+        assert!(loudest > 20_000 && loudest <= (0.8 * i16::MAX as f64) as u16);
+        let wav = wav(&samples);
+        assert_eq!(&wav[..4], b"RIFF");
+        assert_eq!(wav.len(), 44 + samples.len() * 2);
+    }
+}
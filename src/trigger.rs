@@ -0,0 +1,88 @@
+// src/trigger.rs
+// What an armed timer waits for before it starts counting, so the deadline is
+// set by the trigger rather than by launching: --wait-for-key,
+// --wait-for-file PATH, --after-pid N or --after NAME.
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use crate::platform;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Trigger {
+    Key,           // Space, or resume/toggle on the control FIFO
+    File(PathBuf), // The path existing, polled every tick
+    Pid(u32),      // That process exiting, polled the same way
+    Named(String, Vec<u32>), // Every process so named exiting, the pids found by resolve
+}
+
+impl Trigger {
+    // Shown in place of the status while armed
+    pub fn prompt(&self) -> String {
+        match self {
+            Trigger::Key => "Press space to start".to_string(),
+            Trigger::File(path) => format!("Waiting for {}", path.display()),
+            Trigger::Pid(pid) => format!("Waiting for PID {} to exit", pid),
+            Trigger::Named(name, _) => format!("Waiting for {} to exit", name),
+        }
+    }
+
+    // Look up the processes a trigger waits on, so one that's already gone
+    // is an error rather than an instant start. --after goes by process
+    // name through pgrep, once: a later process of that name doesn't count.
+    pub fn resolve(self) -> Result<Trigger, String> {
+        match self {
+            Trigger::Pid(pid) if !platform::process_exists(pid) => Err(format!("no process with PID {} is running", pid)),
+            Trigger::Named(name, _) => {
+                let out = Command::new("pgrep").arg("-x").arg(&name).stderr(Stdio::null()).output()
+                    .map_err(|e| format!("couldn't run pgrep to find {}: {}", name, e))?;
+                let pids: Vec<u32> = String::from_utf8_lossy(&out.stdout).lines().filter_map(|l| l.trim().parse().ok()).collect();
+                if pids.is_empty() { return Err(format!("no process named {} is running", name)); }
+                Ok(Trigger::Named(name, pids))
+            }
+            other => Ok(other),
+        }
+    }
+
+    // True once it has gone off by itself, a key is the caller's to watch for
+    pub fn fired(&self) -> bool {
+        match self {
+            Trigger::Key => false,
+            Trigger::File(path) => path.exists(),
+            Trigger::Pid(pid) => !platform::process_exists(*pid),
+            Trigger::Named(_, pids) => !pids.iter().any(|&pid| platform::process_exists(pid)),
+        }
+    }
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_trigger_fires_once_the_file_exists() {
+        let path = std::env::temp_dir().join(format!("timerterm-trigger-{}", std::process::id()));
+        let trigger = Trigger::File(path.clone());
+        assert!(!trigger.fired());
+        assert_eq!(trigger.prompt(), format!("Waiting for {}", path.display()));
+        std::fs::write(&path, "").unwrap();
+        assert!(trigger.fired());
+        std::fs::remove_file(&path).unwrap();
+        // Test: A key trigger never fires on its own
+        assert!(!Trigger::Key.fired());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn exit_triggers_fire_once_the_process_is_gone() {
+        let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+        let trigger = Trigger::Pid(child.id()).resolve().unwrap();
+        assert!(!trigger.fired());
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert!(trigger.fired());
+        // Test: Nothing to wait for is an error up front
+        assert_eq!(Trigger::Pid(child.id()).resolve(), Err(format!("no process with PID {} is running", child.id())));
+        assert_eq!(Trigger::Named("timerterm-no-such".into(), vec![]).resolve(), Err("no process named timerterm-no-such is running".to_string()));
+    }
+}
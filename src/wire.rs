@@ -0,0 +1,453 @@
+// src/wire.rs
+// Serialized format of the public types, shared by JSON output, IPC and
+// downstream tools. Durations go over the wire as integer milliseconds.
+//
+// Compatibility rules (enforced by the tests below):
+// - Unknown fields are ignored, so older readers accept newer payloads
+// - New fields must be optional with a default, so newer readers accept older payloads
+// - Renaming or removing a field is a breaking change
+use std::fmt;
+use std::time::Duration;
+
+use serde::de::{self, Deserializer, IgnoredAny, MapAccess, Visitor};
+use serde::ser::{SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::event::{Status, Summary, TimerEvent};
+use crate::plan::{Phase, TimerPlan};
+use crate::timer::Direction;
+
+fn ms(d: Duration) -> u64 {
+    d.as_millis() as u64
+}
+
+fn from_ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+// Read every key of an object, handing known ones to `field` and skipping the rest
+fn each_field<'de, A, F>(mut map: A, mut field: F) -> Result<(), A::Error>
+where
+    A: MapAccess<'de>,
+    F: FnMut(&str, &mut A) -> Result<bool, A::Error>,
+{
+    while let Some(key) = map.next_key::<String>()? {
+        if !field(&key, &mut map)? {
+            map.next_value::<IgnoredAny>()?;
+        }
+    }
+    Ok(())
+}
+
+// ============ Phase =============
+
+impl Serialize for Phase {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut st = s.serialize_struct("Phase", 2)?;
+        st.serialize_field("label", &self.label)?;
+        st.serialize_field("duration_ms", &ms(self.duration))?;
+        st.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Phase {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        struct PhaseVisitor;
+        impl<'de> Visitor<'de> for PhaseVisitor {
+            type Value = Phase;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a phase object")
+            }
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Phase, A::Error> {
+                let (mut label, mut duration) = (None, None);
+                each_field(map, |key, map| Ok(match key {
+                    "label" => { label = Some(map.next_value::<String>()?); true }
+                    "duration_ms" => { duration = Some(map.next_value::<u64>()?); true }
+                    _ => false,
+                }))?;
+                let duration = duration.ok_or_else(|| de::Error::missing_field("duration_ms"))?;
+                Ok(Phase { label: label.unwrap_or_default(), duration: from_ms(duration) })
+            }
+        }
+        d.deserialize_struct("Phase", &["label", "duration_ms"], PhaseVisitor)
+    }
+}
+
+// ============ TimerPlan =============
+
+impl Serialize for TimerPlan {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut st = s.serialize_struct("TimerPlan", 1)?;
+        st.serialize_field("phases", &self.phases)?;
+        st.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for TimerPlan {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        struct PlanVisitor;
+        impl<'de> Visitor<'de> for PlanVisitor {
+            type Value = TimerPlan;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a plan object")
+            }
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<TimerPlan, A::Error> {
+                let mut phases = None;
+                each_field(map, |key, map| Ok(match key {
+                    "phases" => { phases = Some(map.next_value::<Vec<Phase>>()?); true }
+                    _ => false,
+                }))?;
+                let phases = phases.ok_or_else(|| de::Error::missing_field("phases"))?;
+                Ok(TimerPlan { phases })
+            }
+        }
+        d.deserialize_struct("TimerPlan", &["phases"], PlanVisitor)
+    }
+}
+
+// ============ TimerEvent =============
+
+const EVENT_FIELDS: &[&str] = &["event", "duration_ms", "elapsed_ms", "estimate_ms", "index", "label", "tag", "dir", "reason", "note"];
+pub(crate) const EVENT_KINDS: &[&str] = &["started", "paused", "resumed", "phase_started", "adjusted", "interrupted", "reflected", "finished", "cancelled"];
+
+impl TimerEvent {
+    // Name used for the "event" tag on the wire
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TimerEvent::Started { .. } => "started",
+            TimerEvent::Paused { .. } => "paused",
+            TimerEvent::Resumed { .. } => "resumed",
+            TimerEvent::PhaseStarted { .. } => "phase_started",
+            TimerEvent::Adjusted { .. } => "adjusted",
+            TimerEvent::Interrupted { .. } => "interrupted",
+            TimerEvent::Reflected { .. } => "reflected",
+            TimerEvent::Finished { .. } => "finished",
+            TimerEvent::Cancelled { .. } => "cancelled",
+        }
+    }
+}
+
+impl Serialize for TimerEvent {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut st = s.serialize_struct("TimerEvent", 3)?;
+        st.serialize_field("event", self.kind())?;
+        match self {
+            TimerEvent::Started { duration, elapsed, estimate, label, tag, dir } => {
+                st.serialize_field("duration_ms", &ms(*duration))?;
+                if !elapsed.is_zero() { st.serialize_field("elapsed_ms", &ms(*elapsed))?; }
+                if let Some(estimate) = estimate { st.serialize_field("estimate_ms", &ms(*estimate))?; }
+                if let Some(label) = label { st.serialize_field("label", label)?; }
+                if let Some(tag) = tag { st.serialize_field("tag", tag)?; }
+                if let Some(dir) = dir { st.serialize_field("dir", dir)?; }
+            }
+            TimerEvent::PhaseStarted { index, label } => {
+                st.serialize_field("index", index)?;
+                st.serialize_field("label", label)?;
+            }
+            TimerEvent::Paused { elapsed, reason } => {
+                st.serialize_field("elapsed_ms", &ms(*elapsed))?;
+                // Optional, so only written when there is one
+                if let Some(reason) = reason { st.serialize_field("reason", reason)?; }
+            }
+            TimerEvent::Adjusted { elapsed, duration } => {
+                st.serialize_field("elapsed_ms", &ms(*elapsed))?;
+                st.serialize_field("duration_ms", &ms(*duration))?;
+            }
+            TimerEvent::Interrupted { elapsed, note } => {
+                st.serialize_field("elapsed_ms", &ms(*elapsed))?;
+                st.serialize_field("note", note)?;
+            }
+            TimerEvent::Reflected { note } => st.serialize_field("note", note)?,
+            TimerEvent::Resumed { elapsed }
+            | TimerEvent::Finished { elapsed }
+            | TimerEvent::Cancelled { elapsed } => st.serialize_field("elapsed_ms", &ms(*elapsed))?,
+        }
+        st.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for TimerEvent {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        struct EventVisitor;
+        impl<'de> Visitor<'de> for EventVisitor {
+            type Value = TimerEvent;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an event object")
+            }
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<TimerEvent, A::Error> {
+                let (mut kind, mut duration, mut elapsed, mut estimate, mut index, mut label, mut tag, mut dir, mut reason, mut note) = (None, None, None, None, None, None, None, None, None, None);
+                each_field(map, |key, map| Ok(match key {
+                    "event" => { kind = Some(map.next_value::<String>()?); true }
+                    "duration_ms" => { duration = Some(map.next_value::<u64>()?); true }
+                    "elapsed_ms" => { elapsed = Some(map.next_value::<u64>()?); true }
+                    "estimate_ms" => { estimate = Some(map.next_value::<u64>()?); true }
+                    "index" => { index = Some(map.next_value::<usize>()?); true }
+                    "label" => { label = Some(map.next_value::<String>()?); true }
+                    "tag" => { tag = Some(map.next_value::<String>()?); true }
+                    "dir" => { dir = Some(map.next_value::<String>()?); true }
+                    "reason" => { reason = map.next_value::<Option<String>>()?; true }
+                    "note" => { note = Some(map.next_value::<String>()?); true }
+                    _ => false,
+                }))?;
+
+                let kind = kind.ok_or_else(|| de::Error::missing_field("event"))?;
+                let elapsed = || elapsed.map(from_ms).ok_or_else(|| de::Error::missing_field("elapsed_ms"));
+                Ok(match kind.as_str() {
+                    "started" => TimerEvent::Started {
+                        duration: duration.map(from_ms).ok_or_else(|| de::Error::missing_field("duration_ms"))?,
+                        elapsed: elapsed().unwrap_or_default(), // Older logs have none
+                        estimate: estimate.map(from_ms),
+                        label,
+                        tag,
+                        dir,
+                    },
+                    "paused" => TimerEvent::Paused { elapsed: elapsed()?, reason },
+                    "resumed" => TimerEvent::Resumed { elapsed: elapsed()? },
+                    "phase_started" => TimerEvent::PhaseStarted {
+                        index: index.ok_or_else(|| de::Error::missing_field("index"))?,
+                        label: label.unwrap_or_default(),
+                    },
+                    "adjusted" => TimerEvent::Adjusted {
+                        elapsed: elapsed()?,
+                        duration: duration.map(from_ms).ok_or_else(|| de::Error::missing_field("duration_ms"))?,
+                    },
+                    "interrupted" => TimerEvent::Interrupted {
+                        elapsed: elapsed()?,
+                        note: note.ok_or_else(|| de::Error::missing_field("note"))?,
+                    },
+                    "reflected" => TimerEvent::Reflected { note: note.ok_or_else(|| de::Error::missing_field("note"))? },
+                    "finished" => TimerEvent::Finished { elapsed: elapsed()? },
+                    "cancelled" => TimerEvent::Cancelled { elapsed: elapsed()? },
+                    other => return Err(de::Error::unknown_variant(other, EVENT_KINDS)),
+                })
+            }
+        }
+        d.deserialize_struct("TimerEvent", EVENT_FIELDS, EventVisitor)
+    }
+}
+
+// ============ Summary =============
+
+impl Serialize for Summary {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut st = s.serialize_struct("Summary", 5)?;
+        st.serialize_field("label", &self.label)?;
+        st.serialize_field("planned_ms", &ms(self.planned))?;
+        st.serialize_field("elapsed_ms", &ms(self.elapsed))?;
+        st.serialize_field("completed", &self.completed)?;
+        if !self.overtime.is_zero() { st.serialize_field("overtime_ms", &ms(self.overtime))?; }
+        st.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Summary {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        struct SummaryVisitor;
+        impl<'de> Visitor<'de> for SummaryVisitor {
+            type Value = Summary;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a summary object")
+            }
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Summary, A::Error> {
+                let (mut label, mut planned, mut elapsed, mut completed, mut overtime) = (None, None, None, None, None);
+                each_field(map, |key, map| Ok(match key {
+                    "label" => { label = map.next_value::<Option<String>>()?; true }
+                    "planned_ms" => { planned = Some(map.next_value::<u64>()?); true }
+                    "elapsed_ms" => { elapsed = Some(map.next_value::<u64>()?); true }
+                    "completed" => { completed = Some(map.next_value::<bool>()?); true }
+                    "overtime_ms" => { overtime = Some(map.next_value::<u64>()?); true }
+                    _ => false,
+                }))?;
+                Ok(Summary {
+                    label,
+                    planned: from_ms(planned.ok_or_else(|| de::Error::missing_field("planned_ms"))?),
+                    elapsed: from_ms(elapsed.ok_or_else(|| de::Error::missing_field("elapsed_ms"))?),
+                    completed: completed.ok_or_else(|| de::Error::missing_field("completed"))?,
+                    overtime: overtime.map(from_ms).unwrap_or_default(), // Only written when there was some
+                })
+            }
+        }
+        d.deserialize_struct("Summary", &["label", "planned_ms", "elapsed_ms", "completed", "overtime_ms"], SummaryVisitor)
+    }
+}
+
+// ============ Status =============
+
+impl Direction {
+    // "down" or "up" on the wire
+    pub fn name(self) -> &'static str {
+        match self {
+            Direction::Down => "down",
+            Direction::Up => "up",
+        }
+    }
+}
+
+impl Serialize for Status {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut st = s.serialize_struct("Status", 7)?;
+        st.serialize_field("label", &self.label)?;
+        st.serialize_field("direction", self.direction.name())?;
+        st.serialize_field("duration_ms", &ms(self.duration))?;
+        st.serialize_field("elapsed_ms", &ms(self.elapsed))?;
+        if let Some(remaining) = self.remaining { st.serialize_field("remaining_ms", &ms(remaining))?; }
+        st.serialize_field("paused", &self.paused)?;
+        st.serialize_field("finished", &self.finished)?;
+        st.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Status {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        struct StatusVisitor;
+        impl<'de> Visitor<'de> for StatusVisitor {
+            type Value = Status;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a status object")
+            }
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Status, A::Error> {
+                let (mut label, mut direction, mut duration, mut elapsed) = (None, None, None, None);
+                let (mut remaining, mut paused, mut finished) = (None, None, None);
+                each_field(map, |key, map| Ok(match key {
+                    "label" => { label = map.next_value::<Option<String>>()?; true }
+                    "direction" => { direction = Some(map.next_value::<String>()?); true }
+                    "duration_ms" => { duration = Some(map.next_value::<u64>()?); true }
+                    "elapsed_ms" => { elapsed = Some(map.next_value::<u64>()?); true }
+                    "remaining_ms" => { remaining = Some(map.next_value::<u64>()?); true }
+                    "paused" => { paused = Some(map.next_value::<bool>()?); true }
+                    "finished" => { finished = Some(map.next_value::<bool>()?); true }
+                    _ => false,
+                }))?;
+                let direction = match direction.ok_or_else(|| de::Error::missing_field("direction"))?.as_str() {
+                    "down" => Direction::Down,
+                    "up" => Direction::Up,
+                    other => return Err(de::Error::unknown_variant(other, &["down", "up"])),
+                };
+                Ok(Status {
+                    label,
+                    direction,
+                    duration: from_ms(duration.ok_or_else(|| de::Error::missing_field("duration_ms"))?),
+                    elapsed: from_ms(elapsed.ok_or_else(|| de::Error::missing_field("elapsed_ms"))?),
+                    remaining: remaining.map(from_ms), // Only a countdown has it
+                    paused: paused.ok_or_else(|| de::Error::missing_field("paused"))?,
+                    finished: finished.ok_or_else(|| de::Error::missing_field("finished"))?,
+                })
+            }
+        }
+        d.deserialize_struct("Status", STATUS_FIELDS, StatusVisitor)
+    }
+}
+
+const STATUS_FIELDS: &[&str] = &["label", "direction", "duration_ms", "elapsed_ms", "remaining_ms", "paused", "finished"];
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json;
+
+    fn ms(n: u64) -> Duration { Duration::from_millis(n) }
+
+    #[test]
+    fn plan_golden_format() {
+        // Test: Field names are part of the public schema, changes must be deliberate
+        let plan = TimerPlan::new(vec![Phase::new("work", ms(1_500_000)), Phase::new("break", ms(300_000))]);
+        assert_eq!(
+            json::to_string(&plan).unwrap(),
+            r#"{"phases":[{"label":"work","duration_ms":1500000},{"label":"break","duration_ms":300000}]}"#
+        );
+    }
+
+    #[test]
+    fn event_golden_format() {
+        let started = TimerEvent::Started { duration: ms(60_000), elapsed: ms(0), estimate: None, label: None, tag: None, dir: None };
+        assert_eq!(json::to_string(&started).unwrap(), r#"{"event":"started","duration_ms":60000}"#);
+        let resumed = TimerEvent::Started { duration: ms(60_000), elapsed: ms(5_000), estimate: Some(ms(45_000)), label: Some("Pasta".into()), tag: Some("thesis:vim".into()), dir: Some("/home/me/thesis".into()) };
+        assert_eq!(json::to_string(&resumed).unwrap(), r#"{"event":"started","duration_ms":60000,"elapsed_ms":5000,"estimate_ms":45000,"label":"Pasta","tag":"thesis:vim","dir":"/home/me/thesis"}"#);
+        let phase = TimerEvent::PhaseStarted { index: 1, label: "break".into() };
+        assert_eq!(json::to_string(&phase).unwrap(), r#"{"event":"phase_started","index":1,"label":"break"}"#);
+    }
+
+    #[test]
+    fn summary_golden_format() {
+        let summary = Summary { label: None, planned: ms(600_000), elapsed: ms(1_234), completed: false, overtime: ms(0) };
+        assert_eq!(
+            json::to_string(&summary).unwrap(),
+            r#"{"label":null,"planned_ms":600000,"elapsed_ms":1234,"completed":false}"#
+        );
+    }
+
+    #[test]
+    fn status_golden_format() {
+        let status = Status { label: Some("Tea".into()), direction: Direction::Down, duration: ms(180_000), elapsed: ms(60_000),
+            remaining: Some(ms(120_000)), paused: true, finished: false };
+        assert_eq!(
+            json::to_string(&status).unwrap(),
+            r#"{"label":"Tea","direction":"down","duration_ms":180000,"elapsed_ms":60000,"remaining_ms":120000,"paused":true,"finished":false}"#
+        );
+        // Test: A stopwatch has no remaining_ms
+        let status = Status { label: None, direction: Direction::Up, duration: ms(0), elapsed: ms(5), remaining: None, paused: false, finished: false };
+        assert!(!json::to_string(&status).unwrap().contains("remaining_ms"));
+    }
+
+    #[test]
+    fn all_types_roundtrip() {
+        let plan = TimerPlan::new(vec![Phase::new("☕ Break", ms(42))]);
+        assert_eq!(json::from_str::<TimerPlan>(&json::to_string(&plan).unwrap()).unwrap(), plan);
+
+        let events = vec![
+            TimerEvent::Started { duration: ms(10), elapsed: ms(0), estimate: None, label: None, tag: None, dir: None },
+            TimerEvent::Started { duration: ms(10), elapsed: ms(4), estimate: Some(ms(8)), label: Some("Pasta".into()), tag: Some("thesis:vim".into()), dir: Some("/home/me/thesis".into()) },
+            TimerEvent::Paused { elapsed: ms(1), reason: None },
+            TimerEvent::Paused { elapsed: ms(1), reason: Some("phone".into()) },
+            TimerEvent::Resumed { elapsed: ms(1) },
+            TimerEvent::PhaseStarted { index: 2, label: "rest".into() },
+            TimerEvent::Adjusted { elapsed: ms(2), duration: ms(70) },
+            TimerEvent::Interrupted { elapsed: ms(5), note: "Sam \"quick\" call".into() },
+            TimerEvent::Reflected { note: "Shipped it".into() },
+            TimerEvent::Finished { elapsed: ms(10) },
+            TimerEvent::Cancelled { elapsed: ms(3) },
+        ];
+        for event in events {
+            let text = json::to_string(&event).unwrap();
+            assert_eq!(json::from_str::<TimerEvent>(&text).unwrap(), event);
+        }
+
+        let summary = Summary { label: Some("Tea".into()), planned: ms(180_000), elapsed: ms(180_000), completed: true, overtime: ms(42_000) };
+        assert_eq!(json::from_str::<Summary>(&json::to_string(&summary).unwrap()).unwrap(), summary);
+
+        for remaining in [Some(ms(7)), None] {
+            let status = Status { label: None, direction: Direction::Up, duration: ms(9), elapsed: ms(2), remaining, paused: false, finished: true };
+            assert_eq!(json::from_str::<Status>(&json::to_string(&status).unwrap()).unwrap(), status);
+        }
+    }
+
+    #[test]
+    fn unknown_fields_are_ignored() {
+        // Schema evolution: a newer writer added fields an older reader doesn't know
+        let phase: Phase = json::from_str(r#"{"label":"work","duration_ms":5,"color":"red","tags":["a",{"b":1}]}"#).unwrap();
+        assert_eq!(phase, Phase::new("work", ms(5)));
+        let event: TimerEvent = json::from_str(r#"{"event":"paused","elapsed_ms":7,"mood":"tired"}"#).unwrap();
+        assert_eq!(event, TimerEvent::Paused { elapsed: ms(7), reason: None });
+    }
+
+    #[test]
+    fn optional_fields_default_when_missing() {
+        // Schema evolution: an older writer didn't send optional fields
+        let phase: Phase = json::from_str(r#"{"duration_ms":5}"#).unwrap();
+        assert_eq!(phase.label, "");
+        let summary: Summary = json::from_str(r#"{"planned_ms":1,"elapsed_ms":1,"completed":true}"#).unwrap();
+        assert_eq!(summary.label, None);
+        assert_eq!(summary.overtime, Duration::ZERO);
+    }
+
+    #[test]
+    fn required_fields_and_kinds_are_enforced() {
+        // Test: Missing required fields are reported by name
+        let err = json::from_str::<Phase>(r#"{"label":"x"}"#).unwrap_err();
+        assert!(err.to_string().contains("duration_ms"));
+        // Test: Unknown event kinds are rejected rather than misread
+        let err = json::from_str::<TimerEvent>(r#"{"event":"exploded"}"#).unwrap_err();
+        assert!(err.to_string().contains("exploded"));
+    }
+}
@@ -0,0 +1,329 @@
+// src/zone.rs
+// Clock times in other time zones, for the world clock under a countdown:
+//   [display]
+//   world_clock = "Europe/London, America/New_York"
+// Read straight from the system's TZif files under /usr/share/zoneinfo (or
+// $TZDIR), no tz database is built in. Past a file's last transition the
+// POSIX TZ rule at its end takes over, which is all a "slim" file has.
+use std::path::PathBuf;
+
+use crate::calendar;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Zone {
+    pub name: String,            // "America/New_York"
+    transitions: Vec<(i64, i32)>, // From when, the offset east of UTC in seconds
+    initial: i32,                // Before the first transition
+    rule: Option<Rule>,          // After the last one
+}
+
+impl Zone {
+    pub fn load(name: &str) -> Result<Zone, String> {
+        let dir = std::env::var_os("TZDIR").filter(|d| !d.is_empty()).map_or_else(|| PathBuf::from("/usr/share/zoneinfo"), PathBuf::from);
+        // A name is a path under it, never out of it
+        if name.is_empty() || name.starts_with('/') || name.split('/').any(|part| part == "..") {
+            return Err(format!("'{}' isn't a time zone name like Europe/London", name));
+        }
+        let bytes = std::fs::read(dir.join(name)).map_err(|e| format!("no time zone {} in {}: {}", name, dir.display(), e))?;
+        Zone::parse(name, &bytes)
+    }
+
+    // RFC 8536. Version 2 and later files repeat the data with 64-bit times
+    // after the version 1 block, then end in the TZ rule.
+    pub fn parse(name: &str, bytes: &[u8]) -> Result<Zone, String> {
+        let bad = || format!("{} isn't a time zone file", name);
+        let (header, version) = Header::read(bytes).ok_or_else(bad)?;
+        let (header, at, size) = match version {
+            0 => (header, 44, 4),
+            _ => {
+                let second = 44 + header.len(4);
+                (Header::read(bytes.get(second..).ok_or_else(bad)?).ok_or_else(bad)?.0, second + 44, 8)
+            }
+        };
+        let data = bytes.get(at..at + header.len(size)).ok_or_else(bad)?;
+        let times = &data[..header.times * size];
+        let indices = &data[header.times * size..header.times * (size + 1)];
+        let types = &data[header.times * (size + 1)..header.times * (size + 1) + header.types * 6];
+        let offset = |index: usize| types.get(index * 6..index * 6 + 4).map(|b| i32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+        let mut transitions = Vec::with_capacity(header.times);
+        for (time, &index) in times.chunks(size).zip(indices) {
+            let time = match *time {
+                [a, b, c, d] => i32::from_be_bytes([a, b, c, d]) as i64,
+                _ => i64::from_be_bytes(time.try_into().map_err(|_| bad())?),
+            };
+            transitions.push((time, offset(index as usize).ok_or_else(bad)?));
+        }
+        let initial = offset(0).ok_or_else(bad)?;
+        let rule = match version {
+            0 => None,
+            _ => {
+                let footer = String::from_utf8_lossy(&bytes[at + data.len()..]);
+                let tz = footer.trim_matches('\n');
+                if tz.is_empty() { None } else { Some(Rule::parse(tz).ok_or_else(|| format!("{} has a TZ rule '{}' that can't be read", name, tz))?) }
+            }
+        };
+        Ok(Zone { name: name.to_string(), transitions, initial, rule })
+    }
+
+    // Seconds east of UTC at `unix`
+    pub fn offset(&self, unix: i64) -> i32 {
+        let last = self.transitions.iter().rposition(|&(at, _)| at <= unix);
+        let past_all = last.map_or(self.transitions.is_empty(), |i| i + 1 == self.transitions.len());
+        match (&self.rule, last) {
+            (Some(rule), _) if past_all => rule.offset(unix),
+            (_, Some(i)) => self.transitions[i].1,
+            (_, None) => self.initial,
+        }
+    }
+
+    // "America/New_York" is "New York"
+    pub fn city(&self) -> String {
+        self.name.rsplit('/').next().unwrap_or(&self.name).replace('_', " ")
+    }
+
+    // "New York 09:05"
+    pub fn clock(&self, unix: i64) -> String {
+        let local = (unix + self.offset(unix) as i64).rem_euclid(86400);
+        format!("{} {:02}:{:02}", self.city(), local / 3600, local / 60 % 60)
+    }
+}
+
+// One line of each zone's clock, leaving off those that don't fit in `width`
+pub fn footer(zones: &[Zone], unix: i64, width: usize) -> String {
+    let mut line = String::new();
+    for clock in zones.iter().map(|zone| zone.clock(unix)) {
+        let sep = if line.is_empty() { "" } else { "  " };
+        if crate::text::display_width(&line) + sep.len() + crate::text::display_width(&clock) > width { break; }
+        line.push_str(sep);
+        line.push_str(&clock);
+    }
+    line
+}
+
+// The counts in a TZif header
+struct Header {
+    utc: usize,
+    std: usize,
+    leaps: usize,
+    times: usize,
+    types: usize,
+    chars: usize,
+}
+
+impl Header {
+    // And the version, 0 for the original format
+    fn read(bytes: &[u8]) -> Option<(Header, u8)> {
+        if bytes.get(..4)? != b"TZif" { return None; }
+        let version = match *bytes.get(4)? { 0 => 0, v @ b'2'..=b'9' => v - b'0', _ => return None };
+        let count = |i: usize| bytes.get(20 + i * 4..24 + i * 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as usize);
+        Some((Header { utc: count(0)?, std: count(1)?, leaps: count(2)?, times: count(3)?, types: count(4)?, chars: count(5)? }, version))
+    }
+
+    // The data block's length with `size` byte times
+    fn len(&self, size: usize) -> usize {
+        self.times * (size + 1) + self.types * 6 + self.chars + self.leaps * (size + 4) + self.std + self.utc
+    }
+}
+
+// A POSIX TZ rule like "EST5EDT,M3.2.0,M11.1.0", offsets east of UTC
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    std: i32,
+    dst: Option<(i32, Change, Change)>, // Its offset, when it starts and ends
+}
+
+// A day of the year and the local time on it the clocks change
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Change {
+    day: Day,
+    secs: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Day {
+    Julian(i64),          // Jn, 1 to 365, Feb 29 never counted
+    Zero(i64),            // n, 0 to 365, Feb 29 counted
+    Month(i64, i64, i64), // Mm.w.d, the w-th (5 is last) weekday d of month m
+}
+
+impl Rule {
+    pub fn parse(tz: &str) -> Option<Rule> {
+        let mut rest = tz;
+        name(&mut rest)?;
+        let std = -clock(&mut rest)?;
+        if rest.is_empty() { return Some(Rule { std, dst: None }); }
+        name(&mut rest)?;
+        let dst = if rest.is_empty() || rest.starts_with(',') { std + 3600 } else { -clock(&mut rest)? };
+        // Without dates, POSIX leaves it to the implementation: the US ones
+        let rest = if rest.is_empty() { ",M3.2.0,M11.1.0" } else { rest };
+        let (start, end) = rest.strip_prefix(',')?.split_once(',')?;
+        Some(Rule { std, dst: Some((dst, Change::parse(start)?, Change::parse(end)?)) })
+    }
+
+    pub fn offset(&self, unix: i64) -> i32 {
+        let Some((dst, start, end)) = self.dst else { return self.std };
+        let year = calendar::civil_from_days((unix + self.std as i64).div_euclid(86400)).0;
+        // Each change at its local time: the start in standard time, the end in DST
+        let start = start.day.of(year) * 86400 + start.secs as i64 - self.std as i64;
+        let end = end.day.of(year) * 86400 + end.secs as i64 - dst as i64;
+        let in_dst = if start < end { start <= unix && unix < end } else { !(end <= unix && unix < start) };
+        if in_dst { dst } else { self.std }
+    }
+}
+
+impl Change {
+    // "M3.2.0" or "M3.2.0/3", 02:00 by default
+    fn parse(text: &str) -> Option<Change> {
+        let (day, time) = match text.split_once('/') {
+            Some((day, time)) => (day, Some(time)),
+            None => (text, None),
+        };
+        let secs = match time {
+            Some(mut time) => { let secs = clock(&mut time)?; if !time.is_empty() { return None; } secs }
+            None => 7200,
+        };
+        let number = |s: &str| s.parse::<i64>().ok();
+        let day = if let Some(n) = day.strip_prefix('J') {
+            Day::Julian(number(n).filter(|n| (1..=365).contains(n))?)
+        } else if let Some(mwd) = day.strip_prefix('M') {
+            let mut parts = mwd.split('.').map(number);
+            let (m, w, d) = (parts.next()??, parts.next()??, parts.next()??);
+            if parts.next().is_some() || !(1..=12).contains(&m) || !(1..=5).contains(&w) || !(0..=6).contains(&d) { return None; }
+            Day::Month(m, w, d)
+        } else {
+            Day::Zero(number(day).filter(|n| (0..=365).contains(n))?)
+        };
+        Some(Change { day, secs })
+    }
+}
+
+impl Day {
+    // Days since 1970-01-01
+    fn of(self, year: i64) -> i64 {
+        let jan1 = calendar::days_from_civil(year, 1, 1);
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        match self {
+            Day::Julian(n) => jan1 + n - 1 + if leap && n >= 60 { 1 } else { 0 },
+            Day::Zero(n) => jan1 + n,
+            Day::Month(m, w, d) => {
+                let first = calendar::days_from_civil(year, m, 1);
+                let next_month = if m == 12 { calendar::days_from_civil(year + 1, 1, 1) } else { calendar::days_from_civil(year, m + 1, 1) };
+                // 1970-01-01 was a Thursday, weekday 4
+                let mut day = first + (d - (first + 4).rem_euclid(7)).rem_euclid(7) + (w - 1) * 7;
+                while day >= next_month { day -= 7; }
+                day
+            }
+        }
+    }
+}
+
+// A zone abbreviation, "EST" or "<+0330>"
+fn name(rest: &mut &str) -> Option<()> {
+    let len = match rest.strip_prefix('<') {
+        Some(quoted) => quoted.find('>')? + 2,
+        None => rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len()),
+    };
+    if len < 3 { return None; }
+    *rest = &rest[len..];
+    Some(())
+}
+
+// "[+-]hh[:mm[:ss]]" in seconds, as written (POSIX offsets are west of UTC)
+fn clock(rest: &mut &str) -> Option<i32> {
+    let sign = if let Some(r) = rest.strip_prefix('-') { *rest = r; -1 } else { *rest = rest.strip_prefix('+').unwrap_or(rest); 1 };
+    let len = rest.find(|c: char| !c.is_ascii_digit() && c != ':').unwrap_or(rest.len());
+    let mut parts = rest[..len].split(':').map(|p| p.parse::<i32>().ok());
+    let (h, m, s) = (parts.next()??, parts.next().unwrap_or(Some(0))?, parts.next().unwrap_or(Some(0))?);
+    if parts.next().is_some() || h > 167 || m > 59 || s > 59 { return None; }
+    *rest = &rest[len..];
+    Some(sign * (h * 3600 + m * 60 + s))
+}
+
+// ============ Unit Tests =============
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2025-03-09 07:00 UTC, when New York's clocks went forward
+    const US_SPRING: i64 = 1_741_503_600;
+
+    #[test]
+    fn rule_changes_at_local_times() {
+        let ny = Rule::parse("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        assert_eq!(ny.offset(US_SPRING - 1), -5 * 3600);
+        assert_eq!(ny.offset(US_SPRING), -4 * 3600);
+        // Test: Back an hour on 2025-11-02 at 06:00 UTC
+        assert_eq!(ny.offset(1_762_063_200 - 1), -4 * 3600);
+        assert_eq!(ny.offset(1_762_063_200), -5 * 3600);
+        // Test: South of the equator DST spans the new year
+        let sydney = Rule::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+        assert_eq!(sydney.offset(1_735_689_600), 11 * 3600); // 2025-01-01
+        assert_eq!(sydney.offset(1_751_328_000), 10 * 3600); // 2025-07-01
+        // Test: Quoted names and a fixed offset
+        assert_eq!(Rule::parse("<+0530>-5:30").unwrap().offset(0), 5 * 3600 + 1800);
+        assert_eq!(Rule::parse("UTC0").unwrap().offset(US_SPRING), 0);
+        assert_eq!(Rule::parse("EST5EDT").unwrap().offset(US_SPRING), -4 * 3600);
+        assert!(Rule::parse("EST").is_none());
+        assert!(Rule::parse("EST5EDT,M13.1.0,M11.1.0").is_none());
+    }
+
+    #[test]
+    fn month_rules_find_the_weekday() {
+        // Test: The second Sunday of March 2025 is the 9th, the last of October the 26th
+        assert_eq!(Day::Month(3, 2, 0).of(2025), calendar::days_from_civil(2025, 3, 9));
+        assert_eq!(Day::Month(10, 5, 0).of(2025), calendar::days_from_civil(2025, 10, 26));
+        assert_eq!(Day::Julian(60).of(2024), calendar::days_from_civil(2024, 3, 1));
+        assert_eq!(Day::Zero(59).of(2024), calendar::days_from_civil(2024, 2, 29));
+    }
+
+    // A version 2 file: one transition, standard and DST types, then the rule
+    fn tzif(rule: &str) -> Vec<u8> {
+        let header = |times: u32| {
+            let mut h = b"TZif2".to_vec();
+            h.extend([0; 15]);
+            for count in [0, 0, 0, times, 2, 8] { h.extend(u32::to_be_bytes(count)); }
+            h
+        };
+        let types = |out: &mut Vec<u8>| {
+            out.extend((-5 * 3600i32).to_be_bytes()); out.extend([0, 0]);
+            out.extend((-4 * 3600i32).to_be_bytes()); out.extend([1, 4]);
+            out.extend(b"EST\0EDT\0");
+        };
+        let mut out = header(1);
+        out.extend((US_SPRING as i32).to_be_bytes());
+        out.push(1);
+        types(&mut out);
+        out.extend(header(1));
+        out.extend(US_SPRING.to_be_bytes());
+        out.push(1);
+        types(&mut out);
+        out.extend(format!("\n{}\n", rule).bytes());
+        out
+    }
+
+    #[test]
+    fn parse_reads_transitions_then_the_rule() {
+        let zone = Zone::parse("America/New_York", &tzif("EST5EDT,M3.2.0,M11.1.0")).unwrap();
+        assert_eq!(zone.offset(US_SPRING - 1), -5 * 3600);
+        assert_eq!(zone.offset(US_SPRING), -4 * 3600);
+        // Test: Past the last transition the rule goes on changing the clocks
+        assert_eq!(zone.offset(US_SPRING + 365 * 86400), -4 * 3600);
+        assert_eq!(zone.offset(US_SPRING + 300 * 86400), -5 * 3600);
+        assert_eq!(zone.clock(US_SPRING), "New York 03:00");
+        assert!(Zone::parse("x", b"TZif2").is_err());
+        assert!(Zone::parse("x", b"not a zone").is_err());
+        // Test: A corrupt version byte is an error, not an overflow
+        let mut corrupt = tzif("EST5EDT");
+        corrupt[4] = b'\x01';
+        assert!(Zone::parse("x", &corrupt).is_err());
+    }
+
+    #[test]
+    fn footer_leaves_off_what_doesnt_fit() {
+        let zone = |name: &str| Zone { name: name.to_string(), transitions: vec![], initial: 0, rule: None };
+        let zones = [zone("Europe/London"), zone("Etc/UTC")];
+        assert_eq!(footer(&zones, 3600, 80), "London 01:00  UTC 01:00");
+        assert_eq!(footer(&zones, 3600, 20), "London 01:00");
+        assert_eq!(Zone::load("../etc/passwd"), Err("'../etc/passwd' isn't a time zone name like Europe/London".to_string()));
+    }
+}
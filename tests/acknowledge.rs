@@ -0,0 +1,92 @@
+// tests/acknowledge.rs
+mod common;
+use std::io::Write;
+use std::process::Stdio;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn ack_required_waits_for_ack_command() {
+    // E2E: A finished --ack-required timer keeps ringing until `timeterm ack <name>`
+    let runtime = std::env::temp_dir().join(format!("timeterm-ack-{}", std::process::id()));
+    let mut child = common::spawn_command()
+        .args(["1", "--ack-required", "--name", "laundry"])
+        .env("XDG_RUNTIME_DIR", &runtime)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to start timeterm");
+    thread::sleep(Duration::from_millis(2000));
+    assert!(child.try_wait().unwrap().is_none(), "Timer should wait for an ack");
+
+    let mut ack = common::timeterm();
+    ack.args(["ack", "laundry"]).env("XDG_RUNTIME_DIR", &runtime).assert().success();
+    thread::sleep(Duration::from_millis(500));
+    let status = child.try_wait().unwrap().expect("Timer should exit once acknowledged");
+    assert!(status.success());
+
+    let out = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.contains("timerterm ack laundry"));
+    assert!(stdout.contains('\x07'), "Finished timer should ring");
+    let _ = std::fs::remove_dir_all(&runtime);
+}
+
+#[test]
+fn key_press_acknowledges() {
+    // E2E: q on the finished screen acknowledges too
+    let runtime = std::env::temp_dir().join(format!("timeterm-ack-key-{}", std::process::id()));
+    let mut child = common::spawn_command()
+        .args(["1", "--ack-required"])
+        .env("XDG_RUNTIME_DIR", &runtime)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("Failed to start timeterm");
+    thread::sleep(Duration::from_millis(1500));
+    assert!(child.try_wait().unwrap().is_none(), "Timer should wait for an ack");
+    child.stdin.as_mut().unwrap().write_all(b"q").unwrap();
+    thread::sleep(Duration::from_millis(500));
+    let status = child.try_wait().unwrap().expect("q should acknowledge");
+    assert!(status.success());
+    let _ = std::fs::remove_dir_all(&runtime);
+}
+
+#[test]
+#[cfg(all(unix, not(target_os = "macos")))]
+fn unacknowledged_timer_escalates_once() {
+    // E2E: A minute held past the finish, the alarm plays at full volume, a
+    // second notification goes out and escalate.exec runs, all just the once.
+    // Dismissed straight after, the exec is waited for rather than orphaned.
+    use std::os::unix::fs::PermissionsExt;
+    let dir = std::env::temp_dir().join(format!("timeterm-escalate-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("timerterm")).unwrap();
+    let (sound, calls, escalated) = (dir.join("ding.wav"), dir.join("calls"), dir.join("escalated"));
+    std::fs::write(&sound, b"RIFF").unwrap();
+    for (tool, arg) in [("paplay", "$1"), ("notify-send", "$*")] {
+        std::fs::write(dir.join(tool), format!("#!/bin/sh\necho \"{}\" >> {}\n", arg, calls.display())).unwrap();
+        std::fs::set_permissions(dir.join(tool), std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    let config = format!("[escalate]\nafter = \"1:00\"\nexec = \"sleep 0.5; echo $TIMERTERM_NAME >> {}\"\n", escalated.display());
+    std::fs::write(dir.join("timerterm").join("config.toml"), config).unwrap();
+
+    let path = format!("{}:{}", dir.display(), std::env::var("PATH").unwrap_or_default());
+    let out = common::timeterm().env("XDG_CONFIG_HOME", &dir).env("XDG_RUNTIME_DIR", &dir).env("PATH", path)
+        .args(["--internal-test-harness", "1", "--plain", "--no-log", "--ack-required", "--name", "laundry"])
+        .args(["--sound", sound.to_str().unwrap(), "--volume", "50"])
+        .write_stdin("advance 1\nadvance 30\nadvance 30\nadvance 30\nkeys q\n")
+        .timeout(Duration::from_secs(5)).output().unwrap();
+    assert!(out.status.success());
+    thread::sleep(Duration::from_millis(300));
+    let calls = std::fs::read_to_string(&calls).unwrap();
+    let escalated = std::fs::read_to_string(&escalated).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    // Test: The finish and a nag at 50%, escalated at 100%, then back to 50%.
+    // The players run in the background, so in no set order.
+    let lines: Vec<&str> = calls.lines().collect();
+    let mut played: Vec<&str> = lines.iter().filter(|line| line.starts_with("--volume=")).copied().collect();
+    played.sort();
+    assert_eq!(played, ["--volume=32768", "--volume=32768", "--volume=32768", "--volume=65536"]);
+    assert_eq!(lines.iter().filter(|line| line.contains("Still waiting")).count(), 1, "{}", calls);
+    assert_eq!(escalated, "laundry\n");
+}
@@ -0,0 +1,40 @@
+// tests/adjust.rs
+mod common;
+use std::path::Path;
+use std::time::Duration;
+
+// Bells rung by a 2:00 countdown driven by `script`
+fn bells(script: &str, config_home: Option<&Path>) -> usize {
+    let mut cmd = common::timeterm();
+    cmd.args(["--internal-test-harness", "--no-log", "--no-notify", "2:00"]);
+    // An empty config dir keeps the user's out of it
+    cmd.env("XDG_CONFIG_HOME", config_home.unwrap_or(Path::new("/nonexistent")));
+    let out = cmd.write_stdin(script).timeout(Duration::from_secs(5)).output().unwrap();
+    assert!(out.status.success());
+    out.stdout.iter().filter(|&&b| b == b'\x07').count()
+}
+
+#[test]
+fn plus_adds_a_minute() {
+    // E2E: 2:00 plus one is still running at 2:59 and done at 3:00
+    assert_eq!(bells("keys +\nadvance 2:59\n", None), 0);
+    assert_eq!(bells("keys +\nadvance 2:59\nadvance 1\n", None), 1);
+}
+
+#[test]
+fn minus_can_end_the_timer() {
+    // E2E: Taking off more than is left finishes it there and then.
+    // No time passes, the advance is only there to get the next frame out.
+    assert_eq!(bells("advance 30\nkeys --\nadvance 0\n", None), 1);
+}
+
+#[test]
+fn step_comes_from_config() {
+    // E2E: With a 30s step, two presses off leave 1:00
+    let home = std::env::temp_dir().join(format!("timerterm-adjust-{}", std::process::id()));
+    std::fs::create_dir_all(home.join("timerterm")).unwrap();
+    std::fs::write(home.join("timerterm/config.toml"), "[timer]\nadjust_step = \"30s\"\n").unwrap();
+    let rung = (bells("keys --\nadvance 59\n", Some(&home)), bells("keys --\nadvance 1:00\n", Some(&home)));
+    std::fs::remove_dir_all(&home).unwrap();
+    assert_eq!(rung, (0, 1));
+}
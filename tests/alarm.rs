@@ -0,0 +1,161 @@
+// tests/alarm.rs
+mod common;
+use std::time::Duration;
+
+fn run(args: &[&str]) -> std::process::Output {
+    let mut cmd = common::timeterm();
+    cmd.args(args).args(["--no-notify"]).timeout(Duration::from_secs(5)).output().unwrap()
+}
+
+#[test]
+fn bell_rings_at_completion() {
+    // E2E: With no sound file the terminal bell is the alarm, --silent drops it
+    let out = run(&["1"]);
+    assert!(out.status.success());
+    assert_eq!(out.stdout.iter().filter(|&&b| b == 0x07).count(), 1);
+    let out = run(&["1", "--silent"]);
+    assert!(!out.stdout.contains(&0x07));
+}
+
+#[test]
+fn missing_sound_file_is_reported() {
+    // E2E: A bad --sound path fails up front rather than going quiet at the end
+    let out = run(&["1", "--sound", "/nonexistent/ding.wav"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("/nonexistent/ding.wav"));
+}
+
+#[test]
+#[cfg(all(unix, not(target_os = "macos")))]
+fn sound_file_plays_through_player() {
+    // E2E: A fake paplay on PATH gets the file and scaled volume instead of the bell
+    use std::os::unix::fs::PermissionsExt;
+    let dir = std::env::temp_dir().join(format!("timeterm-alarm-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (sound, calls, player) = (dir.join("ding.wav"), dir.join("calls"), dir.join("paplay"));
+    std::fs::write(&sound, b"RIFF").unwrap();
+    std::fs::write(&player, format!("#!/bin/sh\necho \"$@\" >> {}\n", calls.display())).unwrap();
+    std::fs::set_permissions(&player, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let path = format!("{}:{}", dir.display(), std::env::var("PATH").unwrap_or_default());
+    let mut cmd = common::timeterm();
+    let out = cmd.env("PATH", path)
+        .args(["1", "--no-notify", "--sound", sound.to_str().unwrap(), "--volume", "50%"])
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    assert!(!out.stdout.contains(&0x07));
+    // The player runs in the background, give it a moment
+    std::thread::sleep(Duration::from_millis(300));
+    let calls = std::fs::read_to_string(&calls).unwrap();
+    assert_eq!(calls, format!("--volume=32768 {}\n", sound.display()));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn morse_alarm_spells_out_on_the_bell() {
+    // E2E: morse.alarm "ET" is a beep for the dot and two for the dash
+    let home = std::env::temp_dir().join(format!("timeterm-morse-{}", std::process::id()));
+    std::fs::create_dir_all(home.join("timerterm")).unwrap();
+    std::fs::write(home.join("timerterm").join("config.toml"), "[morse]\nalarm = \"ET\"\nunit_ms = 10\n").unwrap();
+    let out = common::timeterm().env("XDG_CONFIG_HOME", &home)
+        .args(["1", "--no-notify"]).timeout(Duration::from_secs(5)).output().unwrap();
+    let _ = std::fs::remove_dir_all(&home);
+    assert!(out.status.success());
+    assert_eq!(out.stdout.iter().filter(|&&b| b == 0x07).count(), 3);
+}
+
+#[test]
+#[cfg(unix)]
+fn pause_media_pauses_only_playing_players_until_dismissed() {
+    // E2E: A fake playerctl with one player playing and one paused
+    use std::os::unix::fs::PermissionsExt;
+    let dir = std::env::temp_dir().join(format!("timeterm-media-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("bin")).unwrap();
+    std::fs::create_dir_all(dir.join("timerterm")).unwrap();
+    std::fs::write(dir.join("timerterm").join("config.toml"), "[sound]\npause_media = true\n").unwrap();
+    let (calls, player) = (dir.join("calls"), dir.join("bin").join("playerctl"));
+    let script = format!(
+        "#!/bin/sh\ncase \"$*\" in\n  -l) printf 'spotify\\nvlc\\n' ;;\n  '-p spotify status') echo Playing ;;\n  '-p vlc status') echo Paused ;;\n  *) echo \"$@\" >> {} ;;\nesac\n",
+        calls.display()
+    );
+    std::fs::write(&player, script).unwrap();
+    std::fs::set_permissions(&player, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let path = format!("{}:{}", dir.join("bin").display(), std::env::var("PATH").unwrap_or_default());
+    let out = common::timeterm().env("XDG_CONFIG_HOME", &dir).env("PATH", path)
+        .args(["--internal-test-harness", "1", "--no-notify", "--ack-required"])
+        .write_stdin("advance 1\nkeys q\n")
+        .timeout(Duration::from_secs(5)).output().unwrap();
+    let calls = std::fs::read_to_string(&calls).unwrap_or_default();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(out.status.success());
+    // Test: vlc was already paused, so it's left alone
+    assert_eq!(calls, "-p spotify pause\n-p spotify play\n");
+}
+
+#[test]
+#[cfg(all(unix, not(target_os = "macos")))]
+fn tone_alarm_plays_a_generated_wav() {
+    // E2E: No sound file, so [tone] alarm is synthesized and handed to the player
+    use std::os::unix::fs::PermissionsExt;
+    let dir = std::env::temp_dir().join(format!("timeterm-tone-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("timerterm")).unwrap();
+    std::fs::write(dir.join("timerterm").join("config.toml"), "[tone]\nalarm = \"880/50 0/20 660+880/50\"\n").unwrap();
+    let (calls, player) = (dir.join("calls"), dir.join("paplay"));
+    std::fs::write(&player, format!("#!/bin/sh\necho \"$2\" >> {}\n", calls.display())).unwrap();
+    std::fs::set_permissions(&player, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let path = format!("{}:{}", dir.display(), std::env::var("PATH").unwrap_or_default());
+    let out = common::timeterm().env("XDG_CONFIG_HOME", &dir).env("PATH", path)
+        .args(["--internal-test-harness", "1", "--no-notify"])
+        .write_stdin("advance 1\n")
+        .timeout(Duration::from_secs(5)).output().unwrap();
+    assert!(out.status.success());
+    assert!(!out.stdout.contains(&0x07));
+    std::thread::sleep(Duration::from_millis(300));
+    let played = std::fs::read_to_string(&calls).unwrap();
+    let wav = std::fs::read(played.trim()).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(&wav[..4], b"RIFF");
+    // 120ms of 16-bit samples at 22050 a second
+    assert_eq!(wav.len(), 44 + (1102 + 441 + 1102) * 2);
+}
+
+#[test]
+fn m_mutes_the_bell_with_a_note() {
+    // E2E: Muted, the finish rings nothing and the screen says so
+    let out = common::timeterm()
+        .args(["--internal-test-harness", "5", "--plain", "--no-notify", "--no-log"])
+        .write_stdin("keys m\nadvance 5\n")
+        .timeout(Duration::from_secs(5)).output().unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.contains("\n  Muted, m to unmute\n"), "{}", stdout);
+    assert!(!stdout.contains('\x07'));
+}
+
+#[test]
+#[cfg(all(unix, not(target_os = "macos")))]
+fn brackets_step_the_volume_the_player_gets() {
+    // E2E: Two ] from 50% plays at 70%, and m twice is unmuted again
+    use std::os::unix::fs::PermissionsExt;
+    let dir = std::env::temp_dir().join(format!("timeterm-volume-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (sound, calls, player) = (dir.join("ding.wav"), dir.join("calls"), dir.join("paplay"));
+    std::fs::write(&sound, b"RIFF").unwrap();
+    std::fs::write(&player, format!("#!/bin/sh\necho \"$1\" >> {}\n", calls.display())).unwrap();
+    std::fs::set_permissions(&player, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let path = format!("{}:{}", dir.display(), std::env::var("PATH").unwrap_or_default());
+    let out = common::timeterm().env("PATH", path)
+        .args(["--internal-test-harness", "5", "--plain", "--no-notify", "--no-log", "--sound", sound.to_str().unwrap(), "--volume", "50"])
+        .write_stdin("keys ]]\nadvance 1\nkeys mm\nadvance 4\n")
+        .timeout(Duration::from_secs(5)).output().unwrap();
+    assert!(out.status.success());
+    assert!(String::from_utf8_lossy(&out.stdout).contains("\n  Volume 70%\n"));
+    std::thread::sleep(Duration::from_millis(300));
+    let calls = std::fs::read_to_string(&calls).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(calls, format!("--volume={}\n", 65536 * 70 / 100));
+}
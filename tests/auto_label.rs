@@ -0,0 +1,61 @@
+// tests/auto_label.rs
+mod common;
+use std::path::Path;
+use std::time::Duration;
+
+// START line of a 1s run in `dir`, with `label_args` on the command line
+fn start_line(home: &Path, dir: &Path, label_args: &[&str]) -> String {
+    let cache = home.join("cache");
+    let mut cmd = common::timeterm();
+    cmd.args(["--internal-test-harness", "--no-notify", "1"]).args(label_args)
+        .current_dir(dir)
+        .env("XDG_CONFIG_HOME", home)
+        .env("XDG_CACHE_HOME", &cache)
+        .write_stdin("advance 1\n")
+        .timeout(Duration::from_secs(5))
+        .assert().success();
+    let log = std::fs::read_to_string(cache.join("timerterm/timerterm.log")).unwrap();
+    std::fs::remove_dir_all(&cache).unwrap();
+    log.lines().next().unwrap().to_string()
+}
+
+#[test]
+fn auto_label_names_the_run_after_its_directory() {
+    // E2E: Outside tmux, auto_label = "tmux" falls back to the directory name
+    let home = std::env::temp_dir().join(format!("timerterm-auto-label-{}", std::process::id()));
+    let dir = home.join("thesis");
+    std::fs::create_dir_all(home.join("timerterm")).unwrap();
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(home.join("timerterm/config.toml"), "[timer]\nauto_label = \"tmux\"\n").unwrap();
+    let logged_dir = format!(" dir={:?}", dir.canonicalize().unwrap().to_string_lossy());
+    assert!(start_line(&home, &dir, &[]).ends_with(&format!(": START duration=1s label=\"thesis\"{}", logged_dir)));
+    // Test: --label still wins
+    assert!(start_line(&home, &dir, &["--label", "Pasta"]).ends_with(&format!(": START duration=1s label=\"Pasta\"{}", logged_dir)));
+    // Test: Off by default
+    std::fs::write(home.join("timerterm/config.toml"), "").unwrap();
+    assert!(start_line(&home, &dir, &[]).ends_with(&format!(": START duration=1s{}", logged_dir)));
+    std::fs::remove_dir_all(&home).unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn runs_in_tmux_are_tagged_with_session_and_window() {
+    // E2E: A fake tmux answers for the session and window, auto_label off
+    use std::os::unix::fs::PermissionsExt;
+    let home = std::env::temp_dir().join(format!("timerterm-tmux-tag-{}", std::process::id()));
+    let cache = home.join("cache");
+    std::fs::create_dir_all(home.join("bin")).unwrap();
+    let tmux = home.join("bin").join("tmux");
+    std::fs::write(&tmux, "#!/bin/sh\n[ \"$3\" = '#S:#W' ] && echo thesis:vim\n").unwrap();
+    std::fs::set_permissions(&tmux, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let path = format!("{}:{}", home.join("bin").display(), std::env::var("PATH").unwrap_or_default());
+    common::timeterm().args(["--internal-test-harness", "--no-notify", "1", "--label", "Pasta"])
+        .env("XDG_CONFIG_HOME", &home).env("XDG_CACHE_HOME", &cache)
+        .env("TMUX", "/tmp/tmux-1000/default,1,0").env("PATH", path)
+        .write_stdin("advance 1\n")
+        .timeout(Duration::from_secs(5))
+        .assert().success();
+    let log = std::fs::read_to_string(cache.join("timerterm/timerterm.log")).unwrap();
+    std::fs::remove_dir_all(&home).unwrap();
+    assert!(log.lines().next().unwrap().ends_with(&format!(": START duration=1s label=\"Pasta\" tag=\"thesis:vim\"{}", common::logged_dir())), "{}", log);
+}
@@ -0,0 +1,125 @@
+// tests/calendar_busy.rs
+mod common;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// "20250601T090000", the civil time of UNIX seconds `t` (inverse of days_from_civil)
+fn ics_time(t: u64) -> String {
+    let (days, rem) = (t / 86400, t % 86400);
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    format!("{:04}{:02}{:02}T{:02}{:02}{:02}", y, m, d, rem / 3600, rem % 3600 / 60, rem % 60)
+}
+
+// Write an .ics with one meeting starting `in_secs` from now, returns its path
+fn calendar_with_meeting(name: &str, in_secs: u64) -> std::path::PathBuf {
+    let start = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + in_secs;
+    let ics = format!(
+        "BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:Standup\nDTSTART:{}Z\nDTEND:{}Z\nEND:VEVENT\nEND:VCALENDAR\n",
+        ics_time(start), ics_time(start + 900),
+    );
+    let path = std::env::temp_dir().join(format!("timeterm-{}-{}.ics", name, std::process::id()));
+    std::fs::write(&path, ics).unwrap();
+    path
+}
+
+#[test]
+fn warns_and_respects_no() {
+    // E2E: A meeting in ~12m overlaps a 25m timer, answering "n" skips the timer
+    let path = calendar_with_meeting("no", 12 * 60);
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["25:00", "--calendar", path.to_str().unwrap()])
+        .write_stdin("n\n")
+        .timeout(std::time::Duration::from_secs(5))
+        .output().unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(out.status.success());
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert!(stderr.contains("Meeting 'Standup' in 12m"), "Unexpected prompt: {}", stderr);
+    assert!(out.stdout.is_empty(), "Timer display shouldn't start");
+}
+
+#[test]
+fn force_skips_the_prompt() {
+    // E2E: --force starts the timer without asking (q quits it right away)
+    let path = calendar_with_meeting("force", 12 * 60);
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["25:00", "--calendar", path.to_str().unwrap(), "--force"])
+        .write_stdin("q")
+        .timeout(std::time::Duration::from_secs(5))
+        .output().unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(out.status.success());
+    assert!(out.stderr.is_empty());
+    assert!(!out.stdout.is_empty());
+}
+
+#[test]
+fn no_conflict_no_prompt() {
+    // E2E: A meeting after the timer ends doesn't trigger a prompt
+    let path = calendar_with_meeting("later", 3600);
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["1", "--calendar", path.to_str().unwrap()])
+        .timeout(std::time::Duration::from_secs(5))
+        .output().unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(out.status.success());
+    assert!(out.stderr.is_empty());
+}
+
+#[test]
+fn stopwatch_never_clashes() {
+    // E2E: A stopwatch has no end, so even with a head start it doesn't ask
+    let path = calendar_with_meeting("stopwatch", 12 * 60);
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["stopwatch", "--elapsed", "20m", "--calendar", path.to_str().unwrap()])
+        .write_stdin("q")
+        .timeout(std::time::Duration::from_secs(5))
+        .output().unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(out.status.success());
+    assert!(out.stderr.is_empty(), "got {:?}", String::from_utf8_lossy(&out.stderr));
+}
+
+#[test]
+fn tzid_times_are_read_in_their_zone() {
+    // E2E: A meeting in ~12m written as Kolkata time (UTC+5:30) still clashes,
+    // read as local time (UTC here) it would be hours away
+    let dir = std::env::temp_dir().join(format!("timeterm-calendar-tzid-{}", std::process::id()));
+    let tzdir = dir.join("zoneinfo");
+    std::fs::create_dir_all(tzdir.join("Asia")).unwrap();
+    let mut tzif = Vec::new();
+    for _ in 0..2 {
+        tzif.extend(b"TZif2");
+        tzif.extend([0; 15]);
+        for count in [0u32, 0, 0, 0, 1, 4] { tzif.extend(count.to_be_bytes()); }
+        tzif.extend(19800i32.to_be_bytes());
+        tzif.extend([0, 0]);
+        tzif.extend(b"IST\0");
+    }
+    tzif.extend(b"\nIST-5:30\n");
+    std::fs::write(tzdir.join("Asia").join("Kolkata"), tzif).unwrap();
+    let start = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + 12 * 60 + 19800;
+    let ics = format!(
+        "BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:Standup\nDTSTART;TZID=Asia/Kolkata:{}\nDTEND;TZID=\"Asia/Kolkata\":{}\nEND:VEVENT\nEND:VCALENDAR\n",
+        ics_time(start), ics_time(start + 900),
+    );
+    let path = dir.join("meeting.ics");
+    std::fs::write(&path, ics).unwrap();
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["25:00", "--calendar", path.to_str().unwrap()])
+        .env("TZ", "UTC").env("TZDIR", &tzdir)
+        .write_stdin("n\n")
+        .timeout(std::time::Duration::from_secs(5))
+        .output().unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(out.status.success());
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert!(stderr.contains("Meeting 'Standup' in 12m"), "Unexpected prompt: {}", stderr);
+}
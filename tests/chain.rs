@@ -0,0 +1,52 @@
+// tests/chain.rs
+mod common;
+use std::time::Duration;
+
+#[test]
+fn chained_durations_run_back_to_back() {
+    // E2E: 5s then 3s then 2s, each logged as its own run, one bell at the end
+    let cache = std::env::temp_dir().join(format!("timerterm-chain-{}", std::process::id()));
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["--internal-test-harness", "--no-notify", "5", "3", "2"])
+        .env("XDG_CACHE_HOME", &cache)
+        .write_stdin("advance 5\nadvance 1\nadvance 2\nadvance 2\n")
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    let log = std::fs::read_to_string(cache.join("timerterm/timerterm.log")).unwrap();
+    std::fs::remove_dir_all(&cache).unwrap();
+    let starts: Vec<&str> = log.lines().filter_map(|l| l.split_once(": START ").map(|(_, rest)| rest)).collect();
+    let dir = common::logged_dir();
+    assert_eq!(starts, [format!("duration=5s{}", dir), format!("duration=3s{}", dir), format!("duration=2s{}", dir)]);
+    assert_eq!(log.matches(": COMPLETED").count(), 3);
+    // Test: The active segment and the overall progress are shown
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let at = |text: &str| stdout.find(text).unwrap_or_else(|| panic!("no {}", text));
+    assert!(at("Segment 1 of 3") < at("Segment 2 of 3") && at("Segment 2 of 3") < at("Segment 3 of 3"));
+    assert!(stdout.contains("0:06 of 0:10 overall"));
+}
+
+#[test]
+fn on_cancel_then_follows_only_a_quit() {
+    // E2E: Quitting the first segment skips the rest for the --on-cancel-then timer
+    let run = |script: &str| {
+        let cache = std::env::temp_dir().join(format!("timerterm-cancel-then-{}", std::process::id()));
+        let out = common::timeterm().args(["--internal-test-harness", "--no-notify", "--silent", "--plain", "5", "--then", "3", "--on-cancel-then", "2"])
+            .env("XDG_CACHE_HOME", &cache)
+            .write_stdin(script)
+            .timeout(Duration::from_secs(5))
+            .output().unwrap();
+        assert!(out.status.success());
+        let log = std::fs::read_to_string(cache.join("timerterm/timerterm.log")).unwrap();
+        std::fs::remove_dir_all(&cache).unwrap();
+        let dir = common::logged_dir();
+        let events: Vec<String> = log.lines().filter_map(|l| l.split_once(": ").map(|(_, rest)| rest.replace(&dir, ""))).collect();
+        (String::from_utf8(out.stdout).unwrap(), events)
+    };
+    let (stdout, events) = run("advance 1\nkeys q\nadvance 2\n");
+    assert_eq!(events, ["START duration=5s", "CANCELLED elapsed=1s", "START duration=2s", "COMPLETED elapsed=2s"]);
+    assert!(stdout.ends_with("0:00  Follow-up\n"), "{}", stdout);
+    // Test: A chain that runs to the end has nothing to follow up
+    let (_, events) = run("advance 5\nadvance 3\n");
+    assert_eq!(events, ["START duration=5s", "COMPLETED elapsed=5s", "START duration=3s", "COMPLETED elapsed=3s"]);
+}
@@ -1,10 +1,10 @@
 // tests/cli_args.rs
-use assert_cmd::Command;
+mod common;
 
 #[test]
 fn runs_1sec_simple_args() {
     // Test: Simple check that 1sec is waited
-    let mut cmd = Command::cargo_bin("timeterm").unwrap();
+    let mut cmd = common::timeterm();
     let out = cmd.arg("1").timeout(std::time::Duration::from_secs(2));
     out.assert().success();
 }
@@ -13,7 +13,7 @@ fn runs_1sec_simple_args() {
 fn runs_with_mins_secs_format() {
     // E2E: Program should accept mm:ss format
     // Won't test full duration, just that for more than 1s it runs
-    let mut cmd = Command::cargo_bin("timeterm").unwrap();
+    let mut cmd = common::timeterm();
     let out = cmd.arg("0:01").timeout(std::time::Duration::from_secs(2));
     out.assert().success();
 }
@@ -21,7 +21,68 @@ fn runs_with_mins_secs_format() {
 #[test]
 fn runs_with_hrs_mins_secs_format() {
     // E2E: Program should accept "0:00:02" (2 seconds) and run for that duration
-    let mut cmd = Command::cargo_bin("timeterm").unwrap();
+    let mut cmd = common::timeterm();
     let out = cmd.arg("0:00:02").timeout(std::time::Duration::from_secs(4));
     out.assert().success(); // Should run for ~2 seconds then exit
 }
+
+#[test]
+fn runs_with_unit_format() {
+    // E2E: Program should accept "1s" style units
+    let mut cmd = common::timeterm();
+    let out = cmd.arg("1s").timeout(std::time::Duration::from_secs(2));
+    out.assert().success();
+}
+
+#[test]
+fn schema_prints_json_schema() {
+    // E2E: "schema summary" prints one JSON Schema document and exits 0
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["schema", "summary"]).output().unwrap();
+    assert!(out.status.success());
+    let text = String::from_utf8(out.stdout).unwrap();
+    assert!(text.starts_with(r#"{"$schema":"#));
+    assert_eq!(text.lines().count(), 1);
+}
+
+#[test]
+fn schema_rejects_unknown_name() {
+    // E2E: Unknown schema names fail with a helpful message
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["schema", "bogus"]).output().unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8(out.stderr).unwrap().contains("summary"));
+}
+
+#[test]
+fn help_and_version_exit_cleanly() {
+    // E2E: --help prints usage and --version the crate version, both without starting a timer
+    let out = common::timeterm().arg("--help").output().unwrap();
+    assert!(out.status.success());
+    assert!(String::from_utf8(out.stdout).unwrap().contains("timerterm pomodoro [OPTIONS]"));
+    let out = common::timeterm().arg("--version").output().unwrap();
+    assert_eq!(String::from_utf8(out.stdout).unwrap(), format!("timerterm {}\n", env!("CARGO_PKG_VERSION")));
+}
+
+#[test]
+fn bad_args_fail_instead_of_defaulting() {
+    // E2E: A bad flag exits 2 with the reason, rather than running a 10 minute timer
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["--volume", "loud"]).timeout(std::time::Duration::from_secs(2)).output().unwrap();
+    assert_eq!(out.status.code(), Some(2));
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert!(stderr.starts_with("timerterm: --volume must be a percentage from 0 to 100, not 'loud'\n"));
+    assert!(out.stdout.is_empty()); // Never entered the alt screen
+}
+
+#[test]
+fn presets_lists_config_presets() {
+    // E2E: "presets" prints each configured preset with its duration
+    let dir = std::env::temp_dir().join(format!("timerterm-presets-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("timerterm")).unwrap();
+    std::fs::write(dir.join("timerterm/config.toml"), "[presets]\ntea = \"3m\"\nlaundry = 2700\n").unwrap();
+    let out = common::timeterm().arg("presets").env("XDG_CONFIG_HOME", &dir).output().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(out.status.success());
+    assert_eq!(String::from_utf8(out.stdout).unwrap(), "tea      3:00\nlaundry  45:00\n");
+}
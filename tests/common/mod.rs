@@ -0,0 +1,40 @@
+// tests/common/mod.rs
+// Shared by the E2E tests through `mod common;`. Every run gets a scratch
+// config and cache dir, so tests never log to the real history or pick up
+// the developer's config.toml, and none is tagged with the developer's tmux
+// session. They run in the scratch dir too, not this checkout, so the
+// project each start logs is one the tests know. Tests that set their own
+// still win.
+#![allow(dead_code)] // Not every test binary uses both
+use std::path::PathBuf;
+
+// One per test binary, under the temp dir
+pub fn scratch() -> PathBuf {
+    std::env::temp_dir().join(format!("timerterm-test-{}", std::process::id()))
+}
+
+// The scratch dir, made if it isn't there yet
+fn scratch_dir() -> PathBuf {
+    std::fs::create_dir_all(scratch()).unwrap();
+    scratch()
+}
+
+// The ` dir="..."` a start logs when run in the scratch dir
+pub fn logged_dir() -> String {
+    format!(" dir={:?}", scratch_dir().canonicalize().unwrap().to_string_lossy())
+}
+
+pub fn timeterm() -> assert_cmd::Command {
+    let mut cmd = assert_cmd::Command::cargo_bin("timeterm").unwrap();
+    cmd.env("XDG_CONFIG_HOME", scratch().join("config")).env("XDG_CACHE_HOME", scratch().join("cache")).env_remove("TMUX");
+    cmd.current_dir(scratch_dir());
+    cmd
+}
+
+// For tests that spawn, signal or wait on the process themselves
+pub fn spawn_command() -> std::process::Command {
+    let mut cmd = std::process::Command::new(assert_cmd::cargo::cargo_bin("timeterm"));
+    cmd.env("XDG_CONFIG_HOME", scratch().join("config")).env("XDG_CACHE_HOME", scratch().join("cache")).env_remove("TMUX");
+    cmd.current_dir(scratch_dir());
+    cmd
+}
@@ -0,0 +1,77 @@
+// tests/config_file.rs
+mod common;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+// Fresh XDG_CONFIG_HOME holding timerterm/config.toml with `text`
+fn config_home(name: &str, text: &str) -> PathBuf {
+    let home = std::env::temp_dir().join(format!("timeterm-config-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(home.join("timerterm")).unwrap();
+    std::fs::write(home.join("timerterm").join("config.toml"), text).unwrap();
+    home
+}
+
+#[test]
+fn config_sets_default_duration() {
+    // E2E: With no duration argument the 1s from the config is used, not 10m
+    let home = config_home("duration", "[timer]\nduration = \"0:01\"\n");
+    let start = Instant::now();
+    let mut cmd = common::timeterm();
+    cmd.env("XDG_CONFIG_HOME", &home).timeout(Duration::from_secs(5)).assert().success();
+    assert!(start.elapsed() < Duration::from_secs(4));
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+#[test]
+fn cli_duration_overrides_config() {
+    // E2E: An explicit 1s beats a configured hour
+    let home = config_home("override", "[timer]\nduration = \"1:00:00\"\n");
+    let mut cmd = common::timeterm();
+    cmd.env("XDG_CONFIG_HOME", &home).arg("1").timeout(Duration::from_secs(5)).assert().success();
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+#[test]
+fn bad_config_is_reported() {
+    // E2E: A broken config stops before the timer and names the file and line
+    let home = config_home("bad", "[timer]\ntick_ms = 0\n");
+    let mut cmd = common::timeterm();
+    let out = cmd.env("XDG_CONFIG_HOME", &home).arg("1").timeout(Duration::from_secs(5)).output().unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("config.toml: line 2"), "Unexpected stderr: {}", stderr);
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+#[test]
+fn preset_runs_configured_duration() {
+    // E2E: `preset tea` runs the 1s defined under [presets], unknown names fail
+    let home = config_home("preset", "[presets]\ntea = \"0:01\"\nworkout = \"45:00\"\n");
+    let mut cmd = common::timeterm();
+    cmd.env("XDG_CONFIG_HOME", &home).args(["preset", "tea"]).timeout(Duration::from_secs(5)).assert().success();
+
+    let mut cmd = common::timeterm();
+    let out = cmd.env("XDG_CONFIG_HOME", &home).args(["preset", "coffee"]).timeout(Duration::from_secs(5)).output().unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("defined presets: tea, workout"), "Unexpected stderr: {}", stderr);
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+#[test]
+fn template_preset_asks_for_what_isnt_set() {
+    // E2E: --set fills one placeholder, the other is asked for on stderr
+    let home = config_home("template", "[presets]\nreview = \"{secs}s --label 'Review {pr}' --no-notify --silent\"\n");
+    let out = common::timeterm().env("XDG_CONFIG_HOME", &home)
+        .args(["preset", "review", "--set", "pr=42", "--plain", "--no-log"])
+        .write_stdin("1\n")
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    assert_eq!(String::from_utf8(out.stderr).unwrap(), "secs: ");
+    assert!(String::from_utf8(out.stdout).unwrap().starts_with("Review 42  0:01\n"));
+    // Test: Listed as written
+    let out = common::timeterm().env("XDG_CONFIG_HOME", &home).arg("presets").output().unwrap();
+    assert_eq!(String::from_utf8(out.stdout).unwrap(), "review  {secs}s --label 'Review {pr}' --no-notify --silent\n");
+    let _ = std::fs::remove_dir_all(&home);
+}
@@ -0,0 +1,37 @@
+// tests/control_fifo.rs
+// FIFOs are Unix only
+#![cfg(unix)]
+mod common;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+#[test]
+fn fifo_commands_drive_the_timer() {
+    // E2E: Writers can come and go, each command lands in the history
+    let dir = std::env::temp_dir().join(format!("timerterm-control-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let fifo = dir.join("timer.fifo");
+    let mut child = common::spawn_command()
+        .args(["10:00", "--no-notify", "--control-fifo"]).arg(&fifo)
+        .env("XDG_CACHE_HOME", &dir)
+        .stdout(std::process::Stdio::null())
+        .spawn().unwrap();
+
+    let start = Instant::now();
+    while !fifo.exists() && start.elapsed() < Duration::from_secs(5) { std::thread::sleep(Duration::from_millis(10)); }
+    // Opening blocks until the timer is listening
+    std::fs::OpenOptions::new().write(true).open(&fifo).unwrap().write_all(b"+60\npause\nbogus\n").unwrap();
+    std::fs::OpenOptions::new().write(true).open(&fifo).unwrap().write_all(b"-30s\ncancel\n").unwrap();
+    while child.try_wait().unwrap().is_none() {
+        assert!(start.elapsed() < Duration::from_secs(5), "timer ignored cancel");
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    let log = std::fs::read_to_string(dir.join("timerterm/timerterm.log")).unwrap();
+    let kinds: Vec<&str> = log.lines().map(|l| l.split_once("]: ").unwrap().1.split(' ').next().unwrap()).collect();
+    assert_eq!(kinds, ["START", "ADJUST", "PAUSE", "ADJUST", "CANCELLED"]);
+    assert!(log.contains("duration=660s") && log.contains("duration=630s"));
+    // Test: The FIFO the timer made is gone with it
+    assert!(!fifo.exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,63 @@
+// tests/countdown_accuracy.rs
+// Long-running accuracy checks, ignored by default.
+// Run with: cargo test --test countdown_accuracy -- --ignored
+mod common;
+use std::time::Instant;
+
+const RUNS: usize = 10;
+const TOLERANCE_MS: f64 = 20.0;
+
+// Run a 1 second timer RUNS times, return expiry error of each run in ms
+fn sample_expiry_errors() -> Vec<f64> {
+    (0..RUNS).map(|_| {
+        let start = Instant::now();
+        let status = common::spawn_command()
+            .arg("1")
+            .stdout(std::process::Stdio::null())
+            .status()
+            .expect("Failed to run timeterm");
+        assert!(status.success());
+        (start.elapsed().as_secs_f64() - 1.0) * 1000.0
+    }).collect()
+}
+
+fn mean(samples: &[f64]) -> f64 {
+    samples.iter().sum::<f64>() / samples.len() as f64
+}
+
+fn std_dev(samples: &[f64]) -> f64 {
+    let m = mean(samples);
+    let var = samples.iter().map(|s| (s - m).powi(2)).sum::<f64>() / samples.len() as f64;
+    var.sqrt()
+}
+
+#[test]
+#[ignore]
+fn expiry_error_within_bounds() {
+    // Test: Timers should expire within ±20ms of the requested duration
+    let errors = sample_expiry_errors();
+    let (m, sd) = (mean(&errors), std_dev(&errors));
+    let worst = errors.iter().cloned().fold(0.0_f64, |a, e| a.max(e.abs()));
+    eprintln!("expiry error: mean={:.1}ms sd={:.1}ms worst={:.1}ms", m, sd, worst);
+
+    // Test: Timer never exits before its deadline (allow 1ms of clock slop)
+    assert!(errors.iter().all(|e| *e > -1.0), "Timer expired early: {:?}", errors);
+    // Test: Mean and ~2 std devs stay inside the tolerance band
+    assert!(m.abs() <= TOLERANCE_MS, "Mean error {:.1}ms out of bounds", m);
+    assert!(m.abs() + 2.0 * sd <= TOLERANCE_MS, "Error spread too wide: sd={:.1}ms", sd);
+}
+
+#[test]
+#[ignore]
+fn expiry_error_does_not_drift_with_duration() {
+    // Test: A 3s timer should be no less accurate than a 1s one (no accumulated drift)
+    let start = Instant::now();
+    let status = common::spawn_command()
+        .arg("3")
+        .stdout(std::process::Stdio::null())
+        .status()
+        .expect("Failed to run timeterm");
+    assert!(status.success());
+    let err = start.elapsed().as_secs_f64() * 1000.0 - 3000.0;
+    assert!(err.abs() <= TOLERANCE_MS, "3s timer off by {:.1}ms", err);
+}
@@ -0,0 +1,34 @@
+// tests/cron.rs
+mod common;
+use std::time::Duration;
+
+#[test]
+fn cron_counts_down_to_each_next_run() {
+    // E2E: Every minute rings within a minute, and --repeat waits for the one after
+    let cache = std::env::temp_dir().join(format!("timerterm-cron-{}", std::process::id()));
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["--internal-test-harness", "--no-notify", "--cron", "* * * * *", "--repeat", "2"])
+        .env("XDG_CACHE_HOME", &cache)
+        .write_stdin("advance 1:00\nadvance 1:00\n")
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    assert_eq!(out.stdout.iter().filter(|&&b| b == b'\x07').count(), 2);
+    let log = std::fs::read_to_string(cache.join("timerterm/timerterm.log")).unwrap();
+    std::fs::remove_dir_all(&cache).unwrap();
+    let starts: Vec<&str> = log.lines().filter_map(|line| line.split_once(": START ").map(|(_, rest)| rest)).collect();
+    assert_eq!(starts.len(), 2, "{}", log);
+    // Test: A minute on, the next run is as far off as the first was
+    assert_eq!(starts[0], starts[1]);
+}
+
+#[test]
+fn cron_that_never_runs_fails() {
+    // E2E: February 31st never comes, which is an error up front
+    let mut cmd = common::timeterm();
+    cmd.args(["--no-log", "--cron", "0 0 31 2 *"])
+        .timeout(Duration::from_secs(5))
+        .assert()
+        .code(1)
+        .stderr("--cron '0 0 31 2 *' doesn't come round in the next 30 years\n");
+}
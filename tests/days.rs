@@ -0,0 +1,33 @@
+// tests/days.rs
+mod common;
+use std::time::Duration;
+
+#[test]
+fn days_counts_down_across_midnight() {
+    // E2E: The count drops by one for each day the clock moves on
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["--internal-test-harness", "days", "2099-12-31", "--plain"])
+        .write_stdin("advance 24h\nadvance 24h\n")
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let counts: Vec<i64> = stdout.lines().map(|line| {
+        let (days, status) = line.split_once("  ").expect("a count and a status");
+        assert_eq!(status, "days until 2099-12-31");
+        days.parse().unwrap()
+    }).collect();
+    assert_eq!(counts.len(), 3, "{}", stdout);
+    assert_eq!([counts[0] - counts[1], counts[1] - counts[2]], [1, 1]);
+}
+
+#[test]
+fn days_to_a_past_date_fails() {
+    // E2E: Counting to a day already gone is an error up front
+    let mut cmd = common::timeterm();
+    cmd.args(["days", "2001-01-01"])
+        .timeout(Duration::from_secs(5))
+        .assert()
+        .code(1)
+        .stderr("2001-01-01 has already passed\n");
+}
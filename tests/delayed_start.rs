@@ -0,0 +1,85 @@
+// tests/delayed_start.rs
+mod common;
+use std::time::Duration;
+
+// A 3s countdown armed with `flags`, driven by `script`, its stdout lines
+fn run(flags: &[&str], script: &str) -> (bool, Vec<String>) {
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["--internal-test-harness", "--no-notify", "--silent", "--no-log", "--plain", "3"])
+        .args(flags)
+        .write_stdin(script)
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    (out.status.success(), String::from_utf8(out.stdout).unwrap().lines().map(String::from).collect())
+}
+
+#[test]
+fn wait_for_key_starts_on_space() {
+    // E2E: Time passing while armed doesn't count, the countdown starts on Space
+    let (ok, lines) = run(&["--wait-for-key"], "advance 10\nkeys  \nadvance 1\nadvance 1\nadvance 1\n");
+    assert!(ok);
+    assert_eq!(lines[0], "0:03  Press space to start");
+    assert_eq!(&lines[1..], ["0:03", "0:02", "0:01", "0:00"]);
+    // Test: Quitting while armed never starts it
+    let (ok, lines) = run(&["--wait-for-key"], "keys q\n");
+    assert!(ok);
+    assert_eq!(lines, ["0:03  Press space to start"]);
+}
+
+#[test]
+fn wait_for_file_starts_once_it_exists() {
+    // E2E: Armed until the file appears, then a full countdown
+    let path = std::env::temp_dir().join(format!("timerterm-delayed-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut child = common::spawn_command()
+        .args(["--internal-test-harness", "--no-notify", "--silent", "--no-log", "--plain", "3", "--wait-for-file"]).arg(&path)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn().unwrap();
+    use std::io::Write;
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(b"advance 5\n").unwrap();
+    std::thread::sleep(Duration::from_millis(300));
+    std::fs::write(&path, "").unwrap();
+    stdin.write_all(b"advance 1\nadvance 3\n").unwrap();
+    drop(stdin);
+    let out = child.wait_with_output().unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], format!("0:03  Waiting for {}", path.display()));
+    // Test: The wait before the file showed up isn't taken off the countdown
+    assert_eq!(lines[lines.len() - 2..], ["0:03", "0:00"]);
+}
+
+#[test]
+#[cfg(unix)]
+fn after_pid_starts_once_the_process_exits() {
+    // E2E: Armed while the process runs, counting once it has exited
+    let mut sleeper = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+    let pid = sleeper.id().to_string();
+    let mut child = common::spawn_command()
+        .args(["--internal-test-harness", "--no-notify", "--silent", "--no-log", "--plain", "3", "--after-pid", &pid])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn().unwrap();
+    use std::io::Write;
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(b"advance 5\n").unwrap();
+    std::thread::sleep(Duration::from_millis(300));
+    sleeper.kill().unwrap();
+    sleeper.wait().unwrap();
+    stdin.write_all(b"advance 1\nadvance 3\n").unwrap();
+    drop(stdin);
+    let out = child.wait_with_output().unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], format!("0:03  Waiting for PID {} to exit", pid));
+    assert_eq!(lines[lines.len() - 2..], ["0:03", "0:00"]);
+    // Test: A process that's already gone is an error before anything is drawn
+    let out = common::timeterm().args(["--internal-test-harness", "3", "--after-pid", &pid]).output().unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    assert_eq!(String::from_utf8(out.stderr).unwrap(), format!("no process with PID {} is running\n", pid));
+}
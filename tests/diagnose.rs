@@ -0,0 +1,18 @@
+// tests/diagnose.rs
+mod common;
+
+#[test]
+fn diagnose_reports_each_clock_measure() {
+    // E2E: A second of real sampling prints every measure, then a verdict.
+    // Test hosts can be noisy, so not which verdict.
+    let out = common::timeterm().args(["diagnose", "1"])
+        .timeout(std::time::Duration::from_secs(5)).output().unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "Sampling the clocks for 0:01...");
+    for (line, measure) in lines[1..6].iter().zip(["Clock source", "Resolution", "Sleep overrun", "Wall clock drift", "Wall clock jumps"]) {
+        assert!(line.starts_with(measure), "got {:?}", line);
+    }
+    assert!(lines[7] == "No anomalies, timers should keep good time here" || lines[7] == "Anomalies:", "got {:?}", lines[7]);
+}
@@ -0,0 +1,31 @@
+// tests/duel.rs
+mod common;
+use std::time::Duration;
+
+fn duel(script: &str) -> String {
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["--internal-test-harness", "duel", "10", "--names", "Ana,Ben", "--no-notify"])
+        .write_stdin(script)
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    String::from_utf8(out.stdout).unwrap()
+}
+
+#[test]
+fn duel_is_won_by_the_side_paused_less() {
+    // E2E: Ana pauses for 3s, so Ben reaches zero first
+    let stdout = duel("keys a\nadvance 3\nkeys a\nadvance 7\nadvance 3\n");
+    assert!(stdout.contains("Ana (PAUSED)"));
+    assert!(stdout.contains("Ben (DONE)"));
+    assert!(stdout.ends_with("Ana  0:10 of 0:10, 1 pause (0:03)\nBen  0:10 of 0:10, no pauses\nBen won by 0:03\n"));
+    // Test: A chime for the first one done, the bell again for the second
+    assert_eq!(stdout.matches('\x07').count(), 2);
+}
+
+#[test]
+fn quitting_a_duel_compares_progress() {
+    // E2E: Space pauses both, l resumes only Ben, then q ends it early
+    let stdout = duel("advance 4\nkeys  l\nadvance 2\nkeys q\n");
+    assert!(stdout.ends_with("Ana  0:04 of 0:10, 1 pause (0:02)\nBen  0:06 of 0:10, 1 pause (0:00)\nBen was ahead by 0:02\n"));
+}
@@ -0,0 +1,35 @@
+// tests/duration_sanity.rs
+mod common;
+use std::time::Duration;
+
+fn run(args: &[&str], stdin: &str) -> std::process::Output {
+    let mut cmd = common::timeterm();
+    cmd.args(args).args(["--no-notify"]).write_stdin(stdin).timeout(Duration::from_secs(5)).output().unwrap()
+}
+
+#[test]
+fn long_duration_asks_first() {
+    // E2E: 90000s (25h) is probably a typo, answering "n" doesn't start it
+    let out = run(&["90000"], "n\n");
+    assert!(out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("25:00:00 is longer than 24:00:00, start it anyway? [y/N]"), "Unexpected stderr: {}", stderr);
+    // Test: The timer never took over the screen
+    assert!(!String::from_utf8_lossy(&out.stdout).contains("\x1b[?1049h"));
+}
+
+#[test]
+fn no_confirm_fails_instead_of_asking() {
+    // E2E: Scripts get an error and exit code instead of a hanging prompt
+    let out = run(&["0", "--no-confirm"], "");
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("A 0:00 timer ends immediately"));
+}
+
+#[test]
+fn force_skips_sanity_check() {
+    // E2E: --force starts a zero-length timer without asking
+    let out = run(&["0", "--force"], "");
+    assert!(out.status.success());
+    assert!(out.stderr.is_empty());
+}
@@ -0,0 +1,31 @@
+// tests/elapsed.rs
+mod common;
+use std::time::Duration;
+
+#[test]
+fn elapsed_gives_a_head_start() {
+    // E2E: A 3:00 timer 2:30 in finishes after 30 more seconds
+    let cache = std::env::temp_dir().join(format!("timerterm-elapsed-{}", std::process::id()));
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["--internal-test-harness", "3:00", "--elapsed", "2:30", "--no-notify"])
+        .env("XDG_CACHE_HOME", &cache)
+        .write_stdin("advance 29\nadvance 1\n")
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    assert_eq!(out.stdout.iter().filter(|&&b| b == b'\x07').count(), 1);
+    // Test: The history counts the head start, from the start event on
+    let log = std::fs::read_to_string(cache.join("timerterm/timerterm.log")).unwrap();
+    std::fs::remove_dir_all(&cache).unwrap();
+    let messages: Vec<&str> = log.lines().map(|l| l.split_once("]: ").unwrap().1).collect();
+    assert_eq!(messages, [format!("START duration=180s elapsed=150s{}", common::logged_dir()), "COMPLETED elapsed=180s".into()]);
+}
+
+#[test]
+fn elapsed_must_leave_time_to_run() {
+    // E2E: Nothing left to count down is refused
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["1:00", "--elapsed", "90s", "--no-log"]).timeout(Duration::from_secs(3)).output().unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&out.stderr), "--elapsed 1:30 leaves nothing of a 1:00 timer\n");
+}
@@ -0,0 +1,43 @@
+// tests/escape_keys.rs
+// Needs a pty, so the keys come through raw mode
+#![cfg(unix)]
+mod common;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::os::fd::FromRawFd;
+use std::process::Stdio;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn escape_sequences_are_not_keys() {
+    // E2E: F2 sends ESC O Q, which mustn't read as q and cancel the run
+    let (mut master, mut slave) = (0, 0);
+    let ret = unsafe { libc::openpty(&mut master, &mut slave, std::ptr::null_mut(), std::ptr::null(), std::ptr::null()) };
+    assert_eq!(ret, 0, "openpty failed");
+    let cache = std::env::temp_dir().join(format!("timerterm-escape-keys-{}", std::process::id()));
+
+    let slave_file = unsafe { File::from_raw_fd(slave) };
+    let mut child = common::spawn_command()
+        .args(["2", "--no-notify"])
+        .env("XDG_CACHE_HOME", &cache)
+        .stdin(Stdio::from(slave_file.try_clone().unwrap()))
+        .stdout(Stdio::from(slave_file))
+        .spawn()
+        .expect("Failed to start timeterm");
+    let mut master = unsafe { File::from_raw_fd(master) };
+    let mut input = master.try_clone().unwrap();
+    let reader = thread::spawn(move || {
+        // Read until EIO once the child has closed its end
+        let mut out = Vec::new();
+        let _ = master.read_to_end(&mut out);
+    });
+
+    thread::sleep(Duration::from_millis(500));
+    input.write_all(b"\x1bOQ").unwrap();
+    assert!(child.wait().unwrap().success());
+    reader.join().unwrap();
+    let log = std::fs::read_to_string(cache.join("timerterm/timerterm.log")).unwrap();
+    std::fs::remove_dir_all(&cache).unwrap();
+    assert!(log.contains(": COMPLETED"), "{}", log);
+}
@@ -0,0 +1,79 @@
+// tests/exec_hook.rs
+// Commands are written for sh
+#![cfg(unix)]
+mod common;
+use std::io::Write;
+use std::process::Stdio;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn exec_runs_after_completion() {
+    // E2E: The command runs once the timer is done, after the terminal is restored.
+    // The harness keeps full frames on a pipe, so there's an alt screen to leave.
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["--internal-test-harness", "1", "--no-notify", "--name", "build", "--exec", "echo \"ran $TIMERTERM_NAME $TIMERTERM_DURATION\""])
+        .env("TERM", "xterm")
+        .write_stdin("advance 1\n")
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    // Test: Output comes after leaving the alt screen, so it stays visible
+    let leave = stdout.rfind("\x1b[?1049l").expect("Alt screen should be left");
+    assert_eq!(&stdout[leave + "\x1b[?1049l".len()..], "ran build 1\n");
+}
+
+#[test]
+fn exec_failure_sets_exit_code() {
+    // E2E: A failing command's exit code is passed on for scripts
+    let mut cmd = common::timeterm();
+    cmd.args(["1", "--no-notify", "--exec", "exit 4"]).timeout(Duration::from_secs(5)).assert().code(4);
+}
+
+#[test]
+fn exec_skipped_when_quit_early() {
+    // E2E: Quitting with q means the timer never finished, so nothing runs
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["60", "--no-notify", "--exec", "echo should-not-run"])
+        .write_stdin("q")
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    assert!(!String::from_utf8_lossy(&out.stdout).contains("should-not-run"));
+}
+
+#[test]
+fn watch_exec_shows_output_under_the_timer() {
+    // E2E: With --watch-exec the command runs on the timer screen, its lines printed under it
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["--internal-test-harness", "--plain", "1", "--no-notify", "--watch-exec", "--exec", "echo built; exit 3"])
+        .write_stdin("advance 1\n")
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.contains("0:00  Running echo built; exit 3 - press k to kill it\n  built\n"), "{}", stdout);
+    // Test: Its exit code is still passed on
+    assert_eq!(out.status.code(), Some(3));
+}
+
+#[test]
+fn k_kills_a_watched_exec() {
+    // E2E: k stops a command that's taking too long
+    let mut child = common::spawn_command()
+        .args(["1", "--no-notify", "--watch-exec", "--exec", "echo started; sleep 30"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start timeterm");
+    thread::sleep(Duration::from_millis(2000));
+    assert!(child.try_wait().unwrap().is_none(), "The command should still be running");
+    let killed = Instant::now();
+    child.stdin.as_mut().unwrap().write_all(b"k").unwrap();
+    let out = child.wait_with_output().unwrap();
+    assert!(killed.elapsed() < Duration::from_secs(5));
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&out.stdout).contains("  started\n"));
+    assert_eq!(String::from_utf8_lossy(&out.stderr), "--exec command killed\n");
+}
@@ -0,0 +1,58 @@
+// tests/git_timebox.rs
+mod common;
+use std::path::Path;
+use std::time::Duration;
+
+// A fresh repo on branch `topic` with a commit message waiting in it
+fn repo(dir: &Path) {
+    let _ = std::fs::remove_dir_all(dir);
+    std::fs::create_dir_all(dir).unwrap();
+    let init = std::process::Command::new("git").args(["init", "-q", "-b", "topic"]).current_dir(dir).status().unwrap();
+    assert!(init.success());
+    std::fs::write(dir.join(".git/COMMIT_EDITMSG"), "Fix the thing\n").unwrap();
+}
+
+#[test]
+fn git_timebox_labels_the_run_and_adds_a_trailer() {
+    // E2E: Labeled repo/branch in the log, then the trailer once it finishes
+    let dir = std::env::temp_dir().join(format!("timerterm-git-timebox-{}", std::process::id()));
+    repo(&dir);
+    let cache = dir.join("cache");
+    let out = common::timeterm().args(["--internal-test-harness", "git-timebox", "3", "--no-notify", "--silent", "--plain"])
+        .current_dir(&dir)
+        .env("XDG_CACHE_HOME", &cache)
+        .write_stdin("advance 3\n")
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    let name = dir.file_name().unwrap().to_str().unwrap();
+    let log = std::fs::read_to_string(cache.join("timerterm/timerterm.log")).unwrap();
+    assert!(log.lines().next().unwrap().ends_with(&format!(": START duration=3s label=\"{}/topic\" dir={:?}", name, dir.canonicalize().unwrap().to_string_lossy())));
+    let message = std::fs::read_to_string(dir.join(".git/COMMIT_EDITMSG")).unwrap();
+    assert_eq!(message, "Fix the thing\n\nTimeboxed: 3s actual 3s\n");
+    // Test: Quitting early still records how long it took, in the file asked for
+    std::fs::write(dir.join("msg.txt"), "Review\n").unwrap();
+    let out = common::timeterm().args(["--internal-test-harness", "git-timebox", "5", "--no-notify", "--silent", "--plain", "--no-log", "--message-file", "msg.txt"])
+        .current_dir(&dir)
+        .write_stdin("advance 2\nkeys q\n")
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    assert_eq!(std::fs::read_to_string(dir.join("msg.txt")).unwrap(), "Review\n\nTimeboxed: 5s actual 2s\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn git_timebox_needs_a_repo() {
+    // E2E: Outside a repo it says so before anything starts
+    let dir = std::env::temp_dir().join(format!("timerterm-git-timebox-none-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let out = common::timeterm().args(["--internal-test-harness", "git-timebox", "3"])
+        .current_dir(&dir)
+        .env("GIT_CEILING_DIRECTORIES", std::env::temp_dir())
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8(out.stderr).unwrap().starts_with("git-timebox: fatal: not a git repository"));
+}
@@ -0,0 +1,40 @@
+// tests/intervals.rs
+mod common;
+use std::time::Duration;
+
+// Run with the fake clock, feeding `script` as harness commands
+fn intervals(args: &[&str], script: &str) -> std::process::Output {
+    let mut cmd = common::timeterm();
+    cmd.args(["--internal-test-harness", "intervals", "--no-log", "--no-notify"]).args(args)
+        .write_stdin(script)
+        .timeout(Duration::from_secs(5))
+        .output().unwrap()
+}
+
+#[test]
+fn intervals_alternate_and_sum_up() {
+    // E2E: 2 rounds of 40s/20s are work, rest, work, each colored, then the totals
+    let out = intervals(&["40s/20s", "x2"], "advance 40\nadvance 20\nadvance 40\n");
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let (screen, after) = stdout.rsplit_once("\x1b[?1049l").unwrap();
+    let at = |text: &str| screen.find(text).unwrap_or_else(|| panic!("no {}", text));
+    assert!(at("Work 1/2") < at("Rest 1/2") && at("Rest 1/2") < at("Work 2/2"));
+    assert!(!screen.contains("Rest 2/2"));
+    // Test: Work is red, rest green
+    let color_of = |text: &str| &screen[screen[..at(text)].rfind("\x1b[3").unwrap()..][..5];
+    assert_eq!(color_of("Work 1/2"), "\x1b[31m");
+    assert_eq!(color_of("Rest 1/2"), "\x1b[32m");
+    // Test: A bell at each change and at the end
+    assert_eq!(screen.matches('\x07').count(), 3);
+    assert_eq!(after, "2 of 2 rounds, 1:20 worked, 0:20 rested\n");
+}
+
+#[test]
+fn quitting_intervals_reports_what_was_done() {
+    // E2E: Quitting in the second round counts the first round only
+    let out = intervals(&["40s/20s"], "advance 40\nadvance 20\nadvance 10\n");
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.ends_with("1 of 8 rounds, 0:50 worked, 0:20 rested\n"));
+}
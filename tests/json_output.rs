@@ -0,0 +1,43 @@
+// tests/json_output.rs
+mod common;
+use std::io::Write;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+#[test]
+fn status_events_and_summary_are_written_as_json() {
+    // E2E: The status file follows the timer, events are appended as they
+    // happen and --json prints the summary once it ends
+    let dir = common::scratch().join("json-output");
+    std::fs::create_dir_all(&dir).unwrap();
+    let (status, events) = (dir.join("status.json"), dir.join("events.jsonl"));
+    let mut child = common::spawn_command()
+        .args(["--internal-test-harness", "3", "--label", "Tea", "--no-notify", "--silent", "--no-log", "--plain", "--json"])
+        .arg("--status-file").arg(&status)
+        .arg("--events-file").arg(&events)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to start timeterm");
+    let mut stdin = child.stdin.take().unwrap();
+    writeln!(stdin, "advance 1").unwrap();
+    let want = r#"{"label":"Tea","direction":"down","duration_ms":3000,"elapsed_ms":1000,"remaining_ms":2000,"paused":false,"finished":false}"#;
+    let start = Instant::now();
+    while std::fs::read_to_string(&status).unwrap_or_default().trim() != want {
+        assert!(start.elapsed() < Duration::from_secs(3), "status never reached 0:01");
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    writeln!(stdin, "advance 2").unwrap();
+    drop(stdin);
+    let out = child.wait_with_output().unwrap();
+    assert!(out.status.success());
+
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert_eq!(stdout.lines().last(), Some(r#"{"label":"Tea","planned_ms":3000,"elapsed_ms":3000,"completed":true}"#));
+    assert_eq!(std::fs::read_to_string(&events).unwrap(),
+        format!("{{\"event\":\"started\",\"duration_ms\":3000,\"label\":\"Tea\",\"dir\":{:?}}}\n{{\"event\":\"finished\",\"elapsed_ms\":3000}}\n",
+            common::scratch().canonicalize().unwrap().to_string_lossy()));
+    // Test: The status file goes once the run is over
+    assert!(!status.exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
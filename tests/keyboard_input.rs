@@ -0,0 +1,76 @@
+// tests/keyboard_input.rs
+mod common;
+use std::io::Write;
+use std::process::{Child, Stdio};
+use std::thread;
+use std::time::Duration;
+
+fn spawn_timer(arg: &str) -> Child {
+    common::spawn_command()
+        .arg(arg)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("Failed to start timeterm")
+}
+
+fn press(child: &mut Child, keys: &str) {
+    let stdin = child.stdin.as_mut().unwrap();
+    stdin.write_all(keys.as_bytes()).unwrap();
+    stdin.flush().unwrap();
+}
+
+#[test]
+fn q_quits_cleanly() {
+    // E2E: Pressing q should exit well before the 60s timer is up
+    let mut child = spawn_timer("60");
+    thread::sleep(Duration::from_millis(300));
+    press(&mut child, "q");
+    thread::sleep(Duration::from_millis(500));
+    let status = child.try_wait().unwrap().expect("Process should have quit on q");
+    assert!(status.success());
+}
+
+#[test]
+fn space_pauses_countdown() {
+    // E2E: A paused 1s timer shouldn't finish, space again resumes it
+    let mut child = spawn_timer("1");
+    press(&mut child, " ");
+    thread::sleep(Duration::from_millis(1500));
+    assert!(child.try_wait().unwrap().is_none(), "Paused timer should still be running");
+
+    press(&mut child, " ");
+    thread::sleep(Duration::from_millis(1500));
+    let status = child.try_wait().unwrap().expect("Resumed timer should have finished");
+    assert!(status.success());
+}
+
+#[test]
+fn r_restarts_countdown() {
+    // E2E: Restarting at ~0.7s into a 1s timer pushes completion past 1.2s
+    let mut child = spawn_timer("1");
+    thread::sleep(Duration::from_millis(700));
+    press(&mut child, "r");
+    thread::sleep(Duration::from_millis(600));
+    assert!(child.try_wait().unwrap().is_none(), "Restarted timer should still be running");
+    let status = child.wait().unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn stopwatch_counts_until_quit_and_reports_elapsed() {
+    // E2E: --stopwatch runs past any duration, q stops it and elapsed is printed
+    let mut child = common::spawn_command()
+        .arg("--stopwatch")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to start timeterm");
+    thread::sleep(Duration::from_millis(1300));
+    assert!(child.try_wait().unwrap().is_none(), "Stopwatch should keep running");
+    press(&mut child, "q");
+    let out = child.wait_with_output().unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.ends_with("Elapsed: 0:01\n"), "Unexpected output tail: {:?}", &stdout[stdout.len().saturating_sub(40)..]);
+}
@@ -0,0 +1,29 @@
+// tests/keypad.rs
+mod common;
+use std::time::Duration;
+
+fn keypad(script: &str) -> String {
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["--internal-test-harness", "--keypad", "--plain", "--no-log", "--no-notify"])
+        .write_stdin(script)
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    String::from_utf8(out.stdout).unwrap()
+}
+
+#[test]
+fn keypad_digits_fill_from_the_right() {
+    // E2E: 1, 3, 0 reads 0:01, 0:13 then 1:30, and Enter counts that down
+    let stdout = keypad("type 130\nadvance 1:30\n");
+    let times: Vec<&str> = stdout.lines().map(|line| line.split("  ").next().unwrap()).collect();
+    assert_eq!(times, ["0:00", "0:01", "0:13", "1:30", "1:30", "0:00", "\x07"]);
+}
+
+#[test]
+fn keypad_backspace_and_empty_enter() {
+    // E2E: Backspace takes the last digit off, Enter on 0:00 does nothing
+    let stdout = keypad("type \ntype 5\x7f45\nadvance 45\n");
+    assert!(stdout.contains("0:05  Type the time"));
+    assert!(stdout.ends_with("0:45\n0:00\n\x07"), "{}", stdout);
+}
@@ -0,0 +1,26 @@
+// tests/label.rs
+mod common;
+use std::time::Duration;
+
+#[test]
+fn label_shows_in_header_title_and_log() {
+    // E2E: One --label reaches the screen, the window title and the history
+    let cache = std::env::temp_dir().join(format!("timerterm-label-{}", std::process::id()));
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["--internal-test-harness", "--no-notify", "5", "--label", "Pasta"])
+        .env("XDG_CACHE_HOME", &cache)
+        .write_stdin("advance 1\n")
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    // Test: The title is pushed over the shell's and popped again on exit
+    assert!(stdout.starts_with("\x1b[?1049h\x1b[?25l\x1b[2J\x1b[22;0t\x1b]0;Pasta - timerterm\x1b\\"));
+    assert!(stdout.ends_with("\x1b[23;0t"));
+    let mut parser = vt100::Parser::new(24, 80, 0);
+    parser.process(stdout.split("\x1b[?1049l").next().unwrap().as_bytes());
+    assert_eq!(parser.screen().contents().lines().next().unwrap().trim(), "Pasta");
+    let log = std::fs::read_to_string(cache.join("timerterm/timerterm.log")).unwrap();
+    std::fs::remove_dir_all(&cache).unwrap();
+    assert!(log.lines().next().unwrap().ends_with(&format!(": START duration=5s label=\"Pasta\"{}", common::logged_dir())));
+}
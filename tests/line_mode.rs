@@ -0,0 +1,56 @@
+// tests/line_mode.rs
+mod common;
+use std::time::Duration;
+
+// Piped stdout has no size, so TERM decides between frames and lines
+fn run_with_term(term: Option<&str>, args: &[&str], script: &str) -> std::process::Output {
+    let mut cmd = common::timeterm();
+    match term {
+        Some(term) => cmd.env("TERM", term),
+        None => cmd.env_remove("TERM"),
+    };
+    cmd.arg("--internal-test-harness").args(["--no-log", "--no-notify"]).args(args)
+        .write_stdin(script)
+        .timeout(Duration::from_secs(5))
+        .output().unwrap()
+}
+
+#[test]
+fn unset_term_prints_a_line_per_update() {
+    // E2E: No escapes at all, just the time as it changes, then the bell.
+    // Time passed while paused still doesn't count.
+    let out = run_with_term(None, &["1:30"], "advance 1\nkeys  \nadvance 10\nkeys  \nadvance 1:29\n");
+    assert!(out.status.success());
+    assert_eq!(String::from_utf8(out.stdout).unwrap(), "1:30\n1:29\n1:29  PAUSED\n0:00\n\x07");
+    // Test: The fallback is explained on stderr
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert_eq!(stderr, "timerterm: terminal size unknown and TERM is unset, assuming 80x24 and printing a line per update\n");
+}
+
+#[test]
+fn dumb_term_gets_lines_and_real_term_gets_frames() {
+    let out = run_with_term(Some("dumb"), &["5"], "advance 5\n");
+    assert_eq!(String::from_utf8(out.stdout).unwrap(), "0:05\n0:00\n\x07");
+    assert!(String::from_utf8(out.stderr).unwrap().contains("TERM is 'dumb'"));
+    // Test: Under the harness a usable TERM keeps full 80x24 frames on a pipe
+    let out = run_with_term(Some("xterm"), &["5"], "advance 5\n");
+    assert!(String::from_utf8(out.stdout).unwrap().contains("\x1b[10;1H"));
+    assert!(out.stderr.is_empty());
+    // Test: Unless --plain asks for lines
+    let out = run_with_term(Some("xterm"), &["5", "--plain"], "advance 5\n");
+    assert_eq!(String::from_utf8(out.stdout).unwrap(), "0:05\n0:00\n\x07");
+    assert!(out.stderr.is_empty());
+}
+
+#[test]
+fn piped_output_gets_lines_whatever_term_says() {
+    // E2E: A real run into a pipe, as with `timerterm 1 | tee log`, prints
+    // lines without escape codes and without explaining itself
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["1", "--no-log", "--no-notify"]).env("TERM", "xterm-256color")
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    assert_eq!(String::from_utf8(out.stdout).unwrap(), "0:01\n0:00\n\x07");
+    assert!(out.stderr.is_empty());
+}
@@ -0,0 +1,70 @@
+// tests/notification.rs
+// Runs against a fake notify-send on PATH, so Linux/BSD only
+#![cfg(all(unix, not(target_os = "macos")))]
+mod common;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Dir holding a notify-send that appends its arguments to `calls`
+fn fake_notifier(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("timeterm-notify-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let script = dir.join("notify-send");
+    std::fs::write(&script, format!("#!/bin/sh\necho \"$@\" >> {}\n", dir.join("calls").display())).unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+    dir
+}
+
+fn run_with(dir: &Path, args: &[&str]) {
+    let path = format!("{}:{}", dir.display(), std::env::var("PATH").unwrap_or_default());
+    let mut cmd = common::timeterm();
+    cmd.env("PATH", path).args(args).timeout(Duration::from_secs(5)).assert().success();
+}
+
+#[test]
+fn notifies_on_completion() {
+    // E2E: Finishing sends the label and elapsed time to the desktop notifier
+    let dir = fake_notifier("done");
+    run_with(&dir, &["1", "--name", "tea"]);
+    let calls = std::fs::read_to_string(dir.join("calls")).unwrap();
+    assert_eq!(calls, "--app-name=timerterm timerterm Timer 'tea' finished after 0:01\n");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn label_names_the_notification() {
+    // E2E: A --label is used as is, no "Timer '...'" around it
+    let dir = fake_notifier("label");
+    run_with(&dir, &["1", "--name", "tea", "--label", "Green tea"]);
+    let calls = std::fs::read_to_string(dir.join("calls")).unwrap();
+    assert_eq!(calls, "--app-name=timerterm timerterm Green tea finished after 0:01\n");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn no_notify_stays_quiet() {
+    // E2E: --no-notify skips the notifier entirely
+    let dir = fake_notifier("quiet");
+    run_with(&dir, &["1", "--no-notify"]);
+    assert!(!dir.join("calls").exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn failing_notifier_falls_back_down_the_chain() {
+    // E2E: notify-send failing, osc is skipped off a terminal and stderr stands in
+    let dir = fake_notifier("fallback");
+    std::fs::write(dir.join("notify-send"), "#!/bin/sh\nexit 1\n").unwrap();
+    std::fs::create_dir_all(dir.join("timerterm")).unwrap();
+    std::fs::write(dir.join("timerterm").join("config.toml"), "[notify]\nfallback = \"osc, stderr\"\n").unwrap();
+    let path = format!("{}:{}", dir.display(), std::env::var("PATH").unwrap_or_default());
+    let out = common::timeterm().env("PATH", path).env("XDG_CONFIG_HOME", &dir)
+        .args(["1", "--name", "tea"]).timeout(Duration::from_secs(5)).output().unwrap();
+    assert!(out.status.success());
+    assert!(!String::from_utf8_lossy(&out.stdout).contains("\x1b]777"));
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert!(stderr.contains("timerterm: Timer 'tea' finished after 0:01\n"), "{}", stderr);
+    assert!(stderr.contains("Couldn't send notification: notify-send exited with exit status: 1, used stderr instead\n"), "{}", stderr);
+    let _ = std::fs::remove_dir_all(&dir);
+}
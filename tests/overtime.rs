@@ -0,0 +1,28 @@
+// tests/overtime.rs
+mod common;
+use std::time::Duration;
+
+#[test]
+fn overtime_counts_past_zero_until_a_key() {
+    // E2E: The countdown finishes, 42s more pass in red, q ends it with the overage
+    let runtime = std::env::temp_dir().join(format!("timerterm-overtime-{}", std::process::id()));
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["--internal-test-harness", "--no-log", "--no-notify", "3", "--overtime", "--label", "Standup"])
+        .env("XDG_RUNTIME_DIR", &runtime)
+        .write_stdin("advance 3\nadvance 42\nkeys q\n")
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let (screen, after) = stdout.rsplit_once("\x1b[?1049l").unwrap();
+    let mut parser = vt100::Parser::new(24, 80, 0);
+    parser.process(screen.as_bytes());
+    let rows: Vec<String> = parser.screen().contents().lines().map(|l| l.trim().to_string()).collect();
+    // Test: The last frame is "-0:42" in big digits, drawn red
+    assert_eq!(rows[9], timeterm::render::big_text("-0:42")[0].trim());
+    assert_eq!(rows[15], "OVERTIME - press q or run: timerterm ack timer");
+    assert!(screen.contains("\x1b[31m"));
+    // Test: The summary after the run reports how far over it went
+    assert!(after.contains("Standup: 0:03 of 0:03, finished, 0:42 over\n"));
+    let _ = std::fs::remove_dir_all(&runtime);
+}
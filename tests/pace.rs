@@ -0,0 +1,32 @@
+// tests/pace.rs
+mod common;
+
+#[test]
+fn stopwatch_shows_pace_over_distance() {
+    // E2E: --distance 500m puts the pace per 500m (here just the elapsed time) under the stopwatch
+    // The harness draws frames even on a pipe, as long as TERM says it can
+    let output = common::timeterm()
+        .args(["--internal-test-harness", "stopwatch", "--distance", "500m"])
+        .env("TERM", "xterm")
+        .write_stdin("advance 1\n")
+        .timeout(std::time::Duration::from_secs(5))
+        .output().unwrap();
+    assert!(output.status.success());
+
+    let out = String::from_utf8(output.stdout).unwrap();
+    let shown = out.split("\x1b[?1049l").next().unwrap();
+    let mut parser = vt100::Parser::new(24, 80, 0);
+    parser.process(shown.as_bytes());
+    let screen = parser.screen().contents();
+    // Big elapsed time on rows 10-14, blank row, then the pace on row 16
+    let pace = screen.lines().nth(15).unwrap().trim();
+    assert_eq!(pace, "0:01/500m");
+}
+
+#[test]
+fn distance_needs_a_stopwatch() {
+    // E2E: A countdown has no use for a distance, so it's an argument error
+    let out = common::timeterm().args(["5:00", "--distance", "2km"]).output().unwrap();
+    assert_eq!(out.status.code(), Some(2));
+    assert!(String::from_utf8(out.stderr).unwrap().contains("--distance only works with a stopwatch"));
+}
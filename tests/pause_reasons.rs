@@ -0,0 +1,48 @@
+// tests/pause_reasons.rs
+mod common;
+use std::io::Write;
+use std::process::Stdio;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn pause_reason_is_logged_with_the_pause() {
+    // E2E: space, c, space with --pause-reasons logs the pause as a coffee break
+    let cache = std::env::temp_dir().join(format!("timerterm-history-{}", std::process::id()));
+    let mut child = common::spawn_command()
+        .args(["1", "--pause-reasons"])
+        .env("XDG_CACHE_HOME", &cache)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to start timeterm");
+    let mut press = |keys: &str| {
+        let stdin = child.stdin.as_mut().unwrap();
+        stdin.write_all(keys.as_bytes()).unwrap();
+        stdin.flush().unwrap();
+        thread::sleep(Duration::from_millis(300));
+    };
+    press(" ");
+    press("c");
+    press(" ");
+
+    let out = child.wait_with_output().unwrap();
+    assert!(out.status.success());
+    // Test: The menu is offered while paused
+    assert!(String::from_utf8_lossy(&out.stdout).contains("PAUSED  [c]offee [p]hone [m]eeting [o]ther"));
+    let log = std::fs::read_to_string(cache.join("timerterm/timerterm.log")).unwrap();
+    std::fs::remove_dir_all(&cache).unwrap();
+    let messages: Vec<&str> = log.lines().map(|l| l.split_once("]: ").unwrap().1).collect();
+    let start = format!("START duration=1s{}", common::logged_dir());
+    assert_eq!(messages, [start.as_str(), "PAUSE elapsed=0s reason=coffee", "RESUME elapsed=0s", "COMPLETED elapsed=1s"]);
+}
+
+#[test]
+fn no_log_leaves_history_alone() {
+    // E2E: --no-log runs without creating the log
+    let cache = std::env::temp_dir().join(format!("timerterm-nolog-{}", std::process::id()));
+    let mut cmd = common::timeterm();
+    cmd.args(["1", "--no-log"]).env("XDG_CACHE_HOME", &cache).timeout(Duration::from_secs(3));
+    cmd.assert().success();
+    assert!(!cache.exists());
+}
@@ -0,0 +1,147 @@
+// tests/pomodoro.rs
+// Runs on the test harness's fake clock, each "advance" is a step of the cycle
+mod common;
+use std::time::Duration;
+
+fn pomodoro(args: &[&str], script: &str, config_home: Option<&std::path::Path>) -> std::process::Output {
+    let mut cmd = common::timeterm();
+    cmd.args(["pomodoro", "--internal-test-harness", "--no-log"]).args(args);
+    if let Some(home) = config_home { cmd.env("XDG_CONFIG_HOME", home); }
+    cmd.write_stdin(script).timeout(Duration::from_secs(5)).output().unwrap()
+}
+
+#[test]
+fn pomodoro_cycles_phases_with_bell() {
+    // E2E: 1s work/long-break phases alternate, ring on each transition, quitting stops the cycle
+    let out = pomodoro(&["--work", "0:01", "--long-break", "0:01", "--long-every", "1"], "advance 1\nadvance 1\nkeys q\n", None);
+    assert!(out.status.success());
+
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let work = stdout.find("Work 1/1").expect("Work phase label not shown");
+    let long = stdout.find("Long break").expect("Long break label not shown");
+    assert!(work < long);
+    // Test: Two transitions (work -> long break -> work) means two bells
+    assert_eq!(stdout.matches('\x07').count(), 2);
+    assert!(stdout[long..].contains("Work 1/1"), "Cycle should restart after the long break");
+}
+
+#[test]
+fn breaks_suggest_activities() {
+    // E2E: The break shows the timed activity counting down, then the next one
+    let home = std::env::temp_dir().join(format!("timeterm-breaks-{}", std::process::id()));
+    std::fs::create_dir_all(home.join("timerterm")).unwrap();
+    let config = "[break_activities]\neyes = \"Look 20ft away\"\nwater = \"Drink some water\"\n[break_timers]\neyes = 2\n";
+    std::fs::write(home.join("timerterm").join("config.toml"), config).unwrap();
+    let args = ["--work", "0:01", "--break", "0:05", "--long-every", "2"];
+    let out = pomodoro(&args, "advance 1\nadvance 1\nadvance 1\n", Some(&home));
+    let _ = std::fs::remove_dir_all(&home);
+    assert!(out.status.success());
+
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let brk = stdout.find("Break").expect("Break phase not shown");
+    assert!(!stdout[..brk].contains("Look 20ft away"), "Activities are for breaks only");
+    let eyes = stdout.find("Look 20ft away (0:02)").expect("Timed activity not shown");
+    let water = stdout.find("Drink some water").expect("Next activity not shown");
+    assert!(brk < eyes && eyes < stdout.find("Look 20ft away (0:01)").unwrap());
+    assert!(eyes < water);
+}
+
+#[test]
+fn interruptions_are_noted_and_listed() {
+    // E2E: i opens a note, Enter saves it, and the notes are listed on quitting
+    let script = "advance 5\nkeys i\ntype Sam called\nadvance 0:20\nkeys i\ntype \nkeys q\n";
+    let out = pomodoro(&["--work", "1:00", "--plain"], script, None);
+    assert!(out.status.success());
+
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    // Test: The empty second note is dropped, and the clock kept going while typing
+    assert!(stdout.ends_with("Interruptions:\n  Work 1/4 at 0:05  Sam called\n"), "got: {}", stdout);
+    assert!(stdout.contains("0:35  Work 1/4"));
+}
+
+#[test]
+fn reflection_asks_after_work_and_logs_the_answer() {
+    // E2E: With pomodoro.reflection the finished work block waits on the answer, which goes in the log
+    let home = std::env::temp_dir().join(format!("timeterm-reflect-{}", std::process::id()));
+    std::fs::create_dir_all(home.join("timerterm")).unwrap();
+    std::fs::write(home.join("timerterm").join("config.toml"), "[pomodoro]\nreflection = true\n").unwrap();
+    let mut cmd = common::timeterm();
+    let out = cmd.env("XDG_CONFIG_HOME", &home).env("XDG_CACHE_HOME", &home)
+        .args(["pomodoro", "--internal-test-harness", "--work", "0:01", "--break", "0:01", "--plain"])
+        .write_stdin("advance 1\ntype Fixed the parser\nadvance 1\nadvance 1\ntype \nkeys q\n")
+        .timeout(Duration::from_secs(5)).output().unwrap();
+    let log = std::fs::read_to_string(home.join("timerterm").join("timerterm.log")).unwrap_or_default();
+    let _ = std::fs::remove_dir_all(&home);
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.contains("0:00  Work 1/4 done. What did you accomplish? Fixed the parser_\n"), "got: {}", stdout);
+    // Test: The break isn't asked about, the next work block is, and Enter on nothing logs nothing
+    assert_eq!(stdout.matches("What did you accomplish? _").count(), 2);
+    assert!(stdout.contains("Work 2/4 done. What did you accomplish? _"));
+    assert_eq!(log.matches("REFLECT").count(), 1);
+    assert!(log.contains("REFLECT note=\"Fixed the parser\"\n"), "got: {}", log);
+}
+
+#[test]
+fn strict_breaks_ignore_skip_keys_and_dim() {
+    // E2E: Space and q do nothing on a strict break, which runs the dim hooks around it
+    let home = std::env::temp_dir().join(format!("timeterm-strict-{}", std::process::id()));
+    std::fs::create_dir_all(home.join("timerterm")).unwrap();
+    let hooks = home.join("hooks");
+    let config = format!("[pomodoro]\ndim = \"echo dim $TIMERTERM_NAME >> {0}\"\nundim = \"echo undim >> {0}\"\n", hooks.display());
+    std::fs::write(home.join("timerterm").join("config.toml"), config).unwrap();
+    let out = pomodoro(&["--work", "0:01", "--break", "0:02", "--strict-breaks", "--plain"], "advance 1\nkeys  q\nadvance 2\nkeys q\n", Some(&home));
+    let hooks = std::fs::read_to_string(&hooks).unwrap_or_default();
+    let _ = std::fs::remove_dir_all(&home);
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(!stdout.contains("PAUSED"), "got: {}", stdout);
+    assert!(stdout.contains("Work 2/4"), "the break should have run out, got: {}", stdout);
+    assert_eq!(hooks, "dim Break\nundim\n");
+}
+
+#[test]
+#[cfg(unix)]
+fn strict_break_holds_ctrl_c_until_it_ends() {
+    // E2E: SIGINT mid-break waits for the break to run out, then stops the session
+    use std::io::Write;
+    let mut child = common::spawn_command()
+        .args(["pomodoro", "--internal-test-harness", "--no-log", "--plain", "--work", "0:01", "--break", "0:02", "--strict-breaks"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn().unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(b"advance 1\n").unwrap();
+    std::thread::sleep(Duration::from_millis(300));
+    unsafe { libc::kill(child.id() as i32, libc::SIGINT); }
+    std::thread::sleep(Duration::from_millis(300));
+    assert!(child.try_wait().unwrap().is_none(), "the break should keep going");
+    stdin.write_all(b"advance 2\n").unwrap();
+    let out = child.wait_with_output().unwrap();
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert!(stderr.starts_with("Stopped by SIGINT: Break:"), "got: {}", stderr);
+    assert!(!String::from_utf8(out.stdout).unwrap().contains("Work 2/4"));
+}
+
+#[test]
+fn breaks_take_no_notes() {
+    // E2E: i does nothing on a break, so nothing is listed
+    let out = pomodoro(&["--work", "0:01", "--break", "1:00", "--plain"], "advance 1\nkeys i\ntype tea\nkeys q\n", None);
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(!stdout.contains("Interruption"), "got: {}", stdout);
+}
+
+#[test]
+fn pre_roll_counts_into_each_next_phase() {
+    // E2E: No pre-roll before the first phase, then one before each next
+    let out = pomodoro(&["--work", "0:02", "--break", "0:02", "--pre-roll", "3", "--plain"], "advance 2\nadvance 1\nadvance 2\nkeys q\n", None);
+    assert!(out.status.success());
+
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let pre_roll = stdout.find("0:03  Break starts in").expect("Pre-roll not shown");
+    assert!(!stdout[..pre_roll].contains("starts in"));
+    // Test: It counts down like any phase, then the break runs its full length
+    assert!(stdout[pre_roll..].contains("0:02  Break starts in"));
+    assert!(stdout[pre_roll..].contains("0:02  Break\n"));
+}
@@ -0,0 +1,39 @@
+// tests/quotes.rs
+mod common;
+use std::path::PathBuf;
+
+fn quotes_file(name: &str, text: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("timerterm-{}-{}", std::process::id(), name));
+    std::fs::write(&path, text).unwrap();
+    path
+}
+
+#[test]
+fn quote_is_shown_under_the_timer() {
+    // E2E: With a single quote that's the one shown, two rows under the status row
+    let path = quotes_file("one.txt", "# just the one\nSmall steps add up\n");
+    let mut cmd = common::timeterm();
+    // The harness draws frames even on a pipe
+    cmd.args(["--internal-test-harness", "1", "--no-log", "--quotes"]).arg(&path).env("TERM", "xterm")
+        .write_stdin("advance 1\n").timeout(std::time::Duration::from_secs(3));
+    let output = cmd.output().unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(output.status.success());
+
+    let out = String::from_utf8(output.stdout).unwrap();
+    let shown = out.split("\x1b[?1049l").next().unwrap();
+    let mut parser = vt100::Parser::new(24, 80, 0);
+    parser.process(shown.as_bytes());
+    let screen = parser.screen().contents();
+    assert_eq!(screen.lines().nth(17).unwrap().trim(), "Small steps add up");
+}
+
+#[test]
+fn empty_quotes_file_is_an_error() {
+    // E2E: Nothing to show is reported before the timer starts
+    let path = quotes_file("empty.txt", "\n# none yet\n");
+    let out = common::timeterm().args(["1", "--no-log", "--quotes"]).arg(&path).output().unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8(out.stderr).unwrap().contains("no quotes in file"));
+}
@@ -0,0 +1,43 @@
+// tests/repeat.rs
+mod common;
+use std::path::Path;
+use std::time::Duration;
+
+// Run with the fake clock and a fresh history log, returning stdout and the log
+fn repeat(cache: &Path, args: &[&str], script: &str) -> (String, String) {
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["--internal-test-harness", "--no-notify"]).args(args)
+        .env("XDG_CACHE_HOME", cache)
+        .write_stdin(script)
+        .timeout(Duration::from_secs(5))
+        .output().unwrap();
+    assert!(out.status.success());
+    let log = std::fs::read_to_string(cache.join("timerterm/timerterm.log")).unwrap();
+    std::fs::remove_dir_all(cache).unwrap();
+    (String::from_utf8(out.stdout).unwrap(), log)
+}
+
+#[test]
+fn repeat_runs_the_countdown_n_times() {
+    // E2E: Three 5s cycles, each logged, a bell after each, then it exits
+    let cache = std::env::temp_dir().join(format!("timerterm-repeat-{}", std::process::id()));
+    let (stdout, log) = repeat(&cache, &["5", "--repeat", "3"], "advance 5\nadvance 2\nadvance 3\nadvance 5\n");
+    assert_eq!(log.matches(": START duration=5s").count(), 3);
+    assert_eq!(log.matches(": COMPLETED").count(), 3);
+    assert_eq!(stdout.matches('\x07').count(), 3);
+    // Test: The counter under the time moves on with each cycle
+    let at = |counter: &str| stdout.find(counter).unwrap_or_else(|| panic!("no {}", counter));
+    assert!(at("Cycle 1 of 3") < at("Cycle 2 of 3") && at("Cycle 2 of 3") < at("Cycle 3 of 3"));
+    assert!(!stdout.contains("Cycle 4"));
+}
+
+#[test]
+fn repeat_forever_runs_until_quit() {
+    // E2E: Forever keeps going past any count, quitting cancels the current cycle
+    let cache = std::env::temp_dir().join(format!("timerterm-repeat-forever-{}", std::process::id()));
+    let script = "advance 5\n".repeat(5);
+    let (stdout, log) = repeat(&cache, &["5", "--repeat", "forever"], &script);
+    assert!(stdout.contains("Cycle 6"));
+    assert_eq!(log.matches(": COMPLETED").count(), 5);
+    assert!(log.trim_end().ends_with(": CANCELLED elapsed=0s"));
+}
@@ -0,0 +1,56 @@
+// tests/resize.rs
+// Needs a pty to resize
+#![cfg(unix)]
+mod common;
+use std::fs::File;
+use std::io::Read;
+use std::os::fd::FromRawFd;
+use std::process::Stdio;
+use std::thread;
+use std::time::Duration;
+
+fn set_size(fd: i32, cols: u16, rows: u16) {
+    let ws = libc::winsize { ws_row: rows, ws_col: cols, ws_xpixel: 0, ws_ypixel: 0 };
+    unsafe { libc::ioctl(fd, libc::TIOCSWINSZ, &ws); }
+}
+
+#[test]
+fn resize_recenters_digits() {
+    // E2E: Shrinking the terminal from 80x24 to 40x12 redraws the time centered in the new size
+    let (mut master, mut slave) = (0, 0);
+    let ret = unsafe { libc::openpty(&mut master, &mut slave, std::ptr::null_mut(), std::ptr::null(), std::ptr::null()) };
+    assert_eq!(ret, 0, "openpty failed");
+    set_size(master, 80, 24);
+
+    let slave_file = unsafe { File::from_raw_fd(slave) };
+    let mut child = common::spawn_command()
+        .args(["3", "--no-log"])
+        .stdin(Stdio::from(slave_file.try_clone().unwrap()))
+        .stdout(Stdio::from(slave_file))
+        .spawn()
+        .expect("Failed to start timeterm");
+    let reader = thread::spawn(move || {
+        // Read until EIO once the child has closed its end
+        let mut out = Vec::new();
+        let _ = unsafe { File::from_raw_fd(master) }.read_to_end(&mut out);
+        out
+    });
+
+    thread::sleep(Duration::from_millis(800));
+    set_size(master, 40, 12);
+    // Not the controlling terminal of the child, so the kernel won't send it
+    unsafe { libc::kill(child.id() as i32, libc::SIGWINCH); }
+    assert!(child.wait().unwrap().success());
+    let out = reader.join().unwrap();
+
+    // Everything after the resize's clear is drawn for 40x12
+    let out = String::from_utf8_lossy(&out);
+    let after = out.rsplit("\x1b[2J").next().unwrap();
+    let shown = after.split("\x1b[?1049l").next().unwrap();
+    let mut parser = vt100::Parser::new(12, 40, 0);
+    parser.process(shown.as_bytes());
+    let screen = parser.screen().contents();
+    let rows: Vec<&str> = screen.lines().collect();
+    // 5 digit rows centered in 12 start at row 4, "0:00" is 19 wide so 10 columns in
+    assert_eq!(rows[3].trim_end(), format!("{}###       ###   ###", " ".repeat(10)));
+}
@@ -0,0 +1,91 @@
+// tests/schedule.rs
+mod common;
+use std::path::Path;
+use std::time::Duration;
+
+// Run `schedule` from a file in `dir` with the fake clock, feeding `script`
+fn run(dir: &Path, schedule: &str, script: &str) -> std::process::Output {
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(dir.join("legs.toml"), schedule).unwrap();
+    let mut cmd = common::timeterm();
+    cmd.args(["--internal-test-harness", "run", "--no-log", "--no-notify"]).arg(dir.join("legs.toml"))
+        .write_stdin(script)
+        .timeout(Duration::from_secs(5))
+        .output().unwrap()
+}
+
+#[test]
+fn run_plays_segments_in_order_with_hooks() {
+    // E2E: Two segments, each starting its hook when it ends, then the totals
+    let dir = std::env::temp_dir().join(format!("timerterm-schedule-{}", std::process::id()));
+    let hook = |name: &str| format!("echo \\\"$TIMERTERM_NAME $TIMERTERM_DURATION\\\" > {}", dir.join(name).display());
+    let schedule = format!("name = \"Legs\"\n[warmup]\nlabel = \"Warm up\"\nduration = 3\nexec = \"{}\"\n[squats]\nduration = \"5s\"\nexec = \"{}\"\n",
+        hook("warmup.out"), hook("squats.out"));
+    let out = run(&dir, &schedule, "advance 3\nadvance 5\n");
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let at = |text: &str| stdout.find(text).unwrap_or_else(|| panic!("no {}", text));
+    assert!(at("Legs") < at("Warm up") && at("Warm up") < at("squats"));
+    assert!(stdout.contains("0:03 of 0:08 overall"));
+    assert!(stdout.ends_with("2 of 2 segments, 0:08 of 0:08\n"));
+    // Test: Hooks see the segment's label and length
+    assert_eq!(std::fs::read_to_string(dir.join("warmup.out")).unwrap(), "Warm up 3\n");
+    assert_eq!(std::fs::read_to_string(dir.join("squats.out")).unwrap(), "squats 5\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_reports_quitting_and_failed_hooks() {
+    // E2E: Quitting in the second segment still waits for the first one's hook
+    let dir = std::env::temp_dir().join(format!("timerterm-schedule-quit-{}", std::process::id()));
+    let out = run(&dir, "[warmup]\nduration = 3\nexec = \"exit 3\"\n[squats]\nduration = 5\n", "advance 3\nadvance 1\nkeys q\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8(out.stdout).unwrap().ends_with("1 of 2 segments, 0:04 of 0:08\n"));
+    assert!(String::from_utf8(out.stderr).unwrap().contains("exec for 'warmup' failed: exit status: 3"));
+    // Test: A bad file is reported before anything starts
+    let dir = std::env::temp_dir().join(format!("timerterm-schedule-bad-{}", std::process::id()));
+    let out = run(&dir, "[warmup]\nlabel = \"Warm up\"\n", "");
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8(out.stderr).unwrap().ends_with("legs.toml: [warmup] has no duration\n"));
+}
+
+#[test]
+fn alert_picks_what_ends_a_segment() {
+    // E2E: "none" ends the first quietly, and "bell" stands in for the last one's alarm
+    let dir = std::env::temp_dir().join(format!("timerterm-schedule-alert-{}", std::process::id()));
+    let out = run(&dir, "[warmup]\nduration = 1\nalert = \"none\"\n[squats]\nduration = 1\nalert = \"bell\"\n", "advance 1\nadvance 1\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(out.status.success());
+    assert_eq!(out.stdout.iter().filter(|&&b| b == 0x07).count(), 1);
+}
+
+#[test]
+#[cfg(unix)]
+fn signal_stops_hooks_instead_of_waiting() {
+    // E2E: SIGTERM in the second segment kills the first one's long hook and its children
+    let dir = std::env::temp_dir().join(format!("timerterm-schedule-signal-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let survived = dir.join("survived");
+    let schedule = format!("[warmup]\nduration = 1\nexec = \"(sleep 4; touch {}) & sleep 60\"\n[squats]\nduration = 30\n", survived.display());
+    std::fs::write(dir.join("legs.toml"), schedule).unwrap();
+    let mut child = common::spawn_command()
+        .args(["--internal-test-harness", "run", "--no-log", "--no-notify", "--plain"]).arg(dir.join("legs.toml"))
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn().unwrap();
+    use std::io::Write;
+    child.stdin.as_mut().unwrap().write_all(b"advance 1\n").unwrap();
+    std::thread::sleep(Duration::from_millis(500));
+    let started = std::time::Instant::now();
+    unsafe { libc::kill(child.id() as i32, libc::SIGTERM); }
+    let out = child.wait_with_output().unwrap();
+    assert!(started.elapsed() < Duration::from_secs(4), "shouldn't wait for the hook");
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert!(stderr.starts_with("exec for 'warmup' was still running, killed it\nStopped by SIGTERM: squats:"), "{}", stderr);
+    std::thread::sleep(Duration::from_secs(4).saturating_sub(started.elapsed()) + Duration::from_millis(500));
+    assert!(!survived.exists(), "the hook's children should be killed too");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,47 @@
+// tests/screenshot.rs
+mod common;
+use std::path::PathBuf;
+
+fn out_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("timerterm-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn screenshot_saves_final_screen_as_text() {
+    // E2E: The text dump is the finished 0:00 frame, centered as on screen
+    let path = out_path("final.txt");
+    let mut cmd = common::timeterm();
+    cmd.args(["1", "--no-log", "--screenshot"]).arg(&path).timeout(std::time::Duration::from_secs(3));
+    cmd.assert().success();
+    let text = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    // Not a terminal, so the 80x24 fallback size with digits on rows 10-14
+    let rows: Vec<&str> = text.lines().collect();
+    assert_eq!(rows.len(), 24);
+    assert_eq!(rows[9].trim(), "###       ###   ###");
+    assert!(!text.contains('\x1b'));
+}
+
+#[test]
+#[cfg(feature = "png")]
+fn screenshot_saves_png() {
+    // E2E: A .png path gets an image instead
+    let path = out_path("final.png");
+    let mut cmd = common::timeterm();
+    cmd.args(["1", "--no-log", "--screenshot"]).arg(&path).timeout(std::time::Duration::from_secs(3));
+    cmd.assert().success();
+    let png = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+}
+
+#[test]
+#[cfg(not(feature = "png"))]
+fn png_screenshot_needs_feature() {
+    // E2E: Without the feature a .png path fails before the timer starts
+    let path = out_path("never.png");
+    let out = common::timeterm().args(["1", "--screenshot"]).arg(&path).output().unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    assert!(out.stdout.is_empty());
+    assert!(String::from_utf8(out.stderr).unwrap().contains("need the png feature"));
+}
@@ -0,0 +1,33 @@
+// tests/serial.rs
+// A plain file stands in for the serial port, it gets the same lines
+mod common;
+use std::time::Duration;
+
+#[test]
+fn serial_gets_a_line_per_second_shown() {
+    // E2E: Each second the countdown shows goes out once, in serial.format
+    let dir = std::env::temp_dir().join(format!("timeterm-serial-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("timerterm")).unwrap();
+    std::fs::write(dir.join("timerterm").join("config.toml"), "[serial]\nformat = \"T{secs} {time}\"\n").unwrap();
+    let port = dir.join("port");
+    std::fs::write(&port, "").unwrap();
+    let out = common::timeterm().env("XDG_CONFIG_HOME", &dir)
+        .args(["--internal-test-harness", "3", "--plain", "--no-notify", "--no-log", "--serial", port.to_str().unwrap()])
+        .write_stdin("keys m\nadvance 1\nadvance 1\nadvance 1\n")
+        .timeout(Duration::from_secs(5)).output().unwrap();
+    assert!(out.status.success());
+    let sent = std::fs::read_to_string(&port).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    // Test: Muting redraws with a note, but the time didn't change
+    assert_eq!(sent, "T3 0:03\nT2 0:02\nT1 0:01\nT0 0:00\n");
+}
+
+#[test]
+fn missing_serial_device_is_reported() {
+    // E2E: A device that isn't there stops it before the timer starts
+    let out = common::timeterm()
+        .args(["1", "--no-notify", "--serial", "/nonexistent/ttyUSB0"])
+        .timeout(Duration::from_secs(5)).output().unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8(out.stderr).unwrap().starts_with("Couldn't open --serial /nonexistent/ttyUSB0: "));
+}
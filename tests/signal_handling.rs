@@ -1,15 +1,13 @@
 // tests/signal_handling.rs
-use assert_cmd::Command;
+#![cfg(unix)]
+mod common;
 use std::time::Duration;
 use std::thread;
 
 #[test]
 fn test_ctrl_c_restores_terminal() {
     // 1. Start timeterm process in background
-    let cmd = Command::cargo_bin("timeterm").unwrap();
-
-    let mut child = std::process::Command::new(cmd.get_program())
-        .args(cmd.get_args())
+    let mut child = common::spawn_command()
         .spawn()
         .expect("Failed to start timeterm");
 
@@ -24,7 +22,6 @@ fn test_ctrl_c_restores_terminal() {
     }
 
     // 4. Send SIGINT signal (Ctrl+C)
-    #[cfg(unix)]
     {
         let pid = child.id();
         unsafe {
@@ -38,3 +35,28 @@ fn test_ctrl_c_restores_terminal() {
     // 6. Verify clean exit (exit code 0 means clean shutdown)
     assert!(output.status.success(), "Process should exit cleanly on SIGINT");
 }
+
+#[test]
+fn sigterm_and_sighup_restore_terminal_and_summarize() {
+    // E2E: Either signal leaves the alt screen, exits cleanly and reports progress
+    for (sig, name) in [(libc::SIGTERM, "SIGTERM"), (libc::SIGHUP, "SIGHUP")] {
+        // The harness draws frames on a pipe, its stdin held open keeps it running
+        let child = common::spawn_command()
+            .args(["--internal-test-harness", "30", "--name", "tea"])
+            .env("TERM", "xterm")
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .expect("Failed to start timeterm");
+        thread::sleep(Duration::from_millis(1200));
+        unsafe { libc::kill(child.id() as i32, sig); }
+
+        let output = child.wait_with_output().expect("Failed to wait for process");
+        assert!(output.status.success(), "{} should exit cleanly", name);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.ends_with("\x1b[?25h\x1b[?1049l"), "{} should restore the terminal", name);
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert_eq!(stderr, format!("Stopped by {}: tea: 0:00 of 0:30\n", name));
+    }
+}
@@ -0,0 +1,43 @@
+// tests/sounds.rs
+mod common;
+use std::time::Duration;
+
+#[test]
+fn sounds_add_list_and_use_by_name() {
+    // E2E: A file added to the library is listed and plays as --sound NAME
+    let dir = std::env::temp_dir().join(format!("timeterm-sounds-e2e-{}", std::process::id()));
+    let data = dir.join("data");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("chime.wav"), b"RIFF").unwrap();
+    let sounds = |args: &[&str]| {
+        let out = common::timeterm().env("XDG_DATA_HOME", &data).arg("sounds").args(args)
+            .timeout(Duration::from_secs(5)).output().unwrap();
+        (out.status.code(), String::from_utf8_lossy(&out.stdout).into_owned(), String::from_utf8_lossy(&out.stderr).into_owned())
+    };
+    assert_eq!(sounds(&["list"]).1, "No sounds, add some with: timerterm sounds add FILE\n");
+    assert_eq!(sounds(&["add", dir.join("chime.wav").to_str().unwrap()]).1, "Added 'chime', use it with --sound chime\n");
+    assert_eq!(sounds(&["list"]).1, "chime\n");
+    assert!(data.join("timerterm/sounds/chime.wav").is_file());
+    // Test: Adding it again or playing one that isn't there fails
+    assert_eq!(sounds(&["add", dir.join("chime.wav").to_str().unwrap()]).0, Some(1));
+    let (code, _, stderr) = sounds(&["play", "gong"]);
+    assert_eq!((code, stderr.as_str()), (Some(1), "No sound called 'gong', see: timerterm sounds list\n"));
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        // Test: A fake paplay on PATH gets the library file
+        use std::os::unix::fs::PermissionsExt;
+        let (calls, player) = (dir.join("calls"), dir.join("paplay"));
+        std::fs::write(&player, format!("#!/bin/sh\necho \"$@\" >> {}\n", calls.display())).unwrap();
+        std::fs::set_permissions(&player, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let path = format!("{}:{}", dir.display(), std::env::var("PATH").unwrap_or_default());
+        let out = common::timeterm().env("XDG_DATA_HOME", &data).env("PATH", path)
+            .args(["1", "--no-notify", "--sound", "chime", "--volume", "50"])
+            .timeout(Duration::from_secs(5)).output().unwrap();
+        assert!(out.status.success());
+        std::thread::sleep(Duration::from_millis(300));
+        let library = data.join("timerterm/sounds/chime.wav");
+        assert_eq!(std::fs::read_to_string(&calls).unwrap(), format!("--volume=32768 {}\n", library.display()));
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,32 @@
+// tests/stats.rs
+mod common;
+use std::time::Duration;
+
+#[test]
+fn stats_totals_runs_by_project() {
+    // E2E: Runs started in a directory are counted under it, HOME shown as ~,
+    // and the one with an estimate is checked against it
+    let cache = std::env::temp_dir().join(format!("timerterm-stats-{}", std::process::id()));
+    let runs: [(&str, &[&str]); 2] = [("advance 2\n", &["--estimate", "2"]), ("advance 1\nkeys q\n", &[])];
+    for (script, estimate) in runs {
+        common::timeterm().args(["--internal-test-harness", "--no-notify", "--silent", "--plain", "2"]).args(estimate)
+            .env("XDG_CACHE_HOME", &cache)
+            .write_stdin(script)
+            .timeout(Duration::from_secs(5))
+            .assert().success();
+    }
+    let home = common::scratch().canonicalize().unwrap(); // Where they ran
+    let out = common::timeterm().args(["stats", "--by-project"]).env("XDG_CACHE_HOME", &cache).env("HOME", &home)
+        .timeout(Duration::from_secs(5)).output().unwrap();
+    std::fs::remove_dir_all(&cache).unwrap();
+    assert!(out.status.success());
+    assert_eq!(String::from_utf8(out.stdout).unwrap(),
+        "2 runs, 1 completed, 1 cancelled: 0:03 in all\n1 run estimated: median 1.00x the estimate, 1 within ±10%\n\n~  0:03, 2 runs\n");
+}
+
+#[test]
+fn stats_without_a_log_says_so() {
+    // E2E: No log yet isn't an error
+    let cache = std::env::temp_dir().join(format!("timerterm-stats-empty-{}", std::process::id()));
+    common::timeterm().arg("stats").env("XDG_CACHE_HOME", &cache).assert().success().stdout("No runs logged yet\n");
+}
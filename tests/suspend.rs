@@ -0,0 +1,65 @@
+// tests/suspend.rs
+// Reads process state from /proc
+#![cfg(target_os = "linux")]
+mod common;
+use std::process::{Child, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn spawn_timer(args: &[&str]) -> Child {
+    common::spawn_command()
+        .args(args)
+        .arg("--no-log")
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to start timeterm")
+}
+
+fn signal(child: &Child, sig: i32) {
+    unsafe { libc::kill(child.id() as i32, sig); }
+}
+
+// 'T' in /proc/PID/stat means stopped
+fn is_stopped(child: &Child) -> bool {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", child.id())).unwrap();
+    stat.rsplit_once(") ").unwrap().1.starts_with('T')
+}
+
+#[test]
+fn ctrl_z_restores_terminal_and_stops() {
+    // E2E: SIGTSTP leaves the alt screen before stopping, SIGCONT takes it back
+    // The harness draws frames on a pipe, closing its stdin at the end quits
+    let child = common::spawn_command()
+        .args(["--internal-test-harness", "2", "--no-log"])
+        .env("TERM", "xterm")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to start timeterm");
+    thread::sleep(Duration::from_millis(300));
+    signal(&child, libc::SIGTSTP);
+    thread::sleep(Duration::from_millis(300));
+    assert!(is_stopped(&child), "Ctrl+Z should stop the process");
+    signal(&child, libc::SIGCONT);
+
+    let out = child.wait_with_output().unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert_eq!(stdout.matches("\x1b[?1049l").count(), 2, "left the alt screen on stop and on exit");
+    assert_eq!(stdout.matches("\x1b[?1049h").count(), 2, "entered it on start and on fg");
+}
+
+#[test]
+fn pause_on_suspend_skips_stopped_time() {
+    // E2E: 1.5s stopped doesn't count, so a 1s timer is still running after fg
+    let start = Instant::now();
+    let mut child = spawn_timer(&["1", "--pause-on-suspend"]);
+    thread::sleep(Duration::from_millis(300));
+    signal(&child, libc::SIGTSTP);
+    thread::sleep(Duration::from_millis(1500));
+    signal(&child, libc::SIGCONT);
+    thread::sleep(Duration::from_millis(200));
+    assert!(child.try_wait().unwrap().is_none(), "Timer should still have time left");
+    assert!(child.wait().unwrap().success());
+    assert!(start.elapsed() >= Duration::from_millis(2400));
+}
@@ -0,0 +1,44 @@
+// tests/test_harness.rs
+mod common;
+use std::time::{Duration, Instant};
+
+// Run with the fake clock, feeding `script` as harness commands
+fn harness(args: &[&str], script: &str) -> std::process::Output {
+    let mut cmd = common::timeterm();
+    cmd.arg("--internal-test-harness").args(["--no-log", "--no-notify"]).args(args)
+        .write_stdin(script)
+        .timeout(Duration::from_secs(5))
+        .output().unwrap()
+}
+
+#[test]
+fn fake_clock_finishes_long_countdown_at_once() {
+    // E2E: 25 minutes pass in three commands, well before a real second does
+    let start = Instant::now();
+    let out = harness(&["25:00"], "advance 1\nadvance 12:00\nadvance 12:59\n");
+    assert!(start.elapsed() < Duration::from_secs(2));
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    // Test: A frame per step, ending on the bell once the deadline is reached
+    let mut parser = vt100::Parser::new(24, 80, 0);
+    for (step, time) in stdout.split("\x1b[10;1H").skip(1).zip(["25:00", "24:59", "12:59", "0:00"]) {
+        parser.process(format!("\x1b[10;1H{}", step).as_bytes());
+        let big = timeterm::render::big_text(time);
+        assert_eq!(parser.screen().contents().lines().nth(9).unwrap().trim(), big[0].trim(), "at {}", time);
+    }
+    assert_eq!(stdout.matches('\x07').count(), 1);
+}
+
+#[test]
+fn keys_and_time_interleave_in_order() {
+    // E2E: Time passed while paused doesn't count, running out of commands quits
+    let shot = std::env::temp_dir().join(format!("timerterm-{}-harness.txt", std::process::id()));
+    let shot_arg = shot.to_str().unwrap();
+    let out = harness(&["--screenshot", shot_arg, "1:00"], "advance 10\nkeys  \nadvance 5:00\nkeys  \nadvance 5\n");
+    assert!(out.status.success());
+    let text = std::fs::read_to_string(&shot).unwrap();
+    std::fs::remove_file(&shot).unwrap();
+    let rows: Vec<&str> = text.lines().map(str::trim).collect();
+    let big = timeterm::render::big_text("0:45");
+    assert_eq!(rows[9..14], big.iter().map(|l| l.trim()).collect::<Vec<_>>()[..]);
+}
@@ -0,0 +1,56 @@
+// tests/timer_display.rs
+mod common;
+
+const ENTER_ALT_SCREEN: &str = "\x1b[?1049h";
+const LEAVE_ALT_SCREEN: &str = "\x1b[?1049l";
+
+// Frames of a run through the harness, which draws them even on a pipe
+fn run_timer(args: &[&str], input: &str) -> String {
+    let mut cmd = common::timeterm();
+    let out = cmd.arg("--internal-test-harness").args(args).env("TERM", "xterm").write_stdin(input)
+        .timeout(std::time::Duration::from_secs(4)).output().unwrap();
+    assert!(out.status.success());
+    String::from_utf8(out.stdout).unwrap()
+}
+
+#[test]
+fn takes_over_and_restores_screen() {
+    // E2E: Output should enter the alternate screen, hide the cursor,
+    // then show the cursor and leave the alternate screen on exit
+    let out = run_timer(&["1"], "advance 1\n");
+    assert!(out.starts_with(ENTER_ALT_SCREEN));
+    assert!(out.contains("\x1b[?25l"));
+    assert!(out.ends_with(&format!("\x1b[?25h{}", LEAVE_ALT_SCREEN)));
+}
+
+#[test]
+fn draws_big_digit_countdown() {
+    // E2E: The final frame on the alternate screen should be big ASCII "0:00"
+    let out = run_timer(&["1"], "advance 1\n");
+    let shown = out.split(LEAVE_ALT_SCREEN).next().unwrap();
+    // Non-tty stdout falls back to 80x24
+    let mut parser = vt100::Parser::new(24, 80, 0);
+    parser.process(shown.as_bytes());
+
+    let screen = parser.screen().contents();
+    let rows: Vec<&str> = screen.lines().collect();
+    // 5 digit rows centered in 24 rows start on row 10 (index 9)
+    assert_eq!(rows[9].trim(), "###       ###   ###");
+    assert_eq!(rows[10].trim(), "#   #  #  #   # #   #");
+    assert_eq!(rows[13].trim(), "###       ###   ###");
+}
+
+#[test]
+fn dual_layout_shows_elapsed_below() {
+    // E2E: remaining+elapsed draws elapsed small under the big remaining time
+    let out = run_timer(&["2", "--layout", "remaining+elapsed"], "advance 2\n");
+    let shown = out.split(LEAVE_ALT_SCREEN).next().unwrap();
+    let mut parser = vt100::Parser::new(24, 80, 0);
+    parser.process(shown.as_bytes());
+
+    let screen = parser.screen().contents();
+    let rows: Vec<&str> = screen.lines().collect();
+    // Big "0:00" on rows 10-14, blank row, then the detail line on row 16
+    assert_eq!(rows[9].trim(), "###       ###   ###");
+    assert_eq!(rows[15].trim(), "0:02 elapsed");
+}
@@ -0,0 +1,39 @@
+// tests/today.rs
+mod common;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use timeterm::{log, TimerEvent};
+
+#[test]
+fn today_draws_runs_from_the_log() {
+    // E2E: Runs logged today are summed by label, older days are left out
+    let cache = std::env::temp_dir().join(format!("timerterm-today-{}", std::process::id()));
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
+    let secs = Duration::from_secs;
+    let lines = [
+        log::format_entry(now - 2 * 86400, 1, &TimerEvent::Started { duration: secs(60), elapsed: secs(0), estimate: None, label: Some("Old".into()), tag: None, dir: None }),
+        log::format_entry(now, 2, &TimerEvent::Started { duration: secs(60), elapsed: secs(0), estimate: None, label: Some("Pasta".into()), tag: None, dir: None }),
+        log::format_entry(now, 2, &TimerEvent::Finished { elapsed: secs(60) }),
+        log::format_entry(now, 3, &TimerEvent::Started { duration: secs(60), elapsed: secs(0), estimate: None, label: None, tag: None, dir: None }),
+    ];
+    std::fs::create_dir_all(cache.join("timerterm")).unwrap();
+    std::fs::write(cache.join("timerterm/timerterm.log"), lines.join("\n") + "\n").unwrap();
+    let mut cmd = common::timeterm();
+    let out = cmd.arg("today").env("XDG_CACHE_HOME", &cache).timeout(Duration::from_secs(5)).output().unwrap();
+    std::fs::remove_dir_all(&cache).unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    // Test: Not a terminal, so no colors
+    assert!(!stdout.contains('\x1b'));
+    assert!(stdout.contains("█ Pasta       0:00, 1 run\n"));
+    assert!(stdout.contains("█ (no label)  0:00, 1 run\n"));
+    assert!(stdout.ends_with("1 completed, 0 cancelled, 1 running  (█ ░ ▓)\n"));
+    assert!(!stdout.contains("Old"));
+}
+
+#[test]
+fn today_without_a_log_says_so() {
+    // E2E: No log yet isn't an error
+    let cache = std::env::temp_dir().join(format!("timerterm-today-empty-{}", std::process::id()));
+    let mut cmd = common::timeterm();
+    cmd.arg("today").env("XDG_CACHE_HOME", &cache).assert().success().stdout("No runs logged today\n");
+}
@@ -0,0 +1,33 @@
+// tests/until.rs
+mod common;
+use assert_cmd::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn timerterm() -> Command {
+    let mut cmd = common::timeterm();
+    cmd.args(["--no-log", "--no-notify"]).timeout(Duration::from_secs(5));
+    cmd
+}
+
+#[test]
+fn until_counts_down_to_clock_time() {
+    // E2E: 90 seconds from now in UTC rings after 1:31 of fake time, not a day
+    let at = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + 90;
+    let time = format!("{:02}:{:02}:{:02}Z", at / 3600 % 24, at / 60 % 60, at % 60);
+    let out = timerterm().arg("--internal-test-harness").args(["--until", &time])
+        .write_stdin("advance 1:00\nadvance 31\n")
+        .output().unwrap();
+    assert!(out.status.success());
+    assert_eq!(out.stdout.iter().filter(|&&b| b == b'\x07').count(), 1);
+}
+
+#[test]
+fn until_rejects_past_dates() {
+    // E2E: A date that's gone exits before the timer starts
+    let out = timerterm().args(["--until", "2000-01-01T00:00Z"]).output().unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&out.stderr), "--until '2000-01-01T00:00Z' has already passed\n");
+    // Test: And a malformed one is a usage error
+    let out = timerterm().args(["--until", "noon"]).output().unwrap();
+    assert_eq!(out.status.code(), Some(2));
+}
@@ -0,0 +1,58 @@
+// tests/world_clock.rs
+mod common;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// A TZDIR holding Asia/Kolkata as a version 2 file with no transitions, just
+// its TZ rule, and a config showing it
+fn setup() -> (PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("timerterm-world-clock-{}", std::process::id()));
+    let tzdir = dir.join("zoneinfo");
+    std::fs::create_dir_all(tzdir.join("Asia")).unwrap();
+    let mut tzif = Vec::new();
+    for _ in 0..2 {
+        tzif.extend(b"TZif2");
+        tzif.extend([0; 15]);
+        for count in [0u32, 0, 0, 0, 1, 4] { tzif.extend(count.to_be_bytes()); }
+        tzif.extend(19800i32.to_be_bytes());
+        tzif.extend([0, 0]);
+        tzif.extend(b"IST\0");
+    }
+    tzif.extend(b"\nIST-5:30\n");
+    std::fs::write(tzdir.join("Asia").join("Kolkata"), tzif).unwrap();
+    std::fs::create_dir_all(dir.join("timerterm")).unwrap();
+    std::fs::write(dir.join("timerterm").join("config.toml"), "[display]\nworld_clock = \"Asia/Kolkata\"\n").unwrap();
+    (dir, tzdir)
+}
+
+#[test]
+fn world_clock_is_shown_under_the_timer() {
+    // E2E: Each configured zone's time is drawn two rows under the status row
+    let (dir, tzdir) = setup();
+    let before = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let mut cmd = common::timeterm();
+    let out = cmd.args(["--internal-test-harness", "1", "--no-log"])
+        .env("XDG_CONFIG_HOME", &dir).env("TZDIR", &tzdir).env("TERM", "xterm")
+        .write_stdin("advance 1\n").timeout(Duration::from_secs(3))
+        .output().unwrap();
+    let after = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    assert!(out.status.success());
+    let out = String::from_utf8(out.stdout).unwrap();
+    let mut parser = vt100::Parser::new(24, 80, 0);
+    parser.process(out.split("\x1b[?1049l").next().unwrap().as_bytes());
+    let shown = parser.screen().contents().lines().nth(17).unwrap().trim().to_string();
+    let clock = |unix: u64| {
+        let local = (unix + 19800) % 86400;
+        format!("Kolkata {:02}:{:02}", local / 3600, local / 60 % 60)
+    };
+    assert!(shown == clock(before) || shown == clock(after), "{}", shown);
+
+    // Test: A zone that isn't there is an error up front
+    std::fs::remove_file(tzdir.join("Asia").join("Kolkata")).unwrap();
+    let out = common::timeterm().args(["1", "--no-log"])
+        .env("XDG_CONFIG_HOME", &dir).env("TZDIR", &tzdir)
+        .timeout(Duration::from_secs(3)).output().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8(out.stderr).unwrap().starts_with("Couldn't set up the world clock: no time zone Asia/Kolkata in "));
+}